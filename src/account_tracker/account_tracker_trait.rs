@@ -1,5 +1,5 @@
 use crate::{
-    prelude::{MarketState, Mon, QuoteCurrency, Side, UserBalances},
+    prelude::{MarketState, Mon, QuoteCurrency, Side, TimestampNs, UserBalances},
     types::{LimitOrder, MarginCurrency, MarketOrder, NewOrder, UserOrderIdT},
 };
 
@@ -14,9 +14,17 @@ where
     BaseOrQuote: MarginCurrency<I, D>,
     UserOrderId: UserOrderIdT,
 {
+    /// Reset all tracked statistics, using `wallet_balance` as the new starting balance.
+    fn reset(&mut self, wallet_balance: BaseOrQuote);
+
     /// Update with newest market info.
     fn update(&mut self, market_state: &MarketState<I, D>);
 
+    /// Log the timestamp of the newest market update.
+    /// Unlike `update`, it is called for every market update,
+    /// regardless of `Config::account_tracker_update_every_n_ticks`.
+    fn log_timestamp(&mut self, timestamp_ns: TimestampNs);
+
     /// Process information about the user balances.
     fn sample_user_balances(
         &mut self,
@@ -24,6 +32,16 @@ where
         mid_price: QuoteCurrency<I, D>,
     );
 
+    /// Process information about the order book microstructure,
+    /// namely the resting volume on the bid and the ask side.
+    /// `position_open` indicates whether the user is currently holding a position.
+    fn sample_market_microstructure(
+        &mut self,
+        bid_volume: BaseOrQuote::PairedCurrency,
+        ask_volume: BaseOrQuote::PairedCurrency,
+        position_open: bool,
+    );
+
     /// Log a `LimitOrder` submission event.
     fn log_limit_order_submission(
         &mut self,
@@ -52,4 +70,7 @@ where
         price: QuoteCurrency<I, D>,
         quantity: BaseOrQuote::PairedCurrency,
     );
+
+    /// Log the fee charged for a trade, which is negative for maker rebates.
+    fn log_fee(&mut self, fee: BaseOrQuote);
 }
@@ -1,7 +1,14 @@
-use std::fmt::Display;
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+};
 
 use getset::CopyGetters;
 use num_traits::Zero;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use sliding_features::{
     pure_functions::Echo,
     rolling::{Drawdown, LnReturn, WelfordRolling},
@@ -9,17 +16,34 @@ use sliding_features::{
 };
 
 use crate::{
-    account_tracker::AccountTracker,
+    account_tracker::{
+        cornish_fisher_quantile, statistical_moments, AccountTracker, ReturnsSource,
+    },
     prelude::{MarketState, Mon, QuoteCurrency, Side, UserBalances},
     types::{
         Currency, LimitOrder, MarginCurrency, MarketOrder, NewOrder, TimestampNs, UserOrderIdT,
     },
+    utils::min,
 };
 
 const DAILY_NS: i64 = 86_400_000_000_000;
 
+#[cfg(feature = "quantiles")]
+fn new_quantogram() -> quantogram::Quantogram {
+    quantogram::QuantogramBuilder::new()
+        .with_error(0.001)
+        .build()
+}
+
 /// Keep track of Account performance statistics.
-#[derive(Debug, CopyGetters)]
+///
+/// The rolling statistics are not serialized,
+/// but replayed from the sampled user balances and prices when loading a saved tracker.
+#[derive(Debug, CopyGetters, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "QuoteCurrency<I, D>: Serialize, BaseOrQuote: Serialize, BaseOrQuote::PairedCurrency: Serialize",
+    deserialize = "QuoteCurrency<I, D>: Deserialize<'de>, BaseOrQuote: Deserialize<'de>, BaseOrQuote::PairedCurrency: Deserialize<'de>"
+))]
 pub struct FullAccountTracker<I, const D: u8, BaseOrQuote>
 where
     I: Mon<D>,
@@ -57,32 +81,119 @@ where
     #[getset(get_copy = "pub")]
     sell_volume: BaseOrQuote,
 
+    /// The cumulative fees charged for all trades, net of maker rebates.
+    #[getset(get_copy = "pub")]
+    cumulative_fees: BaseOrQuote,
+
+    /// The number of market updates the tracker processed.
+    #[getset(get_copy = "pub")]
+    num_updates: u64,
+
+    /// The signed net position quantity derived from the logged trades.
+    net_position_qty: BaseOrQuote::PairedCurrency,
+    /// The value of `num_updates` when the current position was opened.
+    position_opened_at_update: u64,
+    /// The total number of updates elapsed across all closed positions.
+    sum_trade_duration_ticks: u64,
+    /// The number of open-close cycles of the position.
+    num_closed_trades: u64,
+    /// The average entry price of the position derived from the logged trades.
+    position_entry_price: QuoteCurrency<I, D>,
+    /// The realized profit and loss of the current open-close cycle, excluding fees.
+    current_trade_pnl: BaseOrQuote,
+    /// The total profit of the winning and loss of the losing open-close cycles.
+    sum_wins: BaseOrQuote,
+    sum_losses: BaseOrQuote,
+    num_wins: u64,
+    num_losses: u64,
+    /// The realized profit and loss of each open-close cycle, excluding fees.
+    trade_pnls: Vec<f64>,
+    /// The realized profit and loss of all logged trades, excluding fees.
+    total_rpnl: BaseOrQuote,
+    /// The number of `update` calls where `total_rpnl` plus the unrealized pnl was positive.
+    num_updates_in_profit: u64,
+    /// The realized profit and loss of the logged trades bucketed by the nanosecond timestamp
+    /// of the start of the calendar day of the most recent market update.
+    daily_realized_pnl: Vec<(u64, f64)>,
+    /// The timestamp of the most recent market update, even if `update` was not called for it.
+    ts_current: TimestampNs,
+
     price_first: QuoteCurrency<I, D>,
     price_last: QuoteCurrency<I, D>,
     ts_first: TimestampNs,
     ts_last: TimestampNs,
+    /// The number of occurrences of each positive gap in nanoseconds between consecutive updates,
+    /// used for `num_sessions`.
+    update_gaps_ns: HashMap<u64, usize>,
 
     /// Keep track of natural logarithmic returns of users funds.
+    #[serde(skip)]
     user_balances_ln_return: LnReturn<f32, Echo<f32>>,
+    #[serde(skip)]
     drawdown_user_balances: Drawdown<f32, Echo<f32>>, // Drawdown of realized user balances.
-    drawdown_market: Drawdown<f32, Echo<f32>>,        // Drawdown of the market.
+    #[serde(skip)]
+    drawdown_market: Drawdown<f32, Echo<f32>>, // Drawdown of the market.
+    #[serde(skip)]
     user_balances_ln_return_stats: WelfordRolling<f32, Echo<f32>>, // Used for `sharpe` and `kelly_leverage`
+    #[serde(skip)]
     user_balances_neg_ln_return_stats: WelfordRolling<f32, Echo<f32>>, // Used for `sortino`
+    /// The sampled ln returns of the user balances, used for `modified_sharpe`.
+    #[serde(skip)]
+    user_balances_ln_returns: Vec<f64>,
+    /// The sampled ln returns of the starting wallet balance plus the realized profit and loss
+    /// net of fees, used for `realized_sharpe`.
+    realized_balance_ln_returns: Vec<f64>,
+    /// The realized balance at the most recent sample.
+    last_realized_balance: Option<f64>,
 
     /// last sum of all user balances.
     last_balance_sum: BaseOrQuote,
+    /// The sampled sums of the user balances and mid prices, used to restore the rolling statistics.
+    sampled_balances_and_prices: Vec<(f32, f32)>,
+
+    /// Sum of the sampled order book imbalances while a position was held.
+    order_book_imbalance_sum: f64,
+    /// The number of sampled order book imbalances while a position was held.
+    num_order_book_imbalance_samples: usize,
 
     /// Keeps track of ln return distribution of user balances and can compute the quantiles needed for certain risk metrics.
     #[cfg(feature = "quantiles")]
+    #[serde(skip, default = "new_quantogram")]
     quantogram_user_balances_ln_returns: quantogram::Quantogram,
 
     /// Keeps track of the markets logarithmic return at the sampling interval.
-    #[cfg(feature = "quantiles")]
+    #[serde(skip)]
     sampled_market_ln_return: LnReturn<f32, Echo<f32>>,
+    /// Used as the risk free rate in `sharpe_annualized`.
+    #[serde(skip)]
+    sampled_market_ln_return_stats: WelfordRolling<f32, Echo<f32>>,
+    /// The active ln returns of the user balances over the market, used for `information_ratio`.
+    #[serde(skip)]
+    active_ln_return_stats: WelfordRolling<f32, Echo<f32>>,
 
     /// Keeps track of ln return distribution of the market and can compute the quantiles needed for certain risk metrics.
     #[cfg(feature = "quantiles")]
+    #[serde(skip, default = "new_quantogram")]
     quantogram_market_ln_returns: quantogram::Quantogram,
+
+    /// The day and hour of the most recently sampled user balances.
+    last_sampled_day: Option<i64>,
+    last_sampled_hour: Option<i64>,
+    /// The number of sampled returns spanning distinct days and hours.
+    num_daily_samples: usize,
+    num_hourly_samples: usize,
+
+    /// The highest sampled sum of user balances, used for the drawdowns of the `pain_index`.
+    balances_high_water_mark: f64,
+    /// The sum of the drawdowns at the first sample within each distinct day and hour.
+    sum_daily_drawdowns: f64,
+    sum_hourly_drawdowns: f64,
+    /// The user balances at the first sample within the most recently sampled day and hour.
+    daily_period_balance: Option<f64>,
+    hourly_period_balance: Option<f64>,
+    /// The sum of the ln returns between the first samples of consecutive days and hours.
+    sum_daily_ln_returns: f64,
+    sum_hourly_ln_returns: f64,
 }
 
 /// TODO: create its own `risk` crate out of these implementations for better
@@ -112,35 +223,127 @@ where
 
             buy_volume: BaseOrQuote::zero(),
             sell_volume: BaseOrQuote::zero(),
+            cumulative_fees: BaseOrQuote::zero(),
+            num_updates: 0,
+
+            net_position_qty: BaseOrQuote::PairedCurrency::zero(),
+            position_opened_at_update: 0,
+            sum_trade_duration_ticks: 0,
+            num_closed_trades: 0,
+            position_entry_price: QuoteCurrency::zero(),
+            current_trade_pnl: BaseOrQuote::zero(),
+            sum_wins: BaseOrQuote::zero(),
+            sum_losses: BaseOrQuote::zero(),
+            num_wins: 0,
+            num_losses: 0,
+            trade_pnls: Vec::new(),
+            total_rpnl: BaseOrQuote::zero(),
+            num_updates_in_profit: 0,
+            daily_realized_pnl: Vec::new(),
+            ts_current: TimestampNs::from(0),
 
             price_first: QuoteCurrency::zero(),
             price_last: QuoteCurrency::zero(),
             ts_first: TimestampNs::from(0),
             ts_last: TimestampNs::from(0),
+            update_gaps_ns: HashMap::new(),
 
             user_balances_ln_return: LnReturn::default(),
             drawdown_user_balances: Drawdown::default(),
             drawdown_market: Drawdown::default(),
             user_balances_ln_return_stats: WelfordRolling::default(),
             user_balances_neg_ln_return_stats: WelfordRolling::default(),
+            user_balances_ln_returns: Vec::new(),
+            realized_balance_ln_returns: Vec::new(),
+            last_realized_balance: None,
 
             last_balance_sum: BaseOrQuote::zero(),
+            sampled_balances_and_prices: Vec::new(),
 
-            #[cfg(feature = "quantiles")]
-            quantogram_user_balances_ln_returns: quantogram::QuantogramBuilder::new()
-                .with_error(0.001)
-                .build(),
+            order_book_imbalance_sum: 0.0,
+            num_order_book_imbalance_samples: 0,
 
             #[cfg(feature = "quantiles")]
+            quantogram_user_balances_ln_returns: new_quantogram(),
+
             sampled_market_ln_return: LnReturn::default(),
+            sampled_market_ln_return_stats: WelfordRolling::default(),
+            active_ln_return_stats: WelfordRolling::default(),
 
             #[cfg(feature = "quantiles")]
-            quantogram_market_ln_returns: quantogram::QuantogramBuilder::new()
-                .with_error(0.001)
-                .build(),
+            quantogram_market_ln_returns: new_quantogram(),
+
+            last_sampled_day: None,
+            last_sampled_hour: None,
+            num_daily_samples: 0,
+            num_hourly_samples: 0,
+            balances_high_water_mark: Into::<f64>::into(starting_wb),
+            sum_daily_drawdowns: 0.0,
+            sum_hourly_drawdowns: 0.0,
+            daily_period_balance: None,
+            hourly_period_balance: None,
+            sum_daily_ln_returns: 0.0,
+            sum_hourly_ln_returns: 0.0,
         }
     }
 
+    /// Update the rolling statistics with a sampled sum of the user balances and the mid price.
+    fn update_rolling_stats(&mut self, balance_sum: f32, mid_price: f32) {
+        self.drawdown_user_balances.update(balance_sum);
+
+        self.user_balances_ln_return.update(balance_sum);
+        if let Some(ln_ret) = self.user_balances_ln_return.last() {
+            self.user_balances_ln_return_stats.update(ln_ret);
+            if ln_ret < 0.0 {
+                self.user_balances_neg_ln_return_stats.update(ln_ret);
+            }
+            self.user_balances_ln_returns.push(ln_ret as f64);
+            #[cfg(feature = "quantiles")]
+            self.quantogram_user_balances_ln_returns.add(ln_ret as f64);
+        }
+
+        self.sampled_market_ln_return.update(mid_price);
+        if let Some(market_ln_ret) = self.sampled_market_ln_return.last() {
+            self.sampled_market_ln_return_stats.update(market_ln_ret);
+            #[cfg(feature = "quantiles")]
+            self.quantogram_market_ln_returns.add(market_ln_ret as f64);
+        }
+        if let (Some(ln_ret), Some(market_ln_ret)) = (
+            self.user_balances_ln_return.last(),
+            self.sampled_market_ln_return.last(),
+        ) {
+            self.active_ln_return_stats.update(ln_ret - market_ln_ret);
+        }
+    }
+
+    /// Save the tracker as JSON to `path`, e.g. to checkpoint a long backtest.
+    pub fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()>
+    where
+        QuoteCurrency<I, D>: Serialize,
+        BaseOrQuote: Serialize,
+        BaseOrQuote::PairedCurrency: Serialize,
+    {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+
+    /// Load a tracker which was saved with `save_to_path`,
+    /// replaying the sampled user balances and prices into the rolling statistics.
+    pub fn load_from_path<P: AsRef<Path>>(path: P) -> std::io::Result<Self>
+    where
+        QuoteCurrency<I, D>: DeserializeOwned,
+        BaseOrQuote: DeserializeOwned,
+        BaseOrQuote::PairedCurrency: DeserializeOwned,
+    {
+        let file = File::open(path)?;
+        let mut tracker: Self = serde_json::from_reader(BufReader::new(file))?;
+        for (balance_sum, mid_price) in tracker.sampled_balances_and_prices.clone() {
+            tracker.update_rolling_stats(balance_sum, mid_price);
+        }
+        Ok(tracker)
+    }
+
     /// Would be the return of buy and hold strategy
     pub fn buy_and_hold_return(&self) -> BaseOrQuote {
         let qty =
@@ -166,12 +369,18 @@ where
     /// Return the ratio of filled limit orders vs number of submitted limit
     /// orders
     pub fn limit_order_fill_ratio(&self) -> f32 {
+        if self.num_submitted_limit_orders == 0 {
+            return 0.0;
+        }
         self.num_fully_filled_limit_orders as f32 / self.num_submitted_limit_orders as f32
     }
 
     /// Return the ratio of limit order cancellations vs number of submitted
     /// limit orders
     pub fn limit_order_cancellation_ratio(&self) -> f32 {
+        if self.num_submitted_limit_orders == 0 {
+            return 0.0;
+        }
         self.num_cancelled_limit_orders as f32 / self.num_submitted_limit_orders as f32
     }
 
@@ -180,6 +389,16 @@ where
         self.buy_volume + self.sell_volume
     }
 
+    /// The blended fee rate, being the `cumulative_fees` relative to the `turnover`.
+    /// Is zero if nothing was traded yet.
+    pub fn effective_fee_rate(&self) -> f64 {
+        let turnover = self.turnover();
+        if turnover.is_zero() {
+            return 0.0;
+        }
+        Into::<f64>::into(self.cumulative_fees) / Into::<f64>::into(turnover)
+    }
+
     /// The drawdown of user balances.
     pub fn drawdown_user_balances(&self) -> f32 {
         self.drawdown_user_balances.last().unwrap_or(0.0)
@@ -209,11 +428,129 @@ where
         Some(Into::<f64>::into(self.buy_volume / total_volume) as f32)
     }
 
+    /// The average realized profit and loss per closed or flipped position in the margin currency,
+    /// excluding fees. Is zero if no position has been closed yet.
+    pub fn expected_value_per_trade(&self) -> f64 {
+        if self.num_closed_trades == 0 {
+            return 0.0;
+        }
+        Into::<f64>::into(self.total_rpnl) / self.num_closed_trades as f64
+    }
+
+    /// The realized profit and loss of the closed or flipped positions after subtracting
+    /// the `cumulative_fees`, separating the gross trading result from its costs.
+    /// Funding payments are not tracked and therefore not included.
+    pub fn net_pnl(&self) -> f64 {
+        Into::<f64>::into(self.total_rpnl - self.cumulative_fees)
+    }
+
+    /// The number of distinct trading sessions, being the contiguous runs of market updates
+    /// separated by gaps of more than `gap_ns` between their timestamps.
+    /// Is zero without any updates.
+    pub fn num_sessions(&self, gap_ns: u64) -> usize {
+        if self.num_updates == 0 {
+            return 0;
+        }
+        let num_gaps: usize = self
+            .update_gaps_ns
+            .iter()
+            .filter(|(gap, _)| **gap > gap_ns)
+            .map(|(_, count)| count)
+            .sum();
+        num_gaps + 1
+    }
+
+    /// The realized profit and loss of the logged trades per calendar day, excluding fees,
+    /// as the nanosecond timestamp of the start of the day and the pnl within that day.
+    /// Unlike the sampled daily returns, unrealized pnl is not included.
+    /// Days without any realized pnl are omitted.
+    pub fn daily_realized_pnl(&self) -> &[(u64, f64)] {
+        &self.daily_realized_pnl
+    }
+
+    /// The fraction of `update` calls where the realized profit and loss of the logged trades
+    /// plus the unrealized pnl of the position at the mid price was positive, excluding fees.
+    /// Unlike a win ratio, this reflects the whole equity path. Is zero without any updates.
+    pub fn time_in_profit_ratio(&self) -> f64 {
+        if self.num_updates == 0 {
+            return 0.0;
+        }
+        self.num_updates_in_profit as f64 / self.num_updates as f64
+    }
+
+    /// The average number of `update` calls a position was held for, from opening until it was
+    /// closed or flipped to the opposite side. Is zero if no position has been closed yet.
+    pub fn avg_trade_duration_ticks(&self) -> f64 {
+        if self.num_closed_trades == 0 {
+            return 0.0;
+        }
+        self.sum_trade_duration_ticks as f64 / self.num_closed_trades as f64
+    }
+
+    /// The win rate required to break even given the average win and loss sizes of the
+    /// open-close cycles of the position, being `avg_loss / (avg_win + avg_loss)`.
+    /// The pnl of each cycle excludes fees. Is zero if there are no losing cycles.
+    pub fn breakeven_win_rate(&self) -> f64 {
+        let avg = |sum: BaseOrQuote, n: u64| {
+            if n == 0 {
+                0.0
+            } else {
+                Into::<f64>::into(sum) / n as f64
+            }
+        };
+        let avg_win = avg(self.sum_wins, self.num_wins);
+        let avg_loss = avg(self.sum_losses, self.num_losses);
+        if avg_loss == 0.0 {
+            return 0.0;
+        }
+        avg_loss / (avg_win + avg_loss)
+    }
+
+    /// The fraction of the total profit of the winning open-close cycles of the position
+    /// contributed by the `n` largest ones, excluding fees.
+    /// A value near one means the result depends on a few outliers.
+    /// Is zero if there are no winning cycles.
+    pub fn profit_concentration(&self, n: usize) -> f64 {
+        let total_profit: f64 = self.sum_wins.into();
+        if total_profit == 0.0 {
+            return 0.0;
+        }
+        let mut wins: Vec<f64> = self
+            .trade_pnls
+            .iter()
+            .copied()
+            .filter(|pnl| *pnl > 0.0)
+            .collect();
+        wins.sort_unstable_by(|a, b| b.total_cmp(a));
+        wins.iter().take(n).sum::<f64>() / total_profit
+    }
+
+    fn record_closed_trade_pnl(&mut self) {
+        let pnl = std::mem::replace(&mut self.current_trade_pnl, BaseOrQuote::zero());
+        self.trade_pnls.push(pnl.into());
+        if pnl > BaseOrQuote::zero() {
+            self.sum_wins += pnl;
+            self.num_wins += 1;
+        } else if pnl < BaseOrQuote::zero() {
+            self.sum_losses -= pnl;
+            self.num_losses += 1;
+        }
+    }
+
+    /// The average order book imbalance during the users holding periods,
+    /// defined as `(bid_volume - ask_volume) / (bid_volume + ask_volume)` in the range [-1, 1].
+    pub fn avg_order_book_imbalance(&self) -> Option<f64> {
+        if self.num_order_book_imbalance_samples == 0 {
+            return None;
+        }
+        Some(self.order_book_imbalance_sum / self.num_order_book_imbalance_samples as f64)
+    }
+
     /// Return the raw sharpe ratio that has been derived from the sampled returns of the users balances.
     /// This sharpe ratio is not annualized and does not include a risk free rate.
     pub fn sharpe(&self) -> Option<f32> {
         let std_dev = self.user_balances_ln_return_stats.last()?;
-        if std_dev == 0.0 {
+        if !std_dev.is_finite() || std_dev == 0.0 {
             return None;
         }
         let mean_return = self.user_balances_ln_return_stats.mean();
@@ -222,16 +559,220 @@ where
         Some(mean_return / std_dev)
     }
 
+    /// Return the sharpe ratio annualized by `sqrt` of the periods per year of the `source`,
+    /// which must match the interval at which the user balances are sampled.
+    /// If `risk_free_is_bnh`, the mean ln return of the market over the same sampling periods,
+    /// being the return of buy and hold, is subtracted as the risk free rate.
+    pub fn sharpe_annualized(&self, source: ReturnsSource, risk_free_is_bnh: bool) -> Option<f32> {
+        let std_dev = self.user_balances_ln_return_stats.last()?;
+        if !std_dev.is_finite() || std_dev == 0.0 {
+            return None;
+        }
+        let mut excess_return = self.user_balances_ln_return_stats.mean();
+        if risk_free_is_bnh {
+            excess_return -= self.sampled_market_ln_return_stats.mean();
+        }
+
+        Some(excess_return / std_dev * (source.periods_per_year() as f32).sqrt())
+    }
+
+    /// The modified sharpe ratio, being the mean ln return of the user balances relative to its
+    /// modified value at risk at the 95% confidence level, annualized by `sqrt` of the
+    /// periods per year of the `source`. The value at risk uses the Cornish-Fisher expansion
+    /// to account for the skew and excess kurtosis of fat-tailed returns.
+    /// Is zero with less than two sampled returns or a non-positive value at risk.
+    pub fn modified_sharpe(&self, source: ReturnsSource) -> f64 {
+        const Z_95: f64 = -1.6448536269514722;
+
+        if self.user_balances_ln_returns.len() < 2 {
+            return 0.0;
+        }
+        let moments = statistical_moments(&self.user_balances_ln_returns);
+        if moments.std_dev == 0.0 {
+            return 0.0;
+        }
+        let z = cornish_fisher_quantile(Z_95, moments.skew, moments.excess_kurtosis);
+        let modified_var = -(moments.mean + z * moments.std_dev);
+        if modified_var <= 0.0 {
+            return 0.0;
+        }
+        moments.mean / modified_var * source.periods_per_year().sqrt()
+    }
+
+    /// The sharpe ratio of the realized balance, being the starting wallet balance plus the
+    /// realized profit and loss net of fees, sampled together with the user balances and
+    /// annualized by `sqrt` of the periods per year of the `source`.
+    /// Only closed trades move the realized balance, so the unrealized fluctuations
+    /// of an open position do not enter its returns.
+    /// Is zero with less than two sampled returns or without any variation in them.
+    pub fn realized_sharpe(&self, source: ReturnsSource) -> f64 {
+        if self.realized_balance_ln_returns.len() < 2 {
+            return 0.0;
+        }
+        let moments = statistical_moments(&self.realized_balance_ln_returns);
+        if moments.std_dev == 0.0 {
+            return 0.0;
+        }
+        moments.mean / moments.std_dev * source.periods_per_year().sqrt()
+    }
+
+    /// The number of sampled returns of the user balances spanning distinct periods of `source`,
+    /// based on the timestamp of the most recent market update at each sample.
+    /// Useful for gauging the statistical significance of ratios like `sharpe_annualized`.
+    pub fn num_samples(&self, source: ReturnsSource) -> usize {
+        match source {
+            ReturnsSource::Daily => self.num_daily_samples,
+            ReturnsSource::Hourly => self.num_hourly_samples,
+        }
+    }
+
+    /// The geometric mean return of the user balances per period of `source`, being the mean
+    /// of `(1 + r)` over the returns between the first samples of consecutive periods minus one,
+    /// so compounding it over the `num_samples` returns yields their total growth.
+    /// Unlike the arithmetic mean used in `sharpe`, it reflects the compounded performance.
+    /// Is zero if no returns spanning distinct periods of `source` have been sampled.
+    pub fn geometric_mean_return(&self, source: ReturnsSource) -> f64 {
+        let sum_ln_returns = match source {
+            ReturnsSource::Daily => self.sum_daily_ln_returns,
+            ReturnsSource::Hourly => self.sum_hourly_ln_returns,
+        };
+        let num_samples = self.num_samples(source);
+        if num_samples == 0 {
+            return 0.0;
+        }
+        // The sum of the ln returns is the ln of the compounded growth `prod(1 + r)`.
+        (sum_ln_returns / num_samples as f64).exp() - 1.0
+    }
+
+    /// Count a sampled return if the `period` differs from the `last_period` that was sampled.
+    /// Returns whether the sample is the first one within the `period`.
+    fn count_period_sample(
+        last_period: &mut Option<i64>,
+        num_samples: &mut usize,
+        period: i64,
+    ) -> bool {
+        if *last_period == Some(period) {
+            return false;
+        }
+        if last_period.is_some() {
+            *num_samples += 1;
+        }
+        *last_period = Some(period);
+        true
+    }
+
+    /// Add the ln return from the balance at the first sample of the previous period
+    /// to the `balance` at the first sample of a new period.
+    fn add_period_ln_return(
+        period_balance: &mut Option<f64>,
+        sum_ln_returns: &mut f64,
+        balance: f64,
+    ) {
+        if let Some(prev_balance) = period_balance.replace(balance) {
+            *sum_ln_returns += (balance / prev_balance).ln();
+        }
+    }
+
+    /// The Pain Index, being the average drawdown of the sampled user balances from their
+    /// running peak, taking the first sample within each period of `source`.
+    /// Is zero if no user balances have been sampled yet.
+    pub fn pain_index(&self, source: ReturnsSource) -> f64 {
+        let (sum_drawdowns, last_period, num_samples) = match source {
+            ReturnsSource::Daily => (
+                self.sum_daily_drawdowns,
+                self.last_sampled_day,
+                self.num_daily_samples,
+            ),
+            ReturnsSource::Hourly => (
+                self.sum_hourly_drawdowns,
+                self.last_sampled_hour,
+                self.num_hourly_samples,
+            ),
+        };
+        if last_period.is_none() {
+            return 0.0;
+        }
+        // The first sampled period does not yield a return, but does have a drawdown.
+        sum_drawdowns / (num_samples + 1) as f64
+    }
+
+    /// The Pain Ratio, being the annualized return of the user balances
+    /// over the daily `pain_index`.
+    /// Is zero if the daily `pain_index` is zero or no time has passed.
+    pub fn pain_ratio(&self) -> f64 {
+        let pain_index = self.pain_index(ReturnsSource::Daily);
+        let years = Into::<i64>::into(self.ts_last - self.ts_first) as f64
+            / (DAILY_NS as f64 * ReturnsSource::Daily.periods_per_year());
+        if pain_index == 0.0 || years <= 0.0 {
+            return 0.0;
+        }
+        let total_return =
+            Into::<f64>::into(self.last_balance_sum) / Into::<f64>::into(self.wallet_balance_start);
+        let annualized_return = total_return.powf(1.0 / years) - 1.0;
+        annualized_return / pain_index
+    }
+
+    /// The information ratio versus buy-and-hold, being the mean active ln return of the
+    /// user balances over the market relative to the tracking error (std dev of active returns),
+    /// annualized by `sqrt` of the periods per year of the `source`.
+    /// Is zero while the tracking error is unknown or zero.
+    pub fn information_ratio(&self, source: ReturnsSource) -> f64 {
+        let tracking_error = match self.active_ln_return_stats.last() {
+            Some(tracking_error) if tracking_error != 0.0 => tracking_error as f64,
+            _ => return 0.0,
+        };
+        let mean_active_return = self.active_ln_return_stats.mean() as f64;
+        mean_active_return / tracking_error * source.periods_per_year().sqrt()
+    }
+
+    /// The beta of the user balances versus buy-and-hold, being the covariance of the sampled
+    /// ln returns of the user balances and the market relative to the variance of the market.
+    /// The covariance is derived from the variances of both and of their active returns,
+    /// as all of them are sampled over the same periods.
+    /// Is zero while the variance of the market is unknown or zero.
+    pub fn beta(&self) -> f64 {
+        let market_variance = self.sampled_market_ln_return_stats.variance() as f64;
+        if market_variance <= 0.0 {
+            return 0.0;
+        }
+        let covariance = (self.user_balances_ln_return_stats.variance() as f64 + market_variance
+            - self.active_ln_return_stats.variance() as f64)
+            / 2.0;
+        covariance / market_variance
+    }
+
+    /// The Treynor ratio, being the mean ln return of the user balances annualized by
+    /// the periods per year of the `source`, relative to the `beta` versus buy-and-hold.
+    /// No risk free rate is subtracted. Is zero if the `beta` is zero.
+    pub fn treynor_ratio(&self, source: ReturnsSource) -> f64 {
+        let beta = self.beta();
+        if beta == 0.0 {
+            return 0.0;
+        }
+        self.user_balances_ln_return_stats.mean() as f64 * source.periods_per_year() / beta
+    }
+
+    /// The realized volatility of the underlying, being the std dev of the sampled ln returns
+    /// of the market annualized by `sqrt` of the periods per year of the `source`.
+    /// Unlike the volatility of the user balances, it does not depend on the strategy.
+    /// Is zero while the std dev is unknown.
+    pub fn underlying_realized_vol(&self, source: ReturnsSource) -> f64 {
+        self.sampled_market_ln_return_stats
+            .last()
+            .map_or(0.0, |std_dev| {
+                std_dev as f64 * source.periods_per_year().sqrt()
+            })
+    }
+
     /// Returns the theoretical kelly leverage that would maximize the compounded growth rate,
     /// assuming the returns are normally distributed. Which they almost never are. So be aware.
     pub fn kelly_leverage(&self) -> f32 {
         let mean_return = self.user_balances_ln_return_stats.mean();
         let return_variance = self.user_balances_ln_return_stats.variance();
-        assert!(return_variance >= 0.0);
-
-        if return_variance == 0.0 {
+        if !return_variance.is_finite() || return_variance == 0.0 {
             return 0.0;
         }
+        assert!(return_variance > 0.0);
 
         mean_return / return_variance
     }
@@ -240,7 +781,7 @@ where
     /// This sortino ratio is not annualized and does not include a risk free rate.
     pub fn sortino(&self) -> Option<f32> {
         let neg_std_dev = self.user_balances_neg_ln_return_stats.last()?;
-        if neg_std_dev == 0.0 {
+        if !neg_std_dev.is_finite() || neg_std_dev == 0.0 {
             return None;
         }
         let mean_return = self.user_balances_ln_return_stats.mean();
@@ -249,6 +790,23 @@ where
         Some(mean_return / neg_std_dev)
     }
 
+    /// Return the sortino ratio annualized by `sqrt` of the periods per year of the `source`,
+    /// which must match the interval at which the user balances are sampled.
+    /// If `risk_free_is_bnh`, the mean ln return of the market over the same sampling periods,
+    /// being the return of buy and hold, is subtracted as the risk free rate.
+    pub fn sortino_annualized(&self, source: ReturnsSource, risk_free_is_bnh: bool) -> Option<f32> {
+        let neg_std_dev = self.user_balances_neg_ln_return_stats.last()?;
+        if !neg_std_dev.is_finite() || neg_std_dev == 0.0 {
+            return None;
+        }
+        let mut excess_return = self.user_balances_ln_return_stats.mean();
+        if risk_free_is_bnh {
+            excess_return -= self.sampled_market_ln_return_stats.mean();
+        }
+
+        Some(excess_return / neg_std_dev * (source.periods_per_year() as f32).sqrt())
+    }
+
     /// The discriminant ratio (`d_ratio`) divides the return-to-VaR ratio of the user performance
     /// by the return-to-VaR ratio of the buy-and-hold strategy.
     /// If the `d_ratio` is greater than 1, the user outperformed the buy-and-hold strategy.
@@ -275,6 +833,89 @@ where
 
         Some(1.0 + (rtv_algo - rtv_bnh) / (rtv_bnh).abs())
     }
+
+    /// All scalar metrics keyed by their name, e.g for forwarding them to a logging pipeline.
+    /// Metrics which are not yet available (`None`) are omitted.
+    /// Metrics depending on a `ReturnsSource` use the daily returns.
+    pub fn metrics(&self) -> HashMap<&'static str, f64> {
+        let mut metrics: HashMap<&'static str, f64> = HashMap::from([
+            ("wallet_balance_start", self.wallet_balance_start.into()),
+            ("rpnl", self.rpnl().into()),
+            ("kelly_leverage", self.kelly_leverage() as f64),
+            ("buy_volume", self.buy_volume.into()),
+            ("sell_volume", self.sell_volume.into()),
+            ("turnover", self.turnover().into()),
+            ("cumulative_fees", self.cumulative_fees.into()),
+            ("effective_fee_rate", self.effective_fee_rate()),
+            ("buy_and_hold_return", self.buy_and_hold_return().into()),
+            ("sell_and_hold_return", self.sell_and_hold_return().into()),
+            ("num_trading_days", self.num_trading_days() as f64),
+            (
+                "limit_order_fill_ratio",
+                self.limit_order_fill_ratio() as f64,
+            ),
+            (
+                "limit_order_cancellation_ratio",
+                self.limit_order_cancellation_ratio() as f64,
+            ),
+            (
+                "drawdown_user_balances",
+                self.drawdown_user_balances() as f64,
+            ),
+            ("drawdown_market", self.drawdown_market() as f64),
+            (
+                "num_submitted_limit_orders",
+                self.num_submitted_limit_orders as f64,
+            ),
+            (
+                "num_cancelled_limit_orders",
+                self.num_cancelled_limit_orders as f64,
+            ),
+            (
+                "num_filled_limit_order_events",
+                self.num_filled_limit_order_events as f64,
+            ),
+            (
+                "num_fully_filled_limit_orders",
+                self.num_fully_filled_limit_orders as f64,
+            ),
+            (
+                "num_submitted_market_orders",
+                self.num_submitted_market_orders as f64,
+            ),
+            (
+                "num_filled_market_orders",
+                self.num_filled_market_orders as f64,
+            ),
+            ("avg_trade_duration_ticks", self.avg_trade_duration_ticks()),
+            ("breakeven_win_rate", self.breakeven_win_rate()),
+            (
+                "information_ratio",
+                self.information_ratio(ReturnsSource::Daily),
+            ),
+            ("pain_index", self.pain_index(ReturnsSource::Daily)),
+            ("pain_ratio", self.pain_ratio()),
+            ("time_in_profit_ratio", self.time_in_profit_ratio()),
+            ("expected_value_per_trade", self.expected_value_per_trade()),
+            ("net_pnl", self.net_pnl()),
+            ("beta", self.beta()),
+            ("treynor_ratio", self.treynor_ratio(ReturnsSource::Daily)),
+        ]);
+        if let Some(sharpe) = self.sharpe() {
+            metrics.insert("sharpe", sharpe as f64);
+        }
+        if let Some(sortino) = self.sortino() {
+            metrics.insert("sortino", sortino as f64);
+        }
+        if let Some(buy_volume_ratio) = self.buy_volume_ratio() {
+            metrics.insert("buy_volume_ratio", buy_volume_ratio as f64);
+        }
+        if let Some(imbalance) = self.avg_order_book_imbalance() {
+            metrics.insert("avg_order_book_imbalance", imbalance);
+        }
+
+        metrics
+    }
 }
 
 impl<I, const D: u8, BaseOrQuote, UserOrderId> AccountTracker<I, D, BaseOrQuote, UserOrderId>
@@ -284,11 +925,21 @@ where
     BaseOrQuote: MarginCurrency<I, D>,
     UserOrderId: UserOrderIdT,
 {
+    fn reset(&mut self, wallet_balance: BaseOrQuote) {
+        *self = Self::new(wallet_balance);
+    }
+
     fn update(&mut self, market_state: &MarketState<I, D>) {
+        self.num_updates += 1;
         if self.ts_first == 0.into() {
             self.ts_first = market_state.current_timestamp_ns();
         }
+        let gap_ns = Into::<i64>::into(market_state.current_timestamp_ns() - self.ts_last);
+        if self.num_updates > 1 && gap_ns > 0 {
+            *self.update_gaps_ns.entry(gap_ns as u64).or_insert(0) += 1;
+        }
         self.ts_last = market_state.current_timestamp_ns();
+        self.ts_current = self.ts_last;
 
         if self.price_first.is_zero() {
             self.price_first = market_state.mid_price();
@@ -297,37 +948,94 @@ where
 
         self.drawdown_market
             .update(Into::<f64>::into(market_state.mid_price()) as f32);
+
+        let upnl = BaseOrQuote::pnl(
+            self.position_entry_price,
+            market_state.mid_price(),
+            self.net_position_qty,
+        );
+        if self.total_rpnl + upnl > BaseOrQuote::zero() {
+            self.num_updates_in_profit += 1;
+        }
+    }
+
+    #[inline(always)]
+    fn log_timestamp(&mut self, timestamp_ns: TimestampNs) {
+        self.ts_current = timestamp_ns;
     }
 
     fn sample_user_balances(
         &mut self,
         user_balances: &UserBalances<I, D, BaseOrQuote>,
-        #[allow(unused)] mid_price: QuoteCurrency<I, D>,
+        mid_price: QuoteCurrency<I, D>,
     ) {
         let balance_sum = user_balances.sum();
         self.last_balance_sum = balance_sum;
 
-        let balance_sum = Into::<f64>::into(balance_sum) as f32;
-        self.drawdown_user_balances.update(balance_sum);
+        let balances = Into::<f64>::into(balance_sum);
+        self.balances_high_water_mark = self.balances_high_water_mark.max(balances);
+        let drawdown = (self.balances_high_water_mark - balances) / self.balances_high_water_mark;
+
+        let ts = Into::<i64>::into(self.ts_last);
+        if Self::count_period_sample(
+            &mut self.last_sampled_day,
+            &mut self.num_daily_samples,
+            ts.div_euclid(ReturnsSource::Daily.period_ns()),
+        ) {
+            self.sum_daily_drawdowns += drawdown;
+            Self::add_period_ln_return(
+                &mut self.daily_period_balance,
+                &mut self.sum_daily_ln_returns,
+                balances,
+            );
+        }
+        if Self::count_period_sample(
+            &mut self.last_sampled_hour,
+            &mut self.num_hourly_samples,
+            ts.div_euclid(ReturnsSource::Hourly.period_ns()),
+        ) {
+            self.sum_hourly_drawdowns += drawdown;
+            Self::add_period_ln_return(
+                &mut self.hourly_period_balance,
+                &mut self.sum_hourly_ln_returns,
+                balances,
+            );
+        }
 
-        self.user_balances_ln_return.update(balance_sum);
-        if let Some(ln_ret) = self.user_balances_ln_return.last() {
-            self.user_balances_ln_return_stats.update(ln_ret);
-            if ln_ret < 0.0 {
-                self.user_balances_neg_ln_return_stats.update(ln_ret);
+        let realized_balance =
+            Into::<f64>::into(self.wallet_balance_start + self.total_rpnl - self.cumulative_fees);
+        if let Some(last_realized_balance) = self.last_realized_balance {
+            if last_realized_balance > 0.0 && realized_balance > 0.0 {
+                self.realized_balance_ln_returns
+                    .push((realized_balance / last_realized_balance).ln());
             }
-            #[cfg(feature = "quantiles")]
-            self.quantogram_user_balances_ln_returns.add(ln_ret as f64);
         }
+        self.last_realized_balance = Some(realized_balance);
 
-        #[cfg(feature = "quantiles")]
-        {
-            let mid_price = mid_price.as_ref().to_f64() as f32;
-            self.sampled_market_ln_return.update(mid_price);
-            if let Some(market_ln_ret) = self.sampled_market_ln_return.last() {
-                self.quantogram_market_ln_returns.add(market_ln_ret as f64);
-            }
+        let balance_sum = Into::<f64>::into(balance_sum) as f32;
+        let mid_price = Into::<f64>::into(mid_price) as f32;
+        self.sampled_balances_and_prices
+            .push((balance_sum, mid_price));
+        self.update_rolling_stats(balance_sum, mid_price);
+    }
+
+    fn sample_market_microstructure(
+        &mut self,
+        bid_volume: BaseOrQuote::PairedCurrency,
+        ask_volume: BaseOrQuote::PairedCurrency,
+        position_open: bool,
+    ) {
+        if !position_open {
+            return;
         }
+        let total_volume = bid_volume + ask_volume;
+        if total_volume.is_zero() {
+            return;
+        }
+        let imbalance =
+            Into::<f64>::into(bid_volume - ask_volume) / Into::<f64>::into(total_volume);
+        self.order_book_imbalance_sum += imbalance;
+        self.num_order_book_imbalance_samples += 1;
     }
 
     #[inline(always)]
@@ -373,6 +1081,58 @@ where
             Side::Buy => self.buy_volume += value,
             Side::Sell => self.sell_volume += value,
         }
+
+        let prev_qty = self.net_position_qty;
+        match side {
+            Side::Buy => self.net_position_qty += quantity,
+            Side::Sell => self.net_position_qty -= quantity,
+        }
+        let zero = BaseOrQuote::PairedCurrency::zero();
+        if prev_qty.is_zero() {
+            self.position_opened_at_update = self.num_updates;
+            self.position_entry_price = price;
+        } else if (prev_qty > zero) == matches!(side, Side::Buy) {
+            self.position_entry_price = QuoteCurrency::new_weighted_price(
+                self.position_entry_price,
+                *prev_qty.abs().as_ref(),
+                price,
+                *quantity.as_ref(),
+            );
+        } else {
+            let closed_qty = min(prev_qty.abs(), quantity);
+            let closed_qty = if prev_qty > zero {
+                closed_qty
+            } else {
+                -closed_qty
+            };
+            let pnl = BaseOrQuote::pnl(self.position_entry_price, price, closed_qty);
+            self.current_trade_pnl += pnl;
+            self.total_rpnl += pnl;
+
+            let day_ts = Into::<i64>::into(self.ts_current).div_euclid(DAILY_NS) * DAILY_NS;
+            let day_ts = day_ts as u64;
+            match self.daily_realized_pnl.last_mut() {
+                Some((last_day_ts, day_pnl)) if *last_day_ts == day_ts => {
+                    *day_pnl += Into::<f64>::into(pnl)
+                }
+                _ => self.daily_realized_pnl.push((day_ts, pnl.into())),
+            }
+
+            if self.net_position_qty.is_zero()
+                || (prev_qty > zero) != (self.net_position_qty > zero)
+            {
+                self.sum_trade_duration_ticks += self.num_updates - self.position_opened_at_update;
+                self.num_closed_trades += 1;
+                self.position_opened_at_update = self.num_updates;
+                self.position_entry_price = price;
+                self.record_closed_trade_pnl();
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn log_fee(&mut self, fee: BaseOrQuote) {
+        self.cumulative_fees += fee;
     }
 
     #[inline(always)]
@@ -451,6 +1211,7 @@ mod tests {
         assert_eq!(at.price_first, QuoteCurrency::new(1005, 1));
         assert_eq!(at.price_last, QuoteCurrency::new(1005, 1));
         assert_eq!(at.drawdown_market(), 0.0);
+        assert_eq!(at.num_updates(), 1);
     }
 
     #[test]
@@ -497,4 +1258,873 @@ mod tests {
         assert!(at.sortino().is_none());
         assert_eq!(at.kelly_leverage(), 4126442.3);
     }
+
+    #[test]
+    fn full_track_sharpe_annualized() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        for (balance, mid_price) in [(100, 1000), (101, 1005), (102, 1010)] {
+            let balances = UserBalances {
+                available_wallet_balance: QuoteCurrency::new(balance, 0),
+                position_margin: QuoteCurrency::zero(),
+                order_margin: QuoteCurrency::zero(),
+                _q: std::marker::PhantomData,
+            };
+            <FullAccountTracker<_, 4, _> as AccountTracker<_, 4, _, NoUserOrderId>>::sample_user_balances(&mut at, &balances, QuoteCurrency::new(mid_price, 1));
+        }
+        let sharpe = at.sharpe().unwrap();
+        assert_eq!(
+            at.sharpe_annualized(ReturnsSource::Daily, false).unwrap(),
+            sharpe * 365_f32.sqrt()
+        );
+        assert_eq!(
+            at.sharpe_annualized(ReturnsSource::Hourly, false).unwrap(),
+            sharpe * 8760_f32.sqrt()
+        );
+
+        // The market returned half as much as the user balances.
+        let std_dev = at.user_balances_ln_return_stats.last().unwrap();
+        let excess_return =
+            at.user_balances_ln_return_stats.mean() - at.sampled_market_ln_return_stats.mean();
+        assert_eq!(
+            at.sharpe_annualized(ReturnsSource::Daily, true).unwrap(),
+            excess_return / std_dev * 365_f32.sqrt()
+        );
+        assert!(
+            at.sharpe_annualized(ReturnsSource::Daily, true).unwrap() < sharpe * 365_f32.sqrt()
+        );
+    }
+
+    #[test]
+    fn full_track_modified_sharpe() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        let sample = |at: &mut At, balance: i64| {
+            let balances = UserBalances {
+                available_wallet_balance: QuoteCurrency::new(balance, 0),
+                position_margin: QuoteCurrency::zero(),
+                order_margin: QuoteCurrency::zero(),
+                _q: std::marker::PhantomData,
+            };
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::sample_user_balances(
+                at,
+                &balances,
+                QuoteCurrency::new(100, 0),
+            );
+        };
+        assert_eq!(at.modified_sharpe(ReturnsSource::Daily), 0.0);
+
+        // Steady gains with a single large loss make for negatively skewed, fat-tailed returns.
+        for balance in [1000, 1010, 1020, 1030, 1040, 990, 1000, 1010, 1020, 1030] {
+            sample(&mut at, balance);
+        }
+        let moments = statistical_moments(&at.user_balances_ln_returns);
+        assert!(moments.skew < 0.0);
+
+        let modified_sharpe = at.modified_sharpe(ReturnsSource::Daily);
+        let z = cornish_fisher_quantile(-1.6448536269514722, moments.skew, moments.excess_kurtosis);
+        let expected = moments.mean / -(moments.mean + z * moments.std_dev) * 365_f64.sqrt();
+        assert!((modified_sharpe - expected).abs() < 1e-9);
+        assert!(modified_sharpe > 0.0);
+        let sharpe = at.sharpe_annualized(ReturnsSource::Daily, false).unwrap() as f64;
+        assert!((modified_sharpe - sharpe).abs() > 1e-3);
+        let hourly = at.modified_sharpe(ReturnsSource::Hourly);
+        assert!((hourly / modified_sharpe - 24_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn full_track_sortino_annualized() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        assert_eq!(at.sortino_annualized(ReturnsSource::Daily, false), None);
+        for (balance, mid_price) in [(100, 1000), (98, 1005), (101, 1003), (99, 1010)] {
+            let balances = UserBalances {
+                available_wallet_balance: QuoteCurrency::new(balance, 0),
+                position_margin: QuoteCurrency::zero(),
+                order_margin: QuoteCurrency::zero(),
+                _q: std::marker::PhantomData,
+            };
+            <FullAccountTracker<_, 4, _> as AccountTracker<_, 4, _, NoUserOrderId>>::sample_user_balances(&mut at, &balances, QuoteCurrency::new(mid_price, 1));
+        }
+        let sortino = at.sortino().unwrap();
+        assert_eq!(
+            at.sortino_annualized(ReturnsSource::Daily, false).unwrap(),
+            sortino * 365_f32.sqrt()
+        );
+        assert_eq!(
+            at.sortino_annualized(ReturnsSource::Hourly, false).unwrap(),
+            sortino * 8760_f32.sqrt()
+        );
+
+        let neg_std_dev = at.user_balances_neg_ln_return_stats.last().unwrap();
+        let excess_return =
+            at.user_balances_ln_return_stats.mean() - at.sampled_market_ln_return_stats.mean();
+        assert_eq!(
+            at.sortino_annualized(ReturnsSource::Daily, true).unwrap(),
+            excess_return / neg_std_dev * 365_f32.sqrt()
+        );
+    }
+
+    #[test]
+    fn full_track_treynor_ratio() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        let sample = |at: &mut At, balance: QuoteCurrency<i64, 4>, mid_price: i64| {
+            let balances = UserBalances {
+                available_wallet_balance: balance,
+                position_margin: QuoteCurrency::zero(),
+                order_margin: QuoteCurrency::zero(),
+                _q: std::marker::PhantomData,
+            };
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::sample_user_balances(
+                at,
+                &balances,
+                QuoteCurrency::new(mid_price, 0),
+            );
+        };
+        assert_eq!(at.treynor_ratio(ReturnsSource::Daily), 0.0);
+
+        // A flat market has no variance, so the beta is zero.
+        sample(&mut at, QuoteCurrency::new(1000, 0), 100);
+        sample(&mut at, QuoteCurrency::new(1010, 0), 100);
+        sample(&mut at, QuoteCurrency::new(990, 0), 100);
+        assert_eq!(at.beta(), 0.0);
+        assert_eq!(at.treynor_ratio(ReturnsSource::Daily), 0.0);
+
+        // The user balances track the square of the market, so its ln returns are doubled.
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        for (balance, mid_price) in [(10000, 100), (12100, 110), (9801, 99), (14400, 120)] {
+            sample(&mut at, QuoteCurrency::new(balance, 1), mid_price);
+        }
+        assert!((at.beta() - 2.0).abs() < 1e-3, "beta: {}", at.beta());
+        let mean_market_ln_return = (1.2_f64).ln() / 3.0;
+        let expected = 2.0 * mean_market_ln_return * 365.0 / 2.0;
+        assert!((at.treynor_ratio(ReturnsSource::Daily) - expected).abs() < 1e-2);
+    }
+
+    #[test]
+    fn full_track_underlying_realized_vol() {
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        let sample = |at: &mut At, balance: i64, mid_price: i64| {
+            let balances = UserBalances {
+                available_wallet_balance: QuoteCurrency::new(balance, 0),
+                position_margin: QuoteCurrency::zero(),
+                order_margin: QuoteCurrency::zero(),
+                _q: std::marker::PhantomData,
+            };
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::sample_user_balances(
+                at,
+                &balances,
+                QuoteCurrency::new(mid_price, 0),
+            );
+        };
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        assert_eq!(at.underlying_realized_vol(ReturnsSource::Daily), 0.0);
+
+        // A flat market has no volatility, no matter the user balances.
+        for balance in [1000, 1100, 900] {
+            sample(&mut at, balance, 100);
+        }
+        assert_eq!(at.underlying_realized_vol(ReturnsSource::Daily), 0.0);
+
+        // The same market yields the same volatility for different user balances.
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        let mut other = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        for mid_price in [100, 110, 99, 120] {
+            sample(&mut at, 1000, mid_price);
+            sample(&mut other, 1000 + mid_price, mid_price);
+        }
+        let std_dev = at.sampled_market_ln_return_stats.last().unwrap() as f64;
+        assert!(std_dev > 0.0);
+        let daily = at.underlying_realized_vol(ReturnsSource::Daily);
+        assert_eq!(daily, std_dev * 365_f64.sqrt());
+        assert_eq!(other.underlying_realized_vol(ReturnsSource::Daily), daily);
+        let hourly = at.underlying_realized_vol(ReturnsSource::Hourly);
+        assert!((hourly / daily - 24_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn full_track_avg_trade_duration_ticks() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        let market_state = MarketState::from_components(
+            QuoteCurrency::new(100, 0),
+            QuoteCurrency::new(101, 0),
+            0.into(),
+            0,
+        );
+        let update = |at: &mut At, n: usize| {
+            for _ in 0..n {
+                <At as AccountTracker<_, 4, _, NoUserOrderId>>::update(at, &market_state);
+            }
+        };
+        let log_trade = |at: &mut At, side: Side, qty: i64| {
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_trade(
+                at,
+                side,
+                QuoteCurrency::new(100, 0),
+                BaseCurrency::new(qty, 0),
+            );
+        };
+        assert_eq!(at.avg_trade_duration_ticks(), 0.0);
+
+        // Held for 4 ticks, closed in two steps.
+        log_trade(&mut at, Side::Buy, 2);
+        update(&mut at, 3);
+        log_trade(&mut at, Side::Sell, 1);
+        update(&mut at, 1);
+        log_trade(&mut at, Side::Sell, 1);
+        assert_eq!(at.avg_trade_duration_ticks(), 4.0);
+
+        // A short held for 2 ticks, then flipped into a long held for 6 ticks.
+        update(&mut at, 5);
+        log_trade(&mut at, Side::Sell, 1);
+        update(&mut at, 2);
+        log_trade(&mut at, Side::Buy, 2);
+        update(&mut at, 6);
+        log_trade(&mut at, Side::Sell, 1);
+        assert_eq!(at.avg_trade_duration_ticks(), 4.0);
+    }
+
+    #[test]
+    fn full_track_num_samples() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        let balances = UserBalances {
+            available_wallet_balance: QuoteCurrency::new(1000, 0),
+            position_margin: QuoteCurrency::zero(),
+            order_margin: QuoteCurrency::zero(),
+            _q: std::marker::PhantomData,
+        };
+        let sample = |at: &mut At, ts_ns: i64| {
+            let market_state = MarketState::from_components(
+                QuoteCurrency::new(100, 0),
+                QuoteCurrency::new(101, 0),
+                ts_ns.into(),
+                0,
+            );
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::update(at, &market_state);
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::sample_user_balances(
+                at,
+                &balances,
+                market_state.mid_price(),
+            );
+        };
+        assert_eq!(at.num_samples(ReturnsSource::Daily), 0);
+
+        // Six daily samples yield five daily returns.
+        for day in 0..6 {
+            sample(&mut at, day * DAILY_NS);
+        }
+        assert_eq!(at.num_samples(ReturnsSource::Daily), 5);
+        assert_eq!(at.num_samples(ReturnsSource::Hourly), 5);
+
+        // Another sample on the same day, but in a different hour.
+        sample(&mut at, 5 * DAILY_NS + 3_600_000_000_000);
+        assert_eq!(at.num_samples(ReturnsSource::Daily), 5);
+        assert_eq!(at.num_samples(ReturnsSource::Hourly), 6);
+    }
+
+    #[test]
+    fn full_track_geometric_mean_return() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        let sample = |at: &mut At, ts_ns: i64, balance: i64| {
+            let market_state = MarketState::from_components(
+                QuoteCurrency::new(100, 0),
+                QuoteCurrency::new(101, 0),
+                ts_ns.into(),
+                0,
+            );
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::update(at, &market_state);
+            let balances = UserBalances {
+                available_wallet_balance: QuoteCurrency::new(balance, 0),
+                position_margin: QuoteCurrency::zero(),
+                order_margin: QuoteCurrency::zero(),
+                _q: std::marker::PhantomData,
+            };
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::sample_user_balances(
+                at,
+                &balances,
+                market_state.mid_price(),
+            );
+        };
+        assert_eq!(at.geometric_mean_return(ReturnsSource::Daily), 0.0);
+
+        // Daily returns of +10%, -10% and +20%.
+        for (day, balance) in [1000, 1100, 990, 1188].into_iter().enumerate() {
+            sample(&mut at, day as i64 * DAILY_NS, balance);
+        }
+        let arithmetic_mean = [0.1, -0.1, 0.2].iter().sum::<f64>() / 3.0;
+        let expected = 1.188_f64.powf(1.0 / 3.0) - 1.0;
+        let geometric_mean = at.geometric_mean_return(ReturnsSource::Daily);
+        assert!((geometric_mean - expected).abs() < 1e-6, "{geometric_mean}");
+        assert!(geometric_mean < arithmetic_mean);
+    }
+
+    #[test]
+    fn full_track_geometric_mean_return_known_cagr() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        let sample = |at: &mut At, ts_ns: i64, balance: i64| {
+            let market_state = MarketState::from_components(
+                QuoteCurrency::new(100, 0),
+                QuoteCurrency::new(101, 0),
+                ts_ns.into(),
+                0,
+            );
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::update(at, &market_state);
+            let balances = UserBalances {
+                available_wallet_balance: QuoteCurrency::new(balance, 0),
+                position_margin: QuoteCurrency::zero(),
+                order_margin: QuoteCurrency::zero(),
+                _q: std::marker::PhantomData,
+            };
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::sample_user_balances(
+                at,
+                &balances,
+                market_state.mid_price(),
+            );
+        };
+        // Three hourly returns of +10% each, two of them within the first day.
+        sample(&mut at, 0, 1000);
+        sample(&mut at, 3_600_000_000_000, 1100);
+        sample(&mut at, DAILY_NS, 1210);
+        sample(&mut at, 2 * DAILY_NS, 1331);
+        assert_eq!(at.num_samples(ReturnsSource::Hourly), 3);
+        assert_eq!(at.num_samples(ReturnsSource::Daily), 2);
+
+        let hourly_mean = at.geometric_mean_return(ReturnsSource::Hourly);
+        assert!((hourly_mean - 0.1).abs() < 1e-6, "{hourly_mean}");
+        // The daily returns of +21% and +10% only start from the first sample of each day.
+        let daily_mean = at.geometric_mean_return(ReturnsSource::Daily);
+        assert!(
+            (daily_mean - (1.331_f64.sqrt() - 1.0)).abs() < 1e-6,
+            "{daily_mean}"
+        );
+        assert_ne!(hourly_mean, daily_mean);
+        // Compounding either mean over its returns yields the total growth of 33.1%.
+        let cagr = (1.0 + hourly_mean).powi(3) - 1.0;
+        assert!((cagr - 0.331).abs() < 1e-6, "{cagr}");
+        let cagr = (1.0 + daily_mean).powi(2) - 1.0;
+        assert!((cagr - 0.331).abs() < 1e-6, "{cagr}");
+    }
+
+    #[test]
+    fn full_track_pain_index_and_ratio() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        let sample = |at: &mut At, ts_ns: i64, balance: i64| {
+            let market_state = MarketState::from_components(
+                QuoteCurrency::new(100, 0),
+                QuoteCurrency::new(101, 0),
+                ts_ns.into(),
+                0,
+            );
+            let balances = UserBalances {
+                available_wallet_balance: QuoteCurrency::new(balance, 0),
+                position_margin: QuoteCurrency::zero(),
+                order_margin: QuoteCurrency::zero(),
+                _q: std::marker::PhantomData,
+            };
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::update(at, &market_state);
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::sample_user_balances(
+                at,
+                &balances,
+                market_state.mid_price(),
+            );
+        };
+        assert_eq!(at.pain_index(ReturnsSource::Daily), 0.0);
+        assert_eq!(at.pain_ratio(), 0.0);
+
+        // Sampled over exactly one year, with drawdowns of 0.1, 0.05 and 110 / 1210.
+        for (i, balance) in [1000, 1100, 990, 1045, 1210, 1100].into_iter().enumerate() {
+            sample(&mut at, 1 + i as i64 * 73 * DAILY_NS, balance);
+        }
+        let pain_index = (0.1 + 0.05 + 110.0 / 1210.0) / 6.0;
+        assert!((at.pain_index(ReturnsSource::Daily) - pain_index).abs() < 1e-9);
+        assert!((at.pain_index(ReturnsSource::Hourly) - pain_index).abs() < 1e-9);
+        assert!((at.pain_ratio() - 0.1 / pain_index).abs() < 1e-6);
+
+        // Another sample on the same day, but in a different hour.
+        sample(&mut at, 1 + 365 * DAILY_NS + 3_600_000_000_000, 968);
+        assert!((at.pain_index(ReturnsSource::Daily) - pain_index).abs() < 1e-9);
+        let hourly_pain_index = (pain_index * 6.0 + 0.2) / 7.0;
+        assert!((at.pain_index(ReturnsSource::Hourly) - hourly_pain_index).abs() < 1e-9);
+    }
+
+    #[test]
+    fn full_track_breakeven_win_rate() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        let log_trade = |at: &mut At, side: Side, price: i64, qty: i64| {
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_trade(
+                at,
+                side,
+                QuoteCurrency::new(price, 0),
+                BaseCurrency::new(qty, 0),
+            );
+        };
+        assert_eq!(at.breakeven_win_rate(), 0.0);
+
+        // A long winning 10.
+        log_trade(&mut at, Side::Buy, 100, 1);
+        log_trade(&mut at, Side::Sell, 110, 1);
+        assert_eq!(at.breakeven_win_rate(), 0.0);
+
+        // A short winning 10 from partial closes.
+        log_trade(&mut at, Side::Sell, 100, 2);
+        log_trade(&mut at, Side::Buy, 90, 1);
+        log_trade(&mut at, Side::Buy, 100, 1);
+
+        // A long losing 30.
+        log_trade(&mut at, Side::Buy, 100, 1);
+        log_trade(&mut at, Side::Sell, 70, 1);
+        assert_eq!(at.breakeven_win_rate(), 0.75);
+    }
+
+    #[test]
+    fn full_track_profit_concentration() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        let log_trade = |at: &mut At, side: Side, price: i64, qty: i64| {
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_trade(
+                at,
+                side,
+                QuoteCurrency::new(price, 0),
+                BaseCurrency::new(qty, 0),
+            );
+        };
+        assert_eq!(at.profit_concentration(1), 0.0);
+
+        // Two longs winning 5 each, a short winning 1000 and a long losing 20.
+        log_trade(&mut at, Side::Buy, 100, 1);
+        log_trade(&mut at, Side::Sell, 105, 1);
+        log_trade(&mut at, Side::Sell, 1100, 1);
+        log_trade(&mut at, Side::Buy, 100, 1);
+        log_trade(&mut at, Side::Buy, 100, 1);
+        log_trade(&mut at, Side::Sell, 105, 1);
+        log_trade(&mut at, Side::Buy, 100, 1);
+        log_trade(&mut at, Side::Sell, 80, 1);
+
+        assert!((at.profit_concentration(1) - 1000.0 / 1010.0).abs() < 1e-9);
+        assert!(at.profit_concentration(1) > 0.99);
+        assert!((at.profit_concentration(2) - 1005.0 / 1010.0).abs() < 1e-9);
+        assert_eq!(at.profit_concentration(3), 1.0);
+        assert_eq!(at.profit_concentration(10), 1.0);
+        assert_eq!(at.profit_concentration(0), 0.0);
+    }
+
+    #[test]
+    fn full_track_expected_value_per_trade() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        let log_trade = |at: &mut At, side: Side, price: i64, qty: i64| {
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_trade(
+                at,
+                side,
+                QuoteCurrency::new(price, 0),
+                BaseCurrency::new(qty, 0),
+            );
+        };
+        assert_eq!(at.expected_value_per_trade(), 0.0);
+
+        // A long winning 10, a short winning 20 and a long losing 15.
+        log_trade(&mut at, Side::Buy, 100, 1);
+        log_trade(&mut at, Side::Sell, 110, 1);
+        log_trade(&mut at, Side::Sell, 110, 2);
+        log_trade(&mut at, Side::Buy, 100, 2);
+        log_trade(&mut at, Side::Buy, 100, 1);
+        assert_eq!(at.expected_value_per_trade(), 15.0);
+        log_trade(&mut at, Side::Sell, 85, 1);
+        assert_eq!(at.expected_value_per_trade(), 5.0);
+    }
+
+    #[test]
+    fn full_track_net_pnl() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        assert_eq!(at.net_pnl(), 0.0);
+
+        <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_trade(
+            &mut at,
+            Side::Buy,
+            QuoteCurrency::new(100, 0),
+            BaseCurrency::new(2, 0),
+        );
+        <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_fee(&mut at, QuoteCurrency::new(12, 2));
+        assert_eq!(at.net_pnl(), -0.12);
+
+        // A winning trade of 20 reduced by the fees of both fills.
+        <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_trade(
+            &mut at,
+            Side::Sell,
+            QuoteCurrency::new(110, 0),
+            BaseCurrency::new(2, 0),
+        );
+        <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_fee(&mut at, QuoteCurrency::new(13, 2));
+        assert_eq!(at.net_pnl(), 19.75);
+    }
+
+    #[test]
+    fn full_track_num_sessions() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        let update = |at: &mut At, ts: i64| {
+            let market_state = MarketState::from_components(
+                QuoteCurrency::new(100, 0),
+                QuoteCurrency::new(101, 0),
+                ts.into(),
+                0,
+            );
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::update(at, &market_state);
+        };
+        assert_eq!(at.num_sessions(1_000), 0);
+
+        // Updates every second with a gap of one hour in between.
+        for ts in [1, 2, 3, 3_603, 3_604].map(|secs| secs * 1_000_000_000) {
+            update(&mut at, ts);
+        }
+        assert_eq!(at.num_sessions(60_000_000_000), 2);
+        assert_eq!(at.num_sessions(3_600_000_000_000), 1);
+        assert_eq!(at.num_sessions(0), 5);
+    }
+
+    #[test]
+    fn full_track_daily_realized_pnl() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        let update = |at: &mut At, ts: i64| {
+            let market_state = MarketState::from_components(
+                QuoteCurrency::new(100, 0),
+                QuoteCurrency::new(101, 0),
+                ts.into(),
+                0,
+            );
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::update(at, &market_state);
+        };
+        let log_trade = |at: &mut At, side: Side, price: i64| {
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_trade(
+                at,
+                side,
+                QuoteCurrency::new(price, 0),
+                BaseCurrency::new(1, 0),
+            );
+        };
+        assert!(at.daily_realized_pnl().is_empty());
+
+        // Two round trips on the first day and one losing trade on the second.
+        update(&mut at, 1);
+        log_trade(&mut at, Side::Buy, 100);
+        log_trade(&mut at, Side::Sell, 110);
+        update(&mut at, DAILY_NS - 1);
+        log_trade(&mut at, Side::Sell, 110);
+        log_trade(&mut at, Side::Buy, 105);
+        // Opening a position overnight only realizes on the second day.
+        log_trade(&mut at, Side::Buy, 100);
+        update(&mut at, DAILY_NS + 1);
+        log_trade(&mut at, Side::Sell, 90);
+        assert_eq!(
+            at.daily_realized_pnl(),
+            &[(0, 15.0), (DAILY_NS as u64, -10.0)]
+        );
+    }
+
+    #[test]
+    fn full_track_time_in_profit_ratio() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        let update = |at: &mut At, mid_price: i64| {
+            let market_state = MarketState::from_components(
+                QuoteCurrency::new(mid_price - 1, 0),
+                QuoteCurrency::new(mid_price + 1, 0),
+                1.into(),
+                0,
+            );
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::update(at, &market_state);
+        };
+        let log_trade = |at: &mut At, side: Side, price: i64| {
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_trade(
+                at,
+                side,
+                QuoteCurrency::new(price, 0),
+                BaseCurrency::new(1, 0),
+            );
+        };
+        assert_eq!(at.time_in_profit_ratio(), 0.0);
+
+        // Break-even does not count as being in profit.
+        update(&mut at, 100);
+        log_trade(&mut at, Side::Buy, 100);
+        update(&mut at, 110);
+        update(&mut at, 95);
+        update(&mut at, 105);
+        assert_eq!(at.time_in_profit_ratio(), 0.5);
+
+        // The realized profit of 5 keeps the account in profit while flat.
+        log_trade(&mut at, Side::Sell, 105);
+        update(&mut at, 90);
+        assert_eq!(at.time_in_profit_ratio(), 0.6);
+    }
+
+    #[test]
+    fn full_track_information_ratio() {
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        let sample = |at: &mut At, balance: i64, mid_price: QuoteCurrency<i64, 4>| {
+            let balances = UserBalances {
+                available_wallet_balance: QuoteCurrency::new(balance, 0),
+                position_margin: QuoteCurrency::zero(),
+                order_margin: QuoteCurrency::zero(),
+                _q: std::marker::PhantomData,
+            };
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::sample_user_balances(
+                at, &balances, mid_price,
+            );
+        };
+        let balances = [100, 101, 103, 102];
+
+        // With a flat market the active returns are the returns of the user balances.
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        assert_eq!(at.information_ratio(ReturnsSource::Daily), 0.0);
+        for balance in balances {
+            sample(&mut at, balance, QuoteCurrency::new(1000, 0));
+        }
+        let expected = at.sharpe().unwrap() as f64 * 365_f64.sqrt();
+        assert!((at.information_ratio(ReturnsSource::Daily) - expected).abs() < 1e-3);
+
+        // A market growing by 1% per period shifts the active returns by its ln return.
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        let mid_prices = [
+            QuoteCurrency::new(1000, 0),
+            QuoteCurrency::new(1010, 0),
+            QuoteCurrency::new(10201, 1),
+            QuoteCurrency::new(1030301, 3),
+        ];
+        for (balance, mid_price) in balances.into_iter().zip(mid_prices) {
+            sample(&mut at, balance, mid_price);
+        }
+        let std_dev = at.user_balances_ln_return_stats.last().unwrap() as f64;
+        let mean_active_return = at.user_balances_ln_return_stats.mean() as f64 - 1.01_f64.ln();
+        let expected = mean_active_return / std_dev * 8760_f64.sqrt();
+        assert!((at.information_ratio(ReturnsSource::Hourly) - expected).abs() < 1e-2);
+        assert!(at.information_ratio(ReturnsSource::Hourly) < 0.0);
+
+        // Tracking the market exactly has no tracking error.
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        for balance in [100, 110, 121] {
+            sample(&mut at, balance, QuoteCurrency::new(balance, 0));
+        }
+        assert_eq!(at.information_ratio(ReturnsSource::Daily), 0.0);
+    }
+
+    #[test]
+    fn full_track_avg_order_book_imbalance() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        assert_eq!(at.avg_order_book_imbalance(), None);
+
+        // Not sampled while no position is held.
+        <At as AccountTracker<_, 4, _, NoUserOrderId>>::sample_market_microstructure(
+            &mut at,
+            BaseCurrency::new(1, 0),
+            BaseCurrency::new(3, 0),
+            false,
+        );
+        assert_eq!(at.avg_order_book_imbalance(), None);
+
+        <At as AccountTracker<_, 4, _, NoUserOrderId>>::sample_market_microstructure(
+            &mut at,
+            BaseCurrency::new(3, 0),
+            BaseCurrency::new(1, 0),
+            true,
+        );
+        assert_eq!(at.avg_order_book_imbalance(), Some(0.5));
+        <At as AccountTracker<_, 4, _, NoUserOrderId>>::sample_market_microstructure(
+            &mut at,
+            BaseCurrency::new(7, 0),
+            BaseCurrency::new(1, 0),
+            true,
+        );
+        assert_eq!(at.avg_order_book_imbalance(), Some(0.625));
+    }
+
+    #[test]
+    fn full_track_metrics() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_trade(
+            &mut at,
+            Side::Buy,
+            QuoteCurrency::new(100, 0),
+            BaseCurrency::new(2, 0),
+        );
+        <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_trade(
+            &mut at,
+            Side::Sell,
+            QuoteCurrency::new(100, 0),
+            BaseCurrency::new(1, 0),
+        );
+        <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_market_order_fill(&mut at);
+
+        let metrics = at.metrics();
+        assert_eq!(metrics["wallet_balance_start"], 1000.0);
+        assert_eq!(metrics["buy_volume"], 200.0);
+        assert_eq!(metrics["sell_volume"], 100.0);
+        assert_eq!(metrics["turnover"], 300.0);
+        assert_eq!(metrics["num_filled_market_orders"], 1.0);
+        assert_eq!(metrics["num_submitted_limit_orders"], 0.0);
+        assert_eq!(metrics["buy_volume_ratio"], 0.6666_f32 as f64);
+        // Not enough samples for these yet.
+        assert!(!metrics.contains_key("sharpe"));
+        assert!(!metrics.contains_key("sortino"));
+    }
+
+    #[test]
+    fn full_track_metrics_keys() {
+        let at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        let metrics = at.metrics();
+        for key in [
+            "wallet_balance_start",
+            "rpnl",
+            "kelly_leverage",
+            "buy_volume",
+            "sell_volume",
+            "turnover",
+            "cumulative_fees",
+            "effective_fee_rate",
+            "buy_and_hold_return",
+            "sell_and_hold_return",
+            "num_trading_days",
+            "limit_order_fill_ratio",
+            "limit_order_cancellation_ratio",
+            "drawdown_user_balances",
+            "drawdown_market",
+            "num_submitted_limit_orders",
+            "num_cancelled_limit_orders",
+            "num_filled_limit_order_events",
+            "num_fully_filled_limit_orders",
+            "num_submitted_market_orders",
+            "num_filled_market_orders",
+            "avg_trade_duration_ticks",
+            "breakeven_win_rate",
+            "information_ratio",
+            "pain_index",
+            "pain_ratio",
+            "time_in_profit_ratio",
+            "expected_value_per_trade",
+            "net_pnl",
+            "beta",
+            "treynor_ratio",
+        ] {
+            assert!(metrics.contains_key(key), "missing metric {key}");
+        }
+        // Only the optional metrics are missing without any samples.
+        assert_eq!(metrics.len(), 31);
+    }
+
+    #[test]
+    fn full_track_metrics_without_enough_samples() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        let metrics = at.metrics();
+        assert!(metrics.values().all(|value| value.is_finite()));
+        assert!(!metrics.contains_key("sharpe"));
+        assert!(!metrics.contains_key("sortino"));
+        assert_eq!(metrics["kelly_leverage"], 0.0);
+        assert_eq!(metrics["limit_order_fill_ratio"], 0.0);
+        assert_eq!(metrics["limit_order_cancellation_ratio"], 0.0);
+
+        // A single negative return has no std dev.
+        for balance in [1000, 990] {
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::sample_user_balances(
+                &mut at,
+                &UserBalances {
+                    available_wallet_balance: QuoteCurrency::new(balance, 0),
+                    position_margin: QuoteCurrency::zero(),
+                    order_margin: QuoteCurrency::zero(),
+                    _q: std::marker::PhantomData,
+                },
+                QuoteCurrency::new(100, 0),
+            );
+        }
+        let metrics = at.metrics();
+        assert!(metrics.values().all(|value| value.is_finite()));
+        assert!(!metrics.contains_key("sharpe"));
+        assert!(!metrics.contains_key("sortino"));
+        assert_eq!(metrics["kelly_leverage"], 0.0);
+        assert_eq!(at.modified_sharpe(ReturnsSource::Daily), 0.0);
+    }
+
+    #[test]
+    fn full_track_effective_fee_rate() {
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        assert_eq!(at.effective_fee_rate(), 0.0);
+
+        <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_trade(
+            &mut at,
+            Side::Buy,
+            QuoteCurrency::new(100, 0),
+            BaseCurrency::new(2, 0),
+        );
+        <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_fee(&mut at, QuoteCurrency::new(12, 2));
+        <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_trade(
+            &mut at,
+            Side::Sell,
+            QuoteCurrency::new(100, 0),
+            BaseCurrency::new(3, 0),
+        );
+        <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_fee(&mut at, QuoteCurrency::new(6, 2));
+
+        assert_eq!(at.cumulative_fees(), QuoteCurrency::new(18, 2));
+        assert_eq!(at.turnover(), QuoteCurrency::new(500, 0));
+        assert_eq!(at.effective_fee_rate(), 0.18 / 500.0);
+    }
+
+    #[test]
+    fn full_track_save_and_load_round_trip() {
+        type At = FullAccountTracker<i64, 4, QuoteCurrency<i64, 4>>;
+        let mut at = FullAccountTracker::new(QuoteCurrency::<i64, 4>::new(1000, 0));
+        <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_trade(
+            &mut at,
+            Side::Buy,
+            QuoteCurrency::new(100, 0),
+            BaseCurrency::new(2, 0),
+        );
+        <At as AccountTracker<_, 4, _, NoUserOrderId>>::log_fee(&mut at, QuoteCurrency::new(12, 2));
+        for (balance, mid_price) in [
+            (1000, 100),
+            (1010, 101),
+            (990, 99),
+            (1030, 102),
+            (1020, 104),
+        ] {
+            let balances = UserBalances {
+                available_wallet_balance: QuoteCurrency::new(balance, 0),
+                position_margin: QuoteCurrency::zero(),
+                order_margin: QuoteCurrency::zero(),
+                _q: std::marker::PhantomData,
+            };
+            <At as AccountTracker<_, 4, _, NoUserOrderId>>::sample_user_balances(
+                &mut at,
+                &balances,
+                QuoteCurrency::new(mid_price, 0),
+            );
+        }
+
+        let path = std::env::temp_dir().join("lfest_full_track_save_and_load_round_trip.json");
+        at.save_to_path(&path).unwrap();
+        let loaded = At::load_from_path(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.wallet_balance_start(), at.wallet_balance_start());
+        assert_eq!(loaded.buy_volume(), at.buy_volume());
+        assert_eq!(loaded.cumulative_fees(), at.cumulative_fees());
+        assert!(at.sharpe().is_some());
+        assert_eq!(loaded.sharpe(), at.sharpe());
+        assert_eq!(loaded.sortino(), at.sortino());
+        assert_eq!(loaded.drawdown_user_balances(), at.drawdown_user_balances());
+        assert_eq!(
+            loaded.information_ratio(ReturnsSource::Daily),
+            at.information_ratio(ReturnsSource::Daily)
+        );
+    }
 }
@@ -3,9 +3,11 @@
 mod account_tracker_trait;
 mod full_track;
 mod no_track;
+mod returns_source;
 mod statistical_moments;
 
 pub use account_tracker_trait::AccountTracker;
 pub use full_track::FullAccountTracker;
 pub use no_track::NoAccountTracker;
+pub use returns_source::ReturnsSource;
 pub use statistical_moments::*;
@@ -2,7 +2,7 @@ use std::fmt::Display;
 
 use crate::{
     account_tracker::AccountTracker,
-    prelude::{MarketState, Mon, QuoteCurrency, Side, UserBalances},
+    prelude::{MarketState, Mon, QuoteCurrency, Side, TimestampNs, UserBalances},
     types::{LimitOrder, MarginCurrency, MarketOrder, NewOrder, UserOrderIdT},
 };
 
@@ -17,9 +17,15 @@ where
     BaseOrQuote: MarginCurrency<I, D>,
     UserOrderId: UserOrderIdT,
 {
+    #[inline(always)]
+    fn reset(&mut self, _wallet_balance: BaseOrQuote) {}
+
     #[inline(always)]
     fn update(&mut self, _market_state: &MarketState<I, D>) {}
 
+    #[inline(always)]
+    fn log_timestamp(&mut self, _timestamp_ns: TimestampNs) {}
+
     #[inline(always)]
     fn sample_user_balances(
         &mut self,
@@ -28,6 +34,15 @@ where
     ) {
     }
 
+    #[inline(always)]
+    fn sample_market_microstructure(
+        &mut self,
+        _bid_volume: BaseOrQuote::PairedCurrency,
+        _ask_volume: BaseOrQuote::PairedCurrency,
+        _position_open: bool,
+    ) {
+    }
+
     #[inline(always)]
     fn log_limit_order_submission(
         &mut self,
@@ -64,6 +79,9 @@ where
         _quantity: BaseOrQuote::PairedCurrency,
     ) {
     }
+
+    #[inline(always)]
+    fn log_fee(&mut self, _fee: BaseOrQuote) {}
 }
 
 impl Display for NoAccountTracker {
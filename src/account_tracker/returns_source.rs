@@ -0,0 +1,27 @@
+/// The interval at which the returns of the user balances are sampled,
+/// which defines the factor for annualizing ratios like `sharpe`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReturnsSource {
+    /// The returns are sampled once per day.
+    Daily,
+    /// The returns are sampled once per hour.
+    Hourly,
+}
+
+impl ReturnsSource {
+    /// The number of sampling periods in a year.
+    pub fn periods_per_year(&self) -> f64 {
+        match self {
+            ReturnsSource::Daily => 365.0,
+            ReturnsSource::Hourly => 24.0 * 365.0,
+        }
+    }
+
+    /// The duration of a sampling period in nanoseconds.
+    pub(crate) fn period_ns(&self) -> i64 {
+        match self {
+            ReturnsSource::Daily => 86_400_000_000_000,
+            ReturnsSource::Hourly => 3_600_000_000_000,
+        }
+    }
+}
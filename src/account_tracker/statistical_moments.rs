@@ -34,6 +34,13 @@ pub fn statistical_moments(vals: &[f64]) -> StatisticalMoments {
     }
 }
 
+/// Adjust the standard normal quantile `z` for the `skew` and `excess_kurtosis`
+/// of a distribution using the Cornish-Fisher expansion.
+pub fn cornish_fisher_quantile(z: f64, skew: f64, excess_kurtosis: f64) -> f64 {
+    z + (z.powi(2) - 1.0) * skew / 6.0 + (z.powi(3) - 3.0 * z) * excess_kurtosis / 24.0
+        - (2.0 * z.powi(3) - 5.0 * z) * skew.powi(2) / 36.0
+}
+
 #[cfg(test)]
 mod tests {
     use rand::{thread_rng, Rng};
@@ -63,4 +70,14 @@ mod tests {
             -1.6660010752838508
         );
     }
+
+    #[test]
+    fn test_cornish_fisher_quantile() {
+        // Without skew and excess kurtosis, the normal quantile is unchanged.
+        assert_eq!(cornish_fisher_quantile(-1.645, 0.0, 0.0), -1.645);
+        // Negative skew and fat tails push the quantile further into the left tail.
+        assert!(cornish_fisher_quantile(-1.645, -1.0, 0.0) < -1.645);
+        assert!(cornish_fisher_quantile(-1.645, 0.0, 3.0) < -1.645);
+        assert!(cornish_fisher_quantile(-1.645, 1.0, 0.0) > -1.645);
+    }
 }
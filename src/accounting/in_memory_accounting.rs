@@ -9,7 +9,7 @@ use crate::{
     Result,
 };
 
-const N_ACCOUNTS: usize = 6;
+const N_ACCOUNTS: usize = 10;
 // The accounts denoted in the margin currency.
 /// The users wallet account.
 pub const USER_WALLET_ACCOUNT: usize = 0;
@@ -23,6 +23,15 @@ pub const EXCHANGE_FEE_ACCOUNT: usize = 3;
 pub const BROKER_MARGIN_ACCOUNT: usize = 4;
 /// The treasury account.
 pub const TREASURY_ACCOUNT: usize = 5;
+/// The users rebate account, where negative fees (maker rebates) accrue.
+pub const REBATE_ACCOUNT: usize = 6;
+/// The users realized profit account, where profits accrue until they are swept into the wallet.
+pub const REALIZED_PNL_ACCOUNT: usize = 7;
+/// The external account, where profits withdrawn from the users wallet are transferred to.
+pub const WITHDRAWAL_ACCOUNT: usize = 8;
+/// The users pending profit and loss account, where the profit and loss of partial closes
+/// accrues until the position is fully closed, when using `PnlRealizationPolicy::OnFullClose`.
+pub const PENDING_PNL_ACCOUNT: usize = 9;
 
 /// Keeps track of transaction in memory.
 #[derive(Debug)]
@@ -2,7 +2,9 @@ use getset::CopyGetters;
 
 use super::{
     AccountId, MarginCurrency, Mon, QuoteCurrency, BROKER_MARGIN_ACCOUNT, EXCHANGE_FEE_ACCOUNT,
-    TREASURY_ACCOUNT, USER_ORDER_MARGIN_ACCOUNT, USER_POSITION_MARGIN_ACCOUNT, USER_WALLET_ACCOUNT,
+    PENDING_PNL_ACCOUNT, REALIZED_PNL_ACCOUNT, REBATE_ACCOUNT, TREASURY_ACCOUNT,
+    USER_ORDER_MARGIN_ACCOUNT, USER_POSITION_MARGIN_ACCOUNT, USER_WALLET_ACCOUNT,
+    WITHDRAWAL_ACCOUNT,
 };
 
 /// A transaction involves two parties.
@@ -46,6 +48,10 @@ fn account_from_int(int: usize) -> &'static str {
         EXCHANGE_FEE_ACCOUNT => "EXCHANGE_FEE_ACCOUNT",
         BROKER_MARGIN_ACCOUNT => "BROKER_MARGIN_ACCOUNT",
         TREASURY_ACCOUNT => "TREASURY_ACCOUNT",
+        REBATE_ACCOUNT => "REBATE_ACCOUNT",
+        REALIZED_PNL_ACCOUNT => "REALIZED_PNL_ACCOUNT",
+        WITHDRAWAL_ACCOUNT => "WITHDRAWAL_ACCOUNT",
+        PENDING_PNL_ACCOUNT => "PENDING_PNL_ACCOUNT",
         _ => panic!("invalid account"),
     }
 }
@@ -1,11 +1,15 @@
-use getset::{CopyGetters, Getters};
+use const_decimal::Decimal;
+use getset::{CopyGetters, Getters, Setters};
 
 use crate::{
     contract_specification::ContractSpecification,
-    prelude::{ConfigError, MarginCurrency, Mon},
+    prelude::{
+        ConfigError, MarginCurrency, Mon, PnlRealizationPolicy, ProfitWithdrawal,
+        SelfTradePrevention, TimestampNs,
+    },
 };
 
-#[derive(Debug, Clone, Getters, CopyGetters)]
+#[derive(Debug, Clone, Getters, CopyGetters, Setters)]
 /// Define the Exchange configuration.
 ///
 /// Generics:
@@ -38,6 +42,136 @@ where
     /// This is used to analyze the trading performance later on, to enable things like `sharpe`, `sortino`, anything based on returns.
     #[getset(get_copy = "pub")]
     sample_returns_every_n_seconds: u64,
+
+    /// If `true`, maker rebates (negative fees) are paid out of the `TREASURY_ACCOUNT`
+    /// when the position is reduced. Otherwise they only offset the fees of the position.
+    #[getset(get_copy = "pub", set = "pub")]
+    credit_maker_rebates: bool,
+
+    /// If `true`, credited maker rebates accrue to the `REBATE_ACCOUNT` and can be queried
+    /// with `Exchange::accrued_rebates`. Otherwise they are credited to the wallet directly.
+    /// Only applies if `credit_maker_rebates` is enabled.
+    #[getset(get_copy = "pub", set = "pub")]
+    separate_rebate_account: bool,
+
+    /// If `true`, realized profits accrue to the `REALIZED_PNL_ACCOUNT`, where they don't back
+    /// new margin until swept into the wallet with `Exchange::sweep_realized_pnl`.
+    /// Realized losses are always debited from the wallet.
+    #[getset(get_copy = "pub", set = "pub")]
+    separate_realized_pnl_account: bool,
+
+    /// Decides when the profit and loss of partially closed positions is realized.
+    #[getset(get_copy = "pub", set = "pub")]
+    pnl_realization_policy: PnlRealizationPolicy,
+
+    /// The optional timestamp at which a dated futures contract expires.
+    /// `Exchange::settle_at_expiry` can only be called once it is reached.
+    #[getset(get_copy = "pub", set = "pub")]
+    expiry_ts: Option<TimestampNs>,
+
+    /// The optional minimum quantity a market update must provide to fill a limit order.
+    /// Smaller fills are deferred, unless they would fully fill the remaining order quantity.
+    #[getset(get_copy = "pub", set = "pub")]
+    min_fill_qty: Option<BaseOrQuote::PairedCurrency>,
+
+    /// The optional maximum fraction (in range (0..1]) of the total quantity of a limit order
+    /// that a single market update may fill, so large orders fill across multiple updates.
+    /// At least one quantity tick fills per update, so small orders still fill eventually.
+    #[getset(get_copy = "pub", set = "pub")]
+    max_fill_fraction_per_tick: Option<Decimal<I, D>>,
+
+    /// If `true`, the unrealized profit of the position counts as available margin
+    /// for new orders in the risk checks. Otherwise only the wallet balance backs new orders.
+    #[getset(get_copy = "pub", set = "pub")]
+    credit_unrealized_pnl: bool,
+
+    /// If `true`, `Exchange::unrealized_pnl` includes the funding accrued by the position
+    /// towards the next funding rate supplied with `Exchange::set_funding_rates`.
+    #[getset(get_copy = "pub", set = "pub")]
+    funding_aware_unrealized_pnl: bool,
+
+    /// If `true`, the outstanding fees of the position are deducted from the available wallet balance
+    /// in `Exchange::user_balances` and the risk checks, even before they are settled.
+    #[getset(get_copy = "pub", set = "pub")]
+    reserve_outstanding_fees: bool,
+
+    /// If `true`, limit orders also fill from trades at their limit price,
+    /// once the trades consumed the volume ahead of them in the queue (see `QueuePosition`).
+    /// Otherwise the order is assumed to have the worst queue position and only trades through its price fill it.
+    #[getset(get_copy = "pub", set = "pub")]
+    queue_position_model: bool,
+
+    /// What to do when a new limit order would cross a resting limit order of the user.
+    #[getset(get_copy = "pub", set = "pub")]
+    self_trade_prevention: SelfTradePrevention,
+
+    /// If `true`, the order margin released by limit order fills is transferred once at the end
+    /// of each market update, instead of after every individual fill.
+    #[getset(get_copy = "pub", set = "pub")]
+    batch_order_margin_updates: bool,
+
+    /// The duration in nanoseconds after a liquidation during which new orders are rejected
+    /// with `RiskError::ReentryCooldownActive`. Zero disables the cooldown.
+    #[getset(get_copy = "pub", set = "pub")]
+    reentry_cooldown_ns: i64,
+
+    /// Only every n-th market update is passed to `AccountTracker::update`, which trades
+    /// the accuracy of the tracked market statistics for speed. Zero behaves like one.
+    #[getset(get_copy = "pub", set = "pub")]
+    account_tracker_update_every_n_ticks: u64,
+
+    /// The optional display precision of the margin currency, e.g. `0.01` for USD.
+    /// `Exchange::rounded_unrealized_pnl` rounds towards zero to a multiple of it.
+    #[getset(get_copy = "pub", set = "pub")]
+    margin_display_quantum: Option<BaseOrQuote>,
+
+    /// The optional minimum equity of the account. Once the equity drops below it,
+    /// `Exchange::update_state` returns `Error::AccountBlownUp` and no more orders are accepted.
+    #[getset(get_copy = "pub", set = "pub")]
+    min_wallet_balance: Option<BaseOrQuote>,
+
+    /// If set, positions are liquidated partially instead of fully,
+    /// once they breach their liquidation price.
+    /// Only as much is closed as needed to restore the margin ratio, being the total equity
+    /// relative to the notional value of the position, to the `maintenance_margin` of the contract
+    /// plus this buffer.
+    #[getset(get_copy = "pub", set = "pub")]
+    partial_liquidation_buffer: Option<Decimal<I, D>>,
+
+    /// If `true`, market orders walk the price levels of the most recent `Depth` update
+    /// and fill at their volume weighted average price.
+    /// Otherwise, or without depth information, they fill entirely at the best bid or ask.
+    #[getset(get_copy = "pub", set = "pub")]
+    fill_market_orders_from_depth: bool,
+
+    /// If set, resting limit orders that would increase the position are cancelled in
+    /// `Exchange::update_state` once the margin ratio, being the total equity relative to the
+    /// notional value of the position at the mid price, drops below this threshold.
+    /// Orders that reduce the position are kept.
+    #[getset(get_copy = "pub", set = "pub")]
+    reduce_only_margin_ratio: Option<Decimal<I, D>>,
+
+    /// The maximum number of orders that may be submitted within one second of the simulated
+    /// exchange time, after which new orders are rejected with `Error::RateLimitExceeded`.
+    /// `None` disables the rate limit.
+    #[getset(get_copy = "pub", set = "pub")]
+    max_orders_per_second: Option<usize>,
+
+    /// If `true`, rejected market and limit order submissions are recorded with their `Error`,
+    /// available through `Exchange::rejected_orders`. Disabled by default to save memory.
+    #[getset(get_copy = "pub", set = "pub")]
+    record_rejected_orders: bool,
+
+    /// If set, a fraction of the profit realized within each interval is withdrawn
+    /// from the wallet to the `WITHDRAWAL_ACCOUNT`, reducing the compounding of profits.
+    #[getset(get_copy = "pub", set = "pub")]
+    profit_withdrawal: Option<ProfitWithdrawal<I, D>>,
+
+    /// If `true`, the long and short legs of the `Exchange::hedge_position` are held
+    /// simultaneously and opened or closed with `Exchange::submit_hedge_market_order`.
+    /// The one-way `Position` is then unused, so all other orders are rejected.
+    #[getset(get_copy = "pub", set = "pub")]
+    hedge_mode: bool,
 }
 
 impl<I, const D: u8, BaseOrQuote> Config<I, D, BaseOrQuote>
@@ -76,6 +210,30 @@ where
             max_num_open_orders,
             contract_spec: contract_specification,
             sample_returns_every_n_seconds,
+            credit_maker_rebates: false,
+            separate_rebate_account: false,
+            separate_realized_pnl_account: false,
+            pnl_realization_policy: PnlRealizationPolicy::default(),
+            expiry_ts: None,
+            min_fill_qty: None,
+            max_fill_fraction_per_tick: None,
+            credit_unrealized_pnl: false,
+            funding_aware_unrealized_pnl: false,
+            reserve_outstanding_fees: false,
+            queue_position_model: false,
+            self_trade_prevention: SelfTradePrevention::default(),
+            batch_order_margin_updates: false,
+            reentry_cooldown_ns: 0,
+            account_tracker_update_every_n_ticks: 1,
+            margin_display_quantum: None,
+            min_wallet_balance: None,
+            partial_liquidation_buffer: None,
+            fill_market_orders_from_depth: false,
+            reduce_only_margin_ratio: None,
+            max_orders_per_second: None,
+            record_rejected_orders: false,
+            profit_withdrawal: None,
+            hedge_mode: false,
         })
     }
 }
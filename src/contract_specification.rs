@@ -23,17 +23,31 @@ where
     #[getset(get = "pub", set = "pub")]
     ticker: String,
 
+    /// An optional label of the margin currency (e.g. "USDT"),
+    /// used instead of the generic currency name when formatting margin amounts.
+    #[getset(get = "pub", set = "pub")]
+    margin_currency_label: Option<String>,
+
     /// The initial deposit required to open a new futures position.
     /// Expressed as basis points.
     #[getset(get_copy = "pub")]
     init_margin_req: Decimal<I, D>,
 
+    /// The optional maximum leverage the exchange allows for this contract.
+    #[getset(get_copy = "pub")]
+    max_leverage: Option<Leverage<I, D>>,
+
     /// The minimum amount that must be maintained in the traders account to
     /// keep existing positions open.
     /// Expressed as basis points.
     #[getset(get_copy = "pub")]
     maintenance_margin: Decimal<I, D>,
 
+    /// The fraction of the entry price by which the liquidation price moves towards the entry,
+    /// so liquidation triggers slightly before the strict maintenance margin breach.
+    #[getset(get_copy = "pub")]
+    liquidation_buffer: Decimal<I, D>,
+
     /// The method for computing `mark-to-market`.
     #[getset(get_copy = "pub", set = "pub")]
     mark_method: MarkMethod,
@@ -53,6 +67,10 @@ where
     /// The taker fee as parts per 100_000
     #[getset(get_copy = "pub")]
     fee_taker: Fee<I, D, Taker>,
+
+    /// The optional, usually reduced, taker fee of a marketable limit order
+    /// which executes immediately. Falls back to `fee_taker` if not set.
+    fee_taker_limit: Option<Fee<I, D, Taker>>,
 }
 
 impl<I, const D: u8, BaseOrQuote> ContractSpecification<I, D, BaseOrQuote>
@@ -71,6 +89,10 @@ where
     /// `quantity_filter`: The rules for quantities in the market.
     /// `fee_maker`: The fee a maker pays.
     /// `fee_taker`: The fee a taker pays.
+    ///
+    /// # Returns:
+    /// `ConfigError::InvalidInitMarginReq` if the `init_margin_req` derived from `leverage`
+    /// rounds to zero at the decimal precision `D`.
     pub fn new(
         leverage: Leverage<I, D>,
         maintenance_margin: Decimal<I, D>,
@@ -84,18 +106,80 @@ where
         }
 
         let init_margin_req = leverage.init_margin_req();
+        if init_margin_req <= Decimal::zero() {
+            return Err(ConfigError::InvalidInitMarginReq);
+        }
 
         Ok(Self {
             ticker: String::new(),
+            margin_currency_label: None,
             init_margin_req,
+            max_leverage: None,
             maintenance_margin: init_margin_req * maintenance_margin,
+            liquidation_buffer: Decimal::zero(),
             mark_method: MarkMethod::default(),
             price_filter,
             quantity_filter,
             fee_maker,
             fee_taker,
+            fee_taker_limit: None,
         })
     }
+
+    /// Set the maximum leverage of the contract.
+    ///
+    /// # Returns:
+    /// `ConfigError::LeverageTooHigh` if the chosen leverage, derived from `init_margin_req`, exceeds `max_leverage`.
+    pub fn with_max_leverage(mut self, max_leverage: Leverage<I, D>) -> Result<Self, ConfigError> {
+        if self.init_margin_req < max_leverage.init_margin_req() {
+            return Err(ConfigError::LeverageTooHigh);
+        }
+        self.max_leverage = Some(max_leverage);
+        Ok(self)
+    }
+
+    /// Set the taker fee of marketable limit orders which execute immediately,
+    /// instead of the `fee_taker` of market orders.
+    pub fn with_fee_taker_limit(mut self, fee_taker_limit: Fee<I, D, Taker>) -> Self {
+        self.fee_taker_limit = Some(fee_taker_limit);
+        self
+    }
+
+    /// The taker fee of a marketable limit order which executes immediately,
+    /// being the `fee_taker` unless a separate `fee_taker_limit` is set.
+    pub fn fee_taker_limit(&self) -> Fee<I, D, Taker> {
+        self.fee_taker_limit.unwrap_or(self.fee_taker)
+    }
+
+    /// Set the liquidation buffer of the contract.
+    ///
+    /// # Returns:
+    /// `ConfigError::InvalidLiquidationBuffer` if the `liquidation_buffer` is negative
+    /// or not smaller than the `maintenance_margin`.
+    pub fn with_liquidation_buffer(
+        mut self,
+        liquidation_buffer: Decimal<I, D>,
+    ) -> Result<Self, ConfigError> {
+        if liquidation_buffer < Decimal::zero() || liquidation_buffer >= self.maintenance_margin {
+            return Err(ConfigError::InvalidLiquidationBuffer);
+        }
+        self.liquidation_buffer = liquidation_buffer;
+        Ok(self)
+    }
+
+    /// The maintenance margin reduced by the `liquidation_buffer`,
+    /// which determines the liquidation price of a position.
+    pub(crate) fn liquidation_margin_req(&self) -> Decimal<I, D> {
+        self.maintenance_margin - self.liquidation_buffer
+    }
+
+    /// Format an amount of the margin currency, using the `margin_currency_label` if one is set.
+    pub fn format_margin(&self, amount: BaseOrQuote::PairedCurrency) -> String {
+        match &self.margin_currency_label {
+            Some(label) => format!("{} {label}", amount.as_ref()),
+            None => amount.to_string(),
+        }
+    }
 }
 
 impl<I, const D: u8, BaseOrQuote> Default for ContractSpecification<I, D, BaseOrQuote>
@@ -136,3 +220,106 @@ impl Default for MarkMethod {
         Self::BidAsk
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_fee_maker, test_fee_taker};
+
+    fn contract_spec(
+        leverage: Leverage<i64, 5>,
+    ) -> ContractSpecification<i64, 5, BaseCurrency<i64, 5>> {
+        ContractSpecification::new(
+            leverage,
+            Decimal::try_from_scaled(5, 1).unwrap(),
+            PriceFilter::default(),
+            QuantityFilter::default(),
+            test_fee_maker(),
+            test_fee_taker(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn contract_specification_max_leverage() {
+        assert_eq!(contract_spec(leverage!(5)).max_leverage(), None);
+
+        let spec = contract_spec(leverage!(5))
+            .with_max_leverage(leverage!(5))
+            .unwrap();
+        assert_eq!(spec.max_leverage(), Some(leverage!(5)));
+        assert!(contract_spec(leverage!(2))
+            .with_max_leverage(leverage!(5))
+            .is_ok());
+        assert_eq!(
+            contract_spec(leverage!(10))
+                .with_max_leverage(leverage!(5))
+                .unwrap_err(),
+            ConfigError::LeverageTooHigh
+        );
+    }
+
+    #[test]
+    fn contract_specification_fee_taker_limit() {
+        assert_eq!(
+            contract_spec(leverage!(1)).fee_taker_limit(),
+            test_fee_taker()
+        );
+
+        let fee = Fee::from(Decimal::try_from_scaled(4, 4).unwrap());
+        let spec = contract_spec(leverage!(1)).with_fee_taker_limit(fee);
+        assert_eq!(spec.fee_taker_limit(), fee);
+        assert_eq!(spec.fee_taker(), test_fee_taker());
+    }
+
+    #[test]
+    fn contract_specification_invalid_init_margin_req() {
+        // With two decimals, 1 / 255 rounds to zero.
+        assert_eq!(
+            ContractSpecification::<i64, 2, BaseCurrency<i64, 2>>::new(
+                leverage!(255),
+                Decimal::try_from_scaled(5, 1).unwrap(),
+                PriceFilter::default(),
+                QuantityFilter::default(),
+                Fee::from(Decimal::try_from_scaled(2, 2).unwrap()),
+                Fee::from(Decimal::try_from_scaled(6, 2).unwrap()),
+            )
+            .unwrap_err(),
+            ConfigError::InvalidInitMarginReq
+        );
+        // While five decimals are precise enough.
+        assert!(contract_spec(leverage!(255)).init_margin_req() > Decimal::zero());
+    }
+
+    #[test]
+    fn contract_specification_liquidation_buffer() {
+        assert_eq!(
+            contract_spec(leverage!(1)).liquidation_buffer(),
+            Decimal::zero()
+        );
+
+        let spec = contract_spec(leverage!(1))
+            .with_liquidation_buffer(Decimal::try_from_scaled(1, 1).unwrap())
+            .unwrap();
+        assert_eq!(
+            spec.liquidation_buffer(),
+            Decimal::try_from_scaled(1, 1).unwrap()
+        );
+        assert_eq!(
+            spec.liquidation_margin_req(),
+            Decimal::try_from_scaled(4, 1).unwrap()
+        );
+        assert_eq!(
+            contract_spec(leverage!(1))
+                .with_liquidation_buffer(Decimal::try_from_scaled(5, 1).unwrap())
+                .unwrap_err(),
+            ConfigError::InvalidLiquidationBuffer
+        );
+        assert_eq!(
+            contract_spec(leverage!(1))
+                .with_liquidation_buffer(Decimal::try_from_scaled(-1, 1).unwrap())
+                .unwrap_err(),
+            ConfigError::InvalidLiquidationBuffer
+        );
+    }
+}
@@ -1,29 +1,37 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, collections::HashMap};
 
 use assert2::assert;
+use const_decimal::Decimal;
 use getset::Getters;
-use num_traits::Zero;
+use num_traits::{One, Zero};
 use tracing::{debug, info, trace, warn};
 
 use crate::{
     account_tracker::AccountTracker,
     accounting::TransactionAccounting,
     config::Config,
+    hedge_position::{HedgeLeg, HedgePosition},
     market_state::MarketState,
     order_margin::OrderMargin,
     prelude::{
-        ActiveLimitOrders, Currency, MarketUpdate, Mon, OrderError, Position, QuoteCurrency,
-        RePricing, Transaction, EXCHANGE_FEE_ACCOUNT, USER_ORDER_MARGIN_ACCOUNT,
-        USER_POSITION_MARGIN_ACCOUNT, USER_WALLET_ACCOUNT,
+        ActiveLimitOrders, BaseCurrency, Bba, Currency, MarketUpdate, Mon, OrderError, Position,
+        QuoteCurrency, RePricing, RiskError, SelfTradePrevention, SmartCandle, Trade, Transaction,
+        EXCHANGE_FEE_ACCOUNT, PENDING_PNL_ACCOUNT, REALIZED_PNL_ACCOUNT, REBATE_ACCOUNT,
+        TREASURY_ACCOUNT, USER_ORDER_MARGIN_ACCOUNT, USER_POSITION_MARGIN_ACCOUNT,
+        USER_WALLET_ACCOUNT, WITHDRAWAL_ACCOUNT,
     },
     risk_engine::{IsolatedMarginRiskEngine, RiskEngine},
     sample_returns_trigger::SampleReturnsTrigger,
     types::{
-        Error, ExchangeOrderMeta, Filled, LimitOrder, LimitOrderUpdate, MarginCurrency,
-        MarketOrder, NewOrder, OrderId, Pending, Result, Side, TimestampNs, UserBalances,
+        Error, ExchangeOrderMeta, ExecutedTrade, Fee, Filled, LimitOrder, LimitOrderUpdate, Maker,
+        MarginCurrency, MarketOrder, NewOrder, OrderId, Pending, PositionSnapshot, QueuePosition,
+        RejectedOrder, Result, Side, StopLimitOrder, Taker, TimestampNs, UserBalances,
         UserOrderIdT,
     },
-    utils::assert_user_wallet_balance,
+    utils::{
+        assert_user_wallet_balance, assert_user_wallet_balance_with_credit, decimal_from_f64, max,
+        min,
+    },
 };
 
 /// Whether to cancel a limit order by its `OrderId` or the `UserOrderId`.
@@ -92,15 +100,86 @@ where
     #[cfg_attr(test, getset(get_mut = "pub(crate)"))]
     position: Position<I, D, BaseOrQuote>,
 
+    /// The long and short legs held in hedge mode.
+    /// Stays empty unless `Config::hedge_mode` is enabled.
+    #[getset(get = "pub")]
+    hedge_position: HedgePosition<I, D, BaseOrQuote>,
+
     /// Active limit orders of the user.
     /// Maps the order `id` to the actual `Order`.
     #[getset(get = "pub")]
     active_limit_orders: ActiveLimitOrders<I, D, BaseOrQuote, UserOrderId>,
 
+    /// Active stop-limit orders of the user, waiting for the market to reach their trigger price.
+    #[getset(get = "pub")]
+    active_stop_orders: Vec<StopLimitOrder<I, D, BaseOrQuote, UserOrderId>>,
+
     order_margin: OrderMargin<I, D, BaseOrQuote, UserOrderId>,
 
     sample_returns_trigger: SampleReturnsTrigger,
 
+    /// The average fill price of the most recently filled `MarketOrder`, if any.
+    last_market_fill_price: Option<QuoteCurrency<I, D>>,
+
+    /// The sum of `mark * dt` while the current position is open, used for `position_twap_mark`.
+    position_twap_mark_sum: f64,
+    /// The total duration in nanoseconds the current position has been open for.
+    position_twap_duration_ns: i64,
+
+    /// Whether the contract has been settled at expiry, after which no more orders are accepted.
+    expired: bool,
+    /// Whether the equity dropped below the `min_wallet_balance`, which ends the simulation.
+    blown_up: bool,
+    /// New orders are rejected until this timestamp is reached, following a liquidation.
+    reentry_cooldown_until_ns: TimestampNs,
+    /// If set with `set_halt`, new orders are rejected until the market timestamp passes it.
+    halted_until_ns: Option<TimestampNs>,
+    /// The rejected order submissions with their reason, if `Config::record_rejected_orders`.
+    rejected_orders: Vec<(RejectedOrder<I, D, BaseOrQuote, UserOrderId>, Error)>,
+
+    /// The best unrealized pnl reached during the currently open position, if any.
+    current_trade_mfe: Option<BaseOrQuote::PairedCurrency>,
+    /// The maximum favorable excursion of each closed position.
+    mfe_distribution: Vec<BaseOrQuote::PairedCurrency>,
+    /// The magnitude of the worst unrealized pnl reached during the currently open position.
+    current_trade_mae: Option<BaseOrQuote::PairedCurrency>,
+    /// The maximum adverse excursion of each closed position.
+    mae_distribution: Vec<BaseOrQuote::PairedCurrency>,
+
+    /// The realized profit and loss of the currently open position so far.
+    current_position_pnl: BaseOrQuote::PairedCurrency,
+    /// The realized profit and loss of the most recently closed position, until a new one opens.
+    last_closed_position_pnl: Option<BaseOrQuote::PairedCurrency>,
+    /// The realized profit and loss of the fills of each order that reduced the position.
+    realized_pnl_by_order: HashMap<OrderId, BaseOrQuote::PairedCurrency>,
+    /// The realized profit and loss of all fills that reduced the position, excluding fees.
+    total_realized_pnl: BaseOrQuote::PairedCurrency,
+    /// The timestamp of the next withdrawal, if `Config::profit_withdrawal` is set.
+    next_profit_withdrawal_ns: Option<TimestampNs>,
+    /// The `total_realized_pnl`, less the `pending_pnl`, at the last withdrawal of profits.
+    realized_pnl_at_last_withdrawal: BaseOrQuote::PairedCurrency,
+
+    /// The fills of user orders since the last call to `drain_fills`.
+    fills: Vec<ExecutedTrade<I, D, BaseOrQuote>>,
+
+    /// The funding rates to settle at their timestamp, sorted by timestamp.
+    funding_rates: Vec<(TimestampNs, Decimal<I, D>)>,
+    /// The index of the next funding rate to be settled.
+    next_funding_rate_idx: usize,
+
+    /// The second of the simulated exchange time in which orders were last submitted.
+    rate_limit_second: i64,
+    /// The number of orders submitted within the `rate_limit_second`.
+    orders_in_rate_limit_second: usize,
+
+    /// The `MarketState::num_updates` step in which `orders_submitted_this_step` were submitted.
+    submission_step: u64,
+    /// The ids of the orders submitted during the `submission_step`.
+    orders_submitted_this_step: Vec<OrderId>,
+
+    /// The highest total equity observed in `update_state`, used for `current_drawdown`.
+    equity_high_water_mark: BaseOrQuote::PairedCurrency,
+
     // To avoid allocations in hot-paths
     limit_order_updates: Vec<LimitOrderUpdate<I, D, BaseOrQuote, UserOrderId>>,
     ids_to_remove: Vec<OrderId>,
@@ -128,6 +207,7 @@ where
             config.sample_returns_every_n_seconds() as i64 * 1_000_000_000,
         ));
         let max_active_orders = config.max_num_open_orders();
+        let equity_high_water_mark = config.starting_wallet_balance();
         Self {
             config,
             market_state,
@@ -136,10 +216,38 @@ where
             next_order_id: OrderId::default(),
             transaction_accounting,
             position: Position::default(),
+            hedge_position: HedgePosition::default(),
             // TODO: two such structs, one for buys, the other for sells.
             active_limit_orders: ActiveLimitOrders::new(10_000),
+            active_stop_orders: Vec::new(),
             order_margin: OrderMargin::new(max_active_orders),
             sample_returns_trigger,
+            last_market_fill_price: None,
+            position_twap_mark_sum: 0.0,
+            position_twap_duration_ns: 0,
+            expired: false,
+            blown_up: false,
+            reentry_cooldown_until_ns: 0.into(),
+            halted_until_ns: None,
+            rejected_orders: Vec::new(),
+            current_trade_mfe: None,
+            mfe_distribution: Vec::new(),
+            current_trade_mae: None,
+            mae_distribution: Vec::new(),
+            current_position_pnl: BaseOrQuote::PairedCurrency::zero(),
+            last_closed_position_pnl: None,
+            realized_pnl_by_order: HashMap::new(),
+            total_realized_pnl: BaseOrQuote::PairedCurrency::zero(),
+            next_profit_withdrawal_ns: None,
+            realized_pnl_at_last_withdrawal: BaseOrQuote::PairedCurrency::zero(),
+            fills: Vec::new(),
+            funding_rates: Vec::new(),
+            next_funding_rate_idx: 0,
+            rate_limit_second: 0,
+            orders_in_rate_limit_second: 0,
+            submission_step: 0,
+            orders_submitted_this_step: Vec::new(),
+            equity_high_water_mark,
             limit_order_updates: Vec::with_capacity(max_active_orders),
             ids_to_remove: Vec::with_capacity(max_active_orders),
         }
@@ -155,6 +263,18 @@ where
         }
     }
 
+    /// The number of market updates processed with `update_state`.
+    #[inline]
+    pub fn num_ticks_processed(&self) -> u64 {
+        self.market_state.num_updates()
+    }
+
+    /// The simulated time in nanoseconds elapsed between the first and the current market update.
+    #[inline]
+    pub fn elapsed_ns(&self) -> i64 {
+        (self.market_state.current_timestamp_ns() - self.market_state.first_ts_ns()).into()
+    }
+
     /// Get the total amount of fees paid to the exchange.
     pub fn fees_paid(&self) -> BaseOrQuote::PairedCurrency {
         self.transaction_accounting
@@ -162,6 +282,175 @@ where
             .expect("is valid account")
     }
 
+    /// Get the total amount of maker rebates that have accrued in the `REBATE_ACCOUNT`.
+    /// Is always zero, unless `Config::credit_maker_rebates` and `Config::separate_rebate_account` are enabled.
+    pub fn accrued_rebates(&self) -> BaseOrQuote::PairedCurrency {
+        self.transaction_accounting
+            .margin_balance_of(REBATE_ACCOUNT)
+            .expect("is valid account")
+    }
+
+    /// Get the realized profits that have accrued in the `REALIZED_PNL_ACCOUNT`,
+    /// which have not been swept into the wallet yet.
+    /// Is always zero, unless `Config::separate_realized_pnl_account` is enabled.
+    pub fn unswept_realized_pnl(&self) -> BaseOrQuote::PairedCurrency {
+        self.transaction_accounting
+            .margin_balance_of(REALIZED_PNL_ACCOUNT)
+            .expect("is valid account")
+    }
+
+    /// Get the profit and loss of partial closes held in the `PENDING_PNL_ACCOUNT`,
+    /// which is only realized into the wallet once the position is fully closed.
+    /// Is always zero, unless `Config::pnl_realization_policy` is `PnlRealizationPolicy::OnFullClose`.
+    pub fn pending_pnl(&self) -> BaseOrQuote::PairedCurrency {
+        self.transaction_accounting
+            .margin_balance_of(PENDING_PNL_ACCOUNT)
+            .expect("is valid account")
+    }
+
+    /// Get the total profits withdrawn to the `WITHDRAWAL_ACCOUNT`.
+    /// Is always zero, unless `Config::profit_withdrawal` is set.
+    pub fn withdrawn_profits(&self) -> BaseOrQuote::PairedCurrency {
+        self.transaction_accounting
+            .margin_balance_of(WITHDRAWAL_ACCOUNT)
+            .expect("is valid account")
+    }
+
+    /// Sweep the realized profits of the `REALIZED_PNL_ACCOUNT` into the wallet,
+    /// where they can back new margin.
+    ///
+    /// # Returns:
+    /// The swept amount.
+    pub fn sweep_realized_pnl(&mut self) -> BaseOrQuote::PairedCurrency {
+        let realized_pnl = self.unswept_realized_pnl();
+        if realized_pnl > BaseOrQuote::PairedCurrency::zero() {
+            let transaction =
+                Transaction::new(USER_WALLET_ACCOUNT, REALIZED_PNL_ACCOUNT, realized_pnl);
+            self.transaction_accounting
+                .create_margin_transfer(transaction)
+                .expect("margin transfer works");
+        }
+        realized_pnl
+    }
+
+    /// How many basis points the price must move from entry to cover the round-trip
+    /// maker and taker fees, independent of the position size.
+    pub fn break_even_move_bps(&self) -> Decimal<I, D> {
+        let contract_spec = self.config.contract_spec();
+        let round_trip_fee =
+            *contract_spec.fee_maker().as_ref() + *contract_spec.fee_taker().as_ref();
+        let bps_per_unit =
+            Decimal::try_from_scaled(I::from(10_000).expect("10_000 can convert to I"), 0)
+                .expect("Can create `Decimal` from 10_000");
+        round_trip_fee * bps_per_unit
+    }
+
+    /// The price at which closing the position nets zero after the round-trip maker and taker fees
+    /// and the funding expected over `periods` funding periods at `funding_rate`.
+    /// With a positive `funding_rate` longs pay and shorts receive the funding,
+    /// which moves the break-even price up for both.
+    /// Returns `None` if there is no position.
+    pub fn break_even_with_funding(
+        &self,
+        periods: u32,
+        funding_rate: Decimal<I, D>,
+    ) -> Option<QuoteCurrency<I, D>> {
+        let contract_spec = self.config.contract_spec();
+        let round_trip_fee =
+            *contract_spec.fee_maker().as_ref() + *contract_spec.fee_taker().as_ref();
+        let periods =
+            Decimal::try_from_scaled(I::from(periods).expect("periods can convert to I"), 0)
+                .expect("Can create `Decimal` from periods");
+        let funding = periods * funding_rate;
+        let multiplier = match &self.position {
+            Position::Neutral => return None,
+            Position::Long(_) => Decimal::one() + round_trip_fee + funding,
+            Position::Short(_) => Decimal::one() - round_trip_fee + funding,
+        };
+        Some(self.position.entry_price() * multiplier)
+    }
+
+    /// The additional position margin required to go from the current position to `target_qty` at `price`,
+    /// given the `init_margin_req` of the contract.
+    /// A positive `target_qty` denotes a long and a negative one a short position.
+    /// Reducing the position requires no additional margin, while flipping the side
+    /// releases the margin of the current position first.
+    pub fn margin_required_for_target(
+        &self,
+        target_qty: BaseOrQuote,
+        price: QuoteCurrency<I, D>,
+    ) -> BaseOrQuote::PairedCurrency {
+        let init_margin_req = self.config.contract_spec().init_margin_req();
+        let current_qty = self.position.quantity();
+
+        if current_qty.is_zero() || current_qty.is_positive() == target_qty.is_positive() {
+            let added_qty = target_qty.abs() - current_qty.abs();
+            if added_qty <= BaseOrQuote::zero() {
+                return BaseOrQuote::PairedCurrency::zero();
+            }
+            return BaseOrQuote::PairedCurrency::convert_from(added_qty, price) * init_margin_req;
+        }
+
+        // The position flips sides, so its margin is released first.
+        let target_margin =
+            BaseOrQuote::PairedCurrency::convert_from(target_qty.abs(), price) * init_margin_req;
+        let position_margin = self
+            .transaction_accounting
+            .margin_balance_of(USER_POSITION_MARGIN_ACCOUNT)
+            .expect("is a valid account");
+        max(
+            target_margin - position_margin,
+            BaseOrQuote::PairedCurrency::zero(),
+        )
+    }
+
+    /// The largest `MarketOrder` quantity of `side` the user can currently afford at the touch price,
+    /// given the `init_margin_req` and the taker fee of the contract.
+    /// Any existing opposite position is closed first, releasing its margin,
+    /// while the margin of resting orders is not available for the new position.
+    /// The result is rounded down to the quantity `tick_size`.
+    pub fn max_openable_qty(&self, side: Side) -> BaseOrQuote {
+        let contract_spec = self.config.contract_spec();
+        let price = match side {
+            Side::Buy => self.market_state.ask(),
+            Side::Sell => self.market_state.bid(),
+        };
+        let (closing_qty, released_margin) = match (&self.position, side) {
+            (Position::Short(pos), Side::Buy) | (Position::Long(pos), Side::Sell) => (
+                pos.quantity(),
+                self.transaction_accounting
+                    .margin_balance_of(USER_POSITION_MARGIN_ACCOUNT)
+                    .expect("is a valid account"),
+            ),
+            _ => (BaseOrQuote::zero(), BaseOrQuote::PairedCurrency::zero()),
+        };
+        let budget = self
+            .available_balance_for_new_orders()
+            .expect("is a valid account")
+            + released_margin;
+        if budget <= BaseOrQuote::PairedCurrency::zero() {
+            return closing_qty;
+        }
+
+        let margin_per_notional =
+            contract_spec.init_margin_req() + *contract_spec.fee_taker().as_ref();
+        let max_notional =
+            BaseOrQuote::PairedCurrency::from(*budget.as_ref() / margin_per_notional);
+        let tick_size = contract_spec.quantity_filter().tick_size_at(price);
+        let new_qty = BaseOrQuote::from(
+            BaseOrQuote::convert_from(max_notional, price)
+                .as_ref()
+                .quantize_round_to_zero(*tick_size.as_ref()),
+        );
+        closing_qty + new_qty
+    }
+
+    /// The average fill price of the most recently filled `MarketOrder`, if any.
+    #[inline]
+    pub fn last_market_fill_price(&self) -> Option<QuoteCurrency<I, D>> {
+        self.last_market_fill_price
+    }
+
     /// Update the exchange state with new information
     /// Returns a reference to order updates vector for performance reasons.
     ///
@@ -180,11 +469,26 @@ where
         U: MarketUpdate<I, D, BaseOrQuote>,
     {
         trace!("update_state: market_update: {market_update}");
+        if self.blown_up {
+            return Err(Error::AccountBlownUp);
+        }
 
+        let prev_ts_ns = self.market_state.current_timestamp_ns();
+        let prev_mark = self.market_state.mid_price();
         self.market_state
             .update_state(market_update, self.config.contract_spec().price_filter())?;
+        self.update_position_twap_mark(prev_ts_ns, prev_mark);
+        self.update_excursions();
+        self.settle_due_funding_rates();
+        self.withdraw_profits();
+        self.equity_high_water_mark = max(self.equity_high_water_mark, self.total_equity());
 
-        self.account_tracker.update(&self.market_state);
+        self.account_tracker
+            .log_timestamp(self.market_state.current_timestamp_ns());
+        let update_every_n_ticks = self.config.account_tracker_update_every_n_ticks().max(1);
+        if (self.market_state.num_updates() - 1) % update_every_n_ticks == 0 {
+            self.account_tracker.update(&self.market_state);
+        }
         if self
             .sample_returns_trigger
             .should_trigger(market_update.timestamp_exchange_ns())
@@ -201,29 +505,711 @@ where
         >>::check_maintenance_margin(
             &self.risk_engine, &self.market_state, &self.position
         ) {
-            self.liquidate();
+            match self.config.partial_liquidation_buffer() {
+                Some(buffer) => self.liquidate_partially(self.partial_liquidation_qty(buffer)),
+                None => self.liquidate(),
+            }
             return Err(e.into());
         };
+        if self.config.hedge_mode() && self.liquidate_hedge_legs() {
+            return Err(RiskError::Liquidate.into());
+        }
+
+        if let Some(min_wallet_balance) = self.config.min_wallet_balance() {
+            let equity = self.total_equity();
+            if equity < min_wallet_balance {
+                warn!(
+                    "equity {equity} dropped below the minimum wallet balance {min_wallet_balance}"
+                );
+                self.blown_up = true;
+                return Err(Error::AccountBlownUp);
+            }
+        }
+
+        if let Some(threshold) = self.config.reduce_only_margin_ratio() {
+            if self.margin_ratio().is_some_and(|ratio| ratio < threshold) {
+                self.cancel_position_increasing_orders();
+            }
+        }
 
         self.check_active_orders(market_update);
+        self.trigger_stop_orders();
         Ok(&self.limit_order_updates)
     }
 
+    /// Trail the active stop-limit orders with a `TrailingDistance`, then submit the `LimitOrder`
+    /// of each one whose trigger price was reached.
+    /// Triggered orders whose `LimitOrder` is rejected are dropped.
+    fn trigger_stop_orders(&mut self) {
+        if self.active_stop_orders.is_empty() {
+            return;
+        }
+        let (bid, ask) = (self.market_state.bid(), self.market_state.ask());
+        let tick_size = self.config.contract_spec().price_filter().tick_size();
+        for order in self.active_stop_orders.iter_mut() {
+            order.trail(bid, ask, tick_size);
+        }
+        let (triggered, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.active_stop_orders)
+            .into_iter()
+            .partition(|order| order.is_triggered(bid, ask));
+        self.active_stop_orders = waiting;
+        for order in triggered {
+            debug!("triggered stop-limit order: {order:?}");
+            if let Err(e) = self.submit_limit_order(order.into_limit_order()) {
+                warn!("dropping the triggered stop-limit order, as its limit order failed: {e}");
+            }
+        }
+    }
+
+    /// Update the exchange state with a batch of taker trades and the best bid and ask after them,
+    /// aggregated into a `SmartCandle` using the `PriceFilter` of the contract.
+    /// Without any trades only the `bba` is applied.
+    ///
+    /// ### Returns:
+    /// If Ok, returns updates regarding limit orders, wether partially filled or fully.
+    pub fn update_state_with_trades(
+        &mut self,
+        taker_trades: &[Trade<I, D, BaseOrQuote>],
+        bba: Bba<I, D>,
+    ) -> Result<&Vec<LimitOrderUpdate<I, D, BaseOrQuote, UserOrderId>>> {
+        if taker_trades.is_empty() {
+            return self.update_state(&bba);
+        }
+        let smart_candle = SmartCandle::new(
+            taker_trades,
+            bba,
+            self.config.contract_spec().price_filter(),
+        );
+        self.update_state(&smart_candle)
+    }
+
+    /// Settle a funding period at the current mark price.
+    /// With a positive `funding_rate`, longs pay shorts and vice versa.
+    /// The payment is `notional * funding_rate`, where the notional is denoted in the margin currency,
+    /// so for inverse futures its `quantity / mark_price` and for linear futures `quantity * mark_price`.
+    ///
+    /// # Returns:
+    /// The funding payment received by the user, which is negative if the user paid.
+    pub fn settle_funding_period(
+        &mut self,
+        funding_rate: Decimal<I, D>,
+    ) -> BaseOrQuote::PairedCurrency {
+        let received = self.funding_payment(funding_rate);
+        if received.is_zero() {
+            return received;
+        }
+        let payment = received.abs();
+        let user_pays = received.is_negative();
+        debug!("settle_funding_period: payment {payment}, user_pays: {user_pays}");
+        let transaction = if user_pays {
+            Transaction::new(TREASURY_ACCOUNT, USER_WALLET_ACCOUNT, payment)
+        } else {
+            Transaction::new(USER_WALLET_ACCOUNT, TREASURY_ACCOUNT, payment)
+        };
+        self.transaction_accounting
+            .create_margin_transfer(transaction)
+            .expect("margin transfer works");
+
+        received
+    }
+
+    /// The funding payment the position receives for the `funding_rate` at the current mark price,
+    /// which is negative if the user pays.
+    fn funding_payment(&self, funding_rate: Decimal<I, D>) -> BaseOrQuote::PairedCurrency {
+        let quantity = self.position.quantity();
+        let notional = BaseOrQuote::PairedCurrency::convert_from(
+            quantity.abs(),
+            self.market_state.mid_price(),
+        );
+        let signed_payment = notional * funding_rate;
+        if quantity.is_positive() {
+            -signed_payment
+        } else {
+            signed_payment
+        }
+    }
+
+    /// The funding the position accrued towards the next funding rate supplied with
+    /// `set_funding_rates`, being its payment at the current mark price pro-rated by the time
+    /// elapsed since the previous funding rate, or the first market update.
+    /// Is negative if the user pays and zero without a next funding rate.
+    pub fn accrued_funding(&self) -> BaseOrQuote::PairedCurrency {
+        let Some((next_ts, funding_rate)) =
+            self.funding_rates.get(self.next_funding_rate_idx).copied()
+        else {
+            return BaseOrQuote::PairedCurrency::zero();
+        };
+        let last_ts = match self.next_funding_rate_idx {
+            0 => self.market_state.first_ts_ns(),
+            idx => self.funding_rates[idx - 1].0,
+        };
+        let period: i64 = (next_ts - last_ts).into();
+        if period <= 0 {
+            return BaseOrQuote::PairedCurrency::zero();
+        }
+        let elapsed: i64 = (self.market_state.current_timestamp_ns() - last_ts).into();
+        let fraction = decimal_from_f64((elapsed as f64 / period as f64).clamp(0.0, 1.0))
+            .expect("Is a valid fraction");
+        self.funding_payment(funding_rate) * fraction
+    }
+
+    /// The unrealized pnl of the position at the current bid and ask.
+    /// With `Config::funding_aware_unrealized_pnl`, the `accrued_funding` is included.
+    pub fn unrealized_pnl(&self) -> BaseOrQuote::PairedCurrency {
+        let upnl = self
+            .position
+            .unrealized_pnl(self.market_state.bid(), self.market_state.ask());
+        if self.config.funding_aware_unrealized_pnl() {
+            upnl + self.accrued_funding()
+        } else {
+            upnl
+        }
+    }
+
+    /// Supply a series of timestamped funding rates, each of which is automatically settled
+    /// with `settle_funding_period` once a market update reaches its timestamp.
+    /// Replaces any previously supplied series.
+    pub fn set_funding_rates(&mut self, mut funding_rates: Vec<(TimestampNs, Decimal<I, D>)>) {
+        funding_rates.sort_by_key(|(ts, _)| *ts);
+        self.funding_rates = funding_rates;
+        self.next_funding_rate_idx = 0;
+    }
+
+    fn settle_due_funding_rates(&mut self) {
+        let now = self.market_state.current_timestamp_ns();
+        while let Some((ts, funding_rate)) =
+            self.funding_rates.get(self.next_funding_rate_idx).copied()
+        {
+            if ts > now {
+                break;
+            }
+            self.next_funding_rate_idx += 1;
+            let payment = self.settle_funding_period(funding_rate);
+            trace!("settled funding rate {funding_rate} of {ts}: {payment}");
+        }
+    }
+
+    /// Once an interval of the `Config::profit_withdrawal` has elapsed,
+    /// withdraw its fraction of the profit realized since the last withdrawal from the wallet.
+    /// Losses within an interval are not offset against later profits.
+    /// Profit still pending in the `PENDING_PNL_ACCOUNT` is only withdrawn once it reached the wallet.
+    fn withdraw_profits(&mut self) {
+        let Some(profit_withdrawal) = self.config.profit_withdrawal() else {
+            return;
+        };
+        let now = self.market_state.current_timestamp_ns();
+        let interval = TimestampNs::from(profit_withdrawal.interval_ns());
+        let next_withdrawal_ns = *self.next_profit_withdrawal_ns.get_or_insert(now + interval);
+        if now < next_withdrawal_ns {
+            return;
+        }
+        self.next_profit_withdrawal_ns = Some(now + interval);
+
+        let settled_realized_pnl = self.total_realized_pnl - self.pending_pnl();
+        let realized_profit = settled_realized_pnl - self.realized_pnl_at_last_withdrawal;
+        self.realized_pnl_at_last_withdrawal = settled_realized_pnl;
+        if realized_profit <= BaseOrQuote::PairedCurrency::zero() {
+            return;
+        }
+        let wallet_balance = self
+            .transaction_accounting
+            .margin_balance_of(USER_WALLET_ACCOUNT)
+            .expect("is valid account");
+        let amount = min(
+            realized_profit * profit_withdrawal.fraction(),
+            wallet_balance,
+        );
+        debug!("withdraw_profits: {amount} of the realized profit {realized_profit}");
+        if amount <= BaseOrQuote::PairedCurrency::zero() {
+            return;
+        }
+        let transaction = Transaction::new(WITHDRAWAL_ACCOUNT, USER_WALLET_ACCOUNT, amount);
+        self.transaction_accounting
+            .create_margin_transfer(transaction)
+            .expect("margin transfer works");
+    }
+
+    /// Halt trading, rejecting all order submissions with `Error::TradingHalted`
+    /// until the market timestamp passes `until_ts`.
+    /// Market updates are still processed, so the position keeps being marked to market.
+    pub fn set_halt(&mut self, until_ts: TimestampNs) {
+        self.halted_until_ns = Some(until_ts);
+    }
+
+    /// Whether the contract has been settled at expiry.
+    #[inline]
+    pub fn is_expired(&self) -> bool {
+        self.expired
+    }
+
+    /// Cash-settle a dated futures contract at the `settlement_price`.
+    /// Cancels all active limit and stop-limit orders, closes the position and any hedge legs
+    /// at the `settlement_price` and marks the exchange as expired, rejecting any further orders.
+    /// If `Config::expiry_ts` is set, it must have been reached.
+    pub fn settle_at_expiry(&mut self, settlement_price: QuoteCurrency<I, D>) -> Result<()> {
+        if self.expired {
+            return Err(Error::ContractExpired);
+        }
+        if let Some(expiry_ts) = self.config.expiry_ts() {
+            if self.market_state.current_timestamp_ns() < expiry_ts {
+                return Err(Error::ContractNotYetExpired);
+            }
+        }
+        info!("settling at expiry with price {settlement_price}");
+
+        let order_ids: Vec<OrderId> = self.active_limit_orders.values().map(|o| o.id()).collect();
+        for order_id in order_ids {
+            self.cancel_limit_order(CancelBy::OrderId(order_id))
+                .expect("The order is active");
+        }
+        self.active_stop_orders.clear();
+
+        let closing_trade = match &self.position {
+            Position::Long(pos) => Some((Side::Sell, pos.quantity())),
+            Position::Short(pos) => Some((Side::Buy, pos.quantity())),
+            Position::Neutral => None,
+        };
+        if let Some((side, quantity)) = closing_trade {
+            let prev_qty = self.position.quantity();
+            let prev_entry_price = self.position.entry_price();
+            let treasury_balance = Self::treasury_balance(&self.transaction_accounting);
+            self.position.change_position(
+                quantity,
+                settlement_price,
+                side,
+                &mut self.transaction_accounting,
+                self.config.contract_spec().init_margin_req(),
+                BaseOrQuote::PairedCurrency::zero(),
+                self.config.pnl_realization_policy(),
+                self.config.credit_maker_rebates(),
+            );
+            Self::segregate_realized_pnl(
+                &self.config,
+                &mut self.transaction_accounting,
+                treasury_balance,
+            );
+            Self::record_excursion(
+                prev_qty,
+                &self.position,
+                &mut self.current_trade_mfe,
+                &mut self.mfe_distribution,
+            );
+            Self::record_excursion(
+                prev_qty,
+                &self.position,
+                &mut self.current_trade_mae,
+                &mut self.mae_distribution,
+            );
+            if let Some(realized_pnl) = Self::record_closed_position_pnl(
+                prev_qty,
+                prev_entry_price,
+                settlement_price,
+                &self.position,
+                &mut self.current_position_pnl,
+                &mut self.last_closed_position_pnl,
+            ) {
+                self.total_realized_pnl += realized_pnl;
+            }
+            self.account_tracker
+                .log_trade(side, settlement_price, quantity);
+        }
+        for leg in [HedgeLeg::Long, HedgeLeg::Short] {
+            let Some(quantity) = self.hedge_position.leg(leg).map(|inner| inner.quantity()) else {
+                continue;
+            };
+            let side = match leg {
+                HedgeLeg::Long => Side::Sell,
+                HedgeLeg::Short => Side::Buy,
+            };
+            let treasury_balance = Self::treasury_balance(&self.transaction_accounting);
+            self.hedge_position
+                .change_position(
+                    quantity,
+                    settlement_price,
+                    side,
+                    leg,
+                    &mut self.transaction_accounting,
+                    self.config.contract_spec().init_margin_req(),
+                    BaseOrQuote::PairedCurrency::zero(),
+                    self.config.pnl_realization_policy(),
+                    self.config.credit_maker_rebates(),
+                )
+                .expect("The whole hedge leg can be closed");
+            Self::segregate_realized_pnl(
+                &self.config,
+                &mut self.transaction_accounting,
+                treasury_balance,
+            );
+            self.account_tracker
+                .log_trade(side, settlement_price, quantity);
+        }
+        debug_assert_eq!(self.position, Position::Neutral);
+        debug_assert_eq!(self.hedge_position, HedgePosition::default());
+        self.expired = true;
+
+        Ok(())
+    }
+
+    /// Reset the `AccountTracker` statistics while keeping the position, orders and balances.
+    /// The current sum of the user balances becomes the new starting balance of the tracker.
+    pub fn reset_account_tracker(&mut self) {
+        let balance = self.user_balances().sum();
+        self.account_tracker.reset(balance);
+    }
+
+    /// Forward the resting volume on both sides of the order book to the `AccountTracker`,
+    /// e.g when processing order book depth data alongside the regular market updates.
+    pub fn sample_market_microstructure(
+        &mut self,
+        bid_volume: BaseOrQuote,
+        ask_volume: BaseOrQuote,
+    ) {
+        let position_open = !matches!(self.position, Position::Neutral);
+        self.account_tracker
+            .sample_market_microstructure(bid_volume, ask_volume, position_open);
+    }
+
+    /// Accumulates the mark price that prevailed since the last update, while a position is open.
+    fn update_position_twap_mark(
+        &mut self,
+        prev_ts_ns: TimestampNs,
+        prev_mark: QuoteCurrency<I, D>,
+    ) {
+        if matches!(self.position, Position::Neutral) {
+            self.position_twap_mark_sum = 0.0;
+            self.position_twap_duration_ns = 0;
+            return;
+        }
+        let dt: i64 = (self.market_state.current_timestamp_ns() - prev_ts_ns).into();
+        self.position_twap_mark_sum += Into::<f64>::into(prev_mark) * dt as f64;
+        self.position_twap_duration_ns += dt;
+    }
+
+    /// The time-weighted average mark price over the lifetime of the currently open position.
+    /// Returns `None` if there is no position or no time has elapsed yet.
+    pub fn position_twap_mark(&self) -> Option<QuoteCurrency<I, D>> {
+        if self.position_twap_duration_ns <= 0 {
+            return None;
+        }
+        let twap = self.position_twap_mark_sum / self.position_twap_duration_ns as f64;
+        decimal_from_f64(twap).ok().map(QuoteCurrency::from)
+    }
+
+    /// Keeps track of the best and the worst unrealized pnl of the currently open position.
+    fn update_excursions(&mut self) {
+        let upnl = self
+            .position
+            .unrealized_pnl(self.market_state.bid(), self.market_state.ask());
+        if let Some(mfe) = self.current_trade_mfe.as_mut() {
+            if upnl > *mfe {
+                *mfe = upnl;
+            }
+        }
+        if let Some(mae) = self.current_trade_mae.as_mut() {
+            if -upnl > *mae {
+                *mae = -upnl;
+            }
+        }
+    }
+
+    /// Records the maximum favorable or adverse excursion once a position is closed or flipped,
+    /// and starts tracking it for a newly opened position.
+    fn record_excursion(
+        prev_qty: BaseOrQuote,
+        position: &Position<I, D, BaseOrQuote>,
+        current_trade_excursion: &mut Option<BaseOrQuote::PairedCurrency>,
+        excursion_distribution: &mut Vec<BaseOrQuote::PairedCurrency>,
+    ) {
+        let new_qty = position.quantity();
+        let closed = !prev_qty.is_zero()
+            && (new_qty.is_zero() || new_qty.is_positive() != prev_qty.is_positive());
+        if closed {
+            if let Some(excursion) = current_trade_excursion.take() {
+                excursion_distribution.push(excursion);
+            }
+        }
+        if !new_qty.is_zero() && current_trade_excursion.is_none() {
+            *current_trade_excursion = Some(BaseOrQuote::PairedCurrency::zero());
+        }
+    }
+
+    /// The maximum favorable excursion, being the best unrealized pnl reached,
+    /// of each closed position in chronological order.
+    #[inline]
+    pub fn mfe_distribution(&self) -> &[BaseOrQuote::PairedCurrency] {
+        &self.mfe_distribution
+    }
+
+    /// The maximum adverse excursion, being the magnitude of the worst unrealized pnl reached,
+    /// of each closed position in chronological order.
+    #[inline]
+    pub fn mae_distribution(&self) -> &[BaseOrQuote::PairedCurrency] {
+        &self.mae_distribution
+    }
+
+    /// The edge ratio, being the average maximum favorable excursion over the average
+    /// maximum adverse excursion of the closed positions.
+    /// Above 1, the positions moved further in favor than against them.
+    /// Is zero if no closed position had an adverse excursion.
+    pub fn edge_ratio(&self) -> f64 {
+        let sum_mae: f64 = self
+            .mae_distribution
+            .iter()
+            .map(|mae| Into::<f64>::into(*mae))
+            .sum();
+        if sum_mae == 0.0 {
+            return 0.0;
+        }
+        // Both distributions hold one value per closed position, so the counts cancel out.
+        let sum_mfe: f64 = self
+            .mfe_distribution
+            .iter()
+            .map(|mfe| Into::<f64>::into(*mfe))
+            .sum();
+        sum_mfe / sum_mae
+    }
+
+    /// Accumulates the realized profit and loss of the open position
+    /// and records it once the position is fully closed.
+    /// Opening a new position, also by flipping sides, resets the recorded value.
+    ///
+    /// # Returns:
+    /// The profit and loss realized by the fill, if it reduced the position.
+    fn record_closed_position_pnl(
+        prev_qty: BaseOrQuote,
+        prev_entry_price: QuoteCurrency<I, D>,
+        fill_price: QuoteCurrency<I, D>,
+        position: &Position<I, D, BaseOrQuote>,
+        current_position_pnl: &mut BaseOrQuote::PairedCurrency,
+        last_closed_position_pnl: &mut Option<BaseOrQuote::PairedCurrency>,
+    ) -> Option<BaseOrQuote::PairedCurrency> {
+        let new_qty = position.quantity();
+        if prev_qty.is_zero() {
+            *current_position_pnl = BaseOrQuote::PairedCurrency::zero();
+            *last_closed_position_pnl = None;
+            return None;
+        }
+        let flipped = !new_qty.is_zero() && new_qty.is_positive() != prev_qty.is_positive();
+        let closed_qty = if new_qty.is_zero() || flipped {
+            prev_qty
+        } else if new_qty.abs() < prev_qty.abs() {
+            prev_qty - new_qty
+        } else {
+            // The position was increased.
+            return None;
+        };
+        let realized_pnl =
+            BaseOrQuote::PairedCurrency::pnl(prev_entry_price, fill_price, closed_qty);
+        *current_position_pnl += realized_pnl;
+
+        if new_qty.is_zero() {
+            *last_closed_position_pnl = Some(*current_position_pnl);
+            *current_position_pnl = BaseOrQuote::PairedCurrency::zero();
+        } else if flipped {
+            *current_position_pnl = BaseOrQuote::PairedCurrency::zero();
+            *last_closed_position_pnl = None;
+        }
+        Some(realized_pnl)
+    }
+
+    /// The realized profit and loss of the most recently closed position, excluding fees.
+    /// Is `None` while a position is open or if no position was closed yet.
+    #[inline]
+    pub fn last_closed_position_pnl(&self) -> Option<BaseOrQuote::PairedCurrency> {
+        self.last_closed_position_pnl
+    }
+
+    /// The profit and loss realized by the fills of the order with `order_id`, excluding fees.
+    /// Is `None` if no fill of the order reduced the position.
+    pub fn realized_pnl_of_order(&self, order_id: OrderId) -> Option<BaseOrQuote::PairedCurrency> {
+        self.realized_pnl_by_order.get(&order_id).copied()
+    }
+
+    /// Returns the fills of all `MarketOrder`s and `LimitOrder`s since the last call,
+    /// in the order they occurred, and clears the internal buffer.
+    /// The limit order fills match the `LimitOrderUpdate`s returned by `update_state`.
+    pub fn drain_fills(&mut self) -> Vec<ExecutedTrade<I, D, BaseOrQuote>> {
+        std::mem::take(&mut self.fills)
+    }
+
     // Liquidate the position by closing it with a market order.
     fn liquidate(&mut self) {
         warn!("liquidating position {}", self.position);
-        let order = match &self.position {
-            Position::Long(pos) => {
-                MarketOrder::new(Side::Sell, pos.quantity()).expect("Can create market order.")
-            }
-            Position::Short(pos) => {
-                MarketOrder::new(Side::Buy, pos.quantity()).expect("Can create market order.")
-            }
+        let quantity = match &self.position {
+            Position::Long(pos) | Position::Short(pos) => pos.quantity(),
             Position::Neutral => panic!("A neutral position can not be liquidated"),
         };
-        self.submit_market_order(order)
-            .expect("Must be able to submit liquidation order");
+        self.submit_liquidation_order(quantity);
+    }
+
+    /// The margin ratio, being the total equity relative to the notional value of the position
+    /// at the mid price. Is `None` for a neutral position.
+    pub fn margin_ratio(&self) -> Option<Decimal<I, D>> {
+        let quantity = self.position.quantity().abs();
+        if quantity.is_zero() {
+            return None;
+        }
+        let notional =
+            BaseOrQuote::PairedCurrency::convert_from(quantity, self.market_state.mid_price());
+        let equity = self.total_equity();
+        Some(*equity.as_ref() / *notional.as_ref())
+    }
+
+    // Cancel the resting limit orders on the side of the position, which would increase it.
+    fn cancel_position_increasing_orders(&mut self) {
+        let increasing_side = match &self.position {
+            Position::Long(_) => Side::Buy,
+            Position::Short(_) => Side::Sell,
+            Position::Neutral => return,
+        };
+        let order_ids: Vec<OrderId> = self
+            .active_limit_orders
+            .values()
+            .filter(|order| order.side() == increasing_side)
+            .map(|order| order.id())
+            .collect();
+        for order_id in order_ids {
+            warn!("cancelling position increasing order {order_id} due to a low margin ratio");
+            self.cancel_limit_order(CancelBy::OrderId(order_id))
+                .expect("The order is active");
+        }
+    }
+
+    /// The quantity to close in a partial liquidation, once the position breached its
+    /// liquidation price. The total equity is only used for sizing: the remaining position is
+    /// the largest one whose notional value at the mid price the equity covers
+    /// with `maintenance_margin + buffer`.
+    /// If the equity already covers the whole position, reducing it can not cure the breach
+    /// and the whole position is closed.
+    /// The quantity is raised to the `min_quantity` of the `QuantityFilter`,
+    /// closing the whole position if that exceeds it.
+    fn partial_liquidation_qty(&self, buffer: Decimal<I, D>) -> BaseOrQuote {
+        let quantity = self.position.quantity().abs();
+        let equity = self.total_equity();
+        if equity <= BaseOrQuote::PairedCurrency::zero() {
+            return quantity;
+        }
+
+        let contract_spec = self.config.contract_spec();
+        let mid_price = self.market_state.mid_price();
+        let max_notional = BaseOrQuote::PairedCurrency::from(
+            *equity.as_ref() / (contract_spec.maintenance_margin() + buffer),
+        );
+        let quantity_filter = contract_spec.quantity_filter();
+        let remaining_qty = BaseOrQuote::from(
+            BaseOrQuote::convert_from(max_notional, mid_price)
+                .as_ref()
+                .quantize_round_to_zero(*quantity_filter.tick_size_at(mid_price).as_ref()),
+        );
+        if remaining_qty >= quantity {
+            return quantity;
+        }
+        let liquidation_qty = quantity - remaining_qty;
+        match quantity_filter.min_quantity_at(mid_price) {
+            Some(min_qty) if liquidation_qty < min_qty => min(min_qty, quantity),
+            _ => liquidation_qty,
+        }
+    }
+
+    // Liquidate only `quantity` of the position, or all of it if `quantity` covers the position.
+    fn liquidate_partially(&mut self, quantity: BaseOrQuote) {
+        if quantity >= self.position.quantity().abs() {
+            self.liquidate();
+            return;
+        }
+        warn!(
+            "partially liquidating {quantity} of position {}",
+            self.position
+        );
+        self.submit_liquidation_order(quantity);
+    }
+
+    // Close `quantity` of the position with a market order.
+    // Like the liquidation of hedge legs, the order bypasses the checks of order submissions,
+    // so it is neither rejected by the rate limit, a cooldown or halt, nor recorded as rejected.
+    fn submit_liquidation_order(&mut self, quantity: BaseOrQuote) {
+        let side = match &self.position {
+            Position::Long(_) => Side::Sell,
+            Position::Short(_) => Side::Buy,
+            Position::Neutral => panic!("A neutral position can not be liquidated"),
+        };
+        let order = MarketOrder::new(side, quantity).expect("Can create market order.");
+        // Without any depth on the closing side, the liquidation still fills at the touch.
+        let touch_price = match side {
+            Side::Buy => self.market_state.ask(),
+            Side::Sell => self.market_state.bid(),
+        };
+        let fill_price = match self.market_order_fill_price(side, quantity) {
+            Ok(fill_price) => fill_price,
+            Err(_) if !touch_price.is_zero() => touch_price,
+            Err(err) => {
+                warn!(
+                    "can not liquidate {quantity} of position {}: {err}",
+                    self.position
+                );
+                return;
+            }
+        };
+        self.account_tracker.log_market_order_submission(&order);
+        let meta = ExchangeOrderMeta::new(
+            self.next_order_id(),
+            self.market_state.current_timestamp_ns(),
+        );
+        self.record_step_submission(meta.id());
+        let filled_order = order
+            .into_pending(meta)
+            .into_filled(fill_price, self.market_state.current_timestamp_ns());
+        let fee_taker = self.config.contract_spec().fee_taker();
+        self.settle_filled_market_order(filled_order, fee_taker);
         info!("balances after liquidation: {:?}", self.user_balances());
+        self.reentry_cooldown_until_ns =
+            self.market_state.current_timestamp_ns() + self.config.reentry_cooldown_ns().into();
+    }
+
+    /// Rejects new orders while the re-entry cooldown after a liquidation is active.
+    fn check_reentry_cooldown(&self) -> Result<()> {
+        if self.market_state.current_timestamp_ns() < self.reentry_cooldown_until_ns {
+            return Err(RiskError::ReentryCooldownActive.into());
+        }
+        Ok(())
+    }
+
+    /// Rejects new orders while trading is halted.
+    fn check_trading_halt(&self) -> Result<()> {
+        if self
+            .halted_until_ns
+            .is_some_and(|until_ts| self.market_state.current_timestamp_ns() <= until_ts)
+        {
+            return Err(Error::TradingHalted);
+        }
+        Ok(())
+    }
+
+    /// Rejects new orders until a market update has provided a best bid and ask.
+    fn check_market_initialized(&self) -> Result<()> {
+        if self.market_state.bid().is_zero() || self.market_state.ask().is_zero() {
+            return Err(Error::MarketNotInitialized);
+        }
+        Ok(())
+    }
+
+    /// Counts the submission towards the current second of the simulated exchange time,
+    /// rejecting it once `Config::max_orders_per_second` is exceeded.
+    fn check_rate_limit(&mut self) -> Result<()> {
+        let Some(max_orders_per_second) = self.config.max_orders_per_second() else {
+            return Ok(());
+        };
+        let second =
+            Into::<i64>::into(self.market_state.current_timestamp_ns()).div_euclid(1_000_000_000);
+        if second != self.rate_limit_second {
+            self.rate_limit_second = second;
+            self.orders_in_rate_limit_second = 0;
+        }
+        if self.orders_in_rate_limit_second >= max_orders_per_second {
+            return Err(Error::RateLimitExceeded);
+        }
+        self.orders_in_rate_limit_second += 1;
+        Ok(())
     }
 
     /// Submit a new `MarketOrder` to the exchange.
@@ -238,56 +1224,319 @@ where
         &mut self,
         order: MarketOrder<I, D, BaseOrQuote, UserOrderId, NewOrder>,
     ) -> Result<MarketOrder<I, D, BaseOrQuote, UserOrderId, Filled<I, D, BaseOrQuote>>> {
+        let rejected_order = self.config.record_rejected_orders().then(|| order.clone());
+        let result = self.try_submit_market_order(order);
+        if let (Some(order), Err(err)) = (rejected_order, &result) {
+            self.rejected_orders
+                .push((RejectedOrder::Market(order), err.clone()));
+        }
+        result
+    }
+
+    fn try_submit_market_order(
+        &mut self,
+        order: MarketOrder<I, D, BaseOrQuote, UserOrderId, NewOrder>,
+    ) -> Result<MarketOrder<I, D, BaseOrQuote, UserOrderId, Filled<I, D, BaseOrQuote>>> {
+        if self.expired {
+            return Err(Error::ContractExpired);
+        }
+        if self.blown_up {
+            return Err(Error::AccountBlownUp);
+        }
+        if self.config.hedge_mode() {
+            return Err(Error::HedgeModeEnabled);
+        }
+        self.check_market_initialized()?;
+        self.check_trading_halt()?;
+        self.check_reentry_cooldown()?;
+        self.check_rate_limit()?;
         self.account_tracker.log_market_order_submission(&order);
 
         // Basic checks
         self.config
             .contract_spec()
             .quantity_filter()
-            .validate_order_quantity(order.quantity())?;
+            .validate_order_quantity_at(order.quantity(), self.market_state.mid_price())?;
 
+        let fill_price = self.market_order_fill_price(order.side(), order.quantity())?;
         let meta = ExchangeOrderMeta::new(
             self.next_order_id(),
             self.market_state.current_timestamp_ns(),
         );
+        self.record_step_submission(meta.id());
         let order = order.into_pending(meta);
 
-        let fill_price = match order.side() {
-            Side::Buy => self.market_state.ask(),
-            Side::Sell => self.market_state.bid(),
-        };
         let position_margin = self
             .transaction_accounting
             .margin_balance_of(USER_POSITION_MARGIN_ACCOUNT)?;
-        let available_wallet_balance = self
-            .transaction_accounting
-            .margin_balance_of(USER_WALLET_ACCOUNT)?;
+        let available_wallet_balance = self.available_balance_for_new_orders()?;
+        let fee_taker = self.config.contract_spec().fee_taker();
         self.risk_engine.check_market_order(
             &self.position,
             position_margin,
             &order,
             fill_price,
             available_wallet_balance,
+            fee_taker,
         )?;
 
         let filled_order = order.into_filled(fill_price, self.market_state.current_timestamp_ns());
-        self.settle_filled_market_order(filled_order.clone());
+        self.settle_filled_market_order(filled_order.clone(), fee_taker);
+
+        Ok(filled_order)
+    }
+
+    /// Reduce the open position by `quantity` at market, capped at the position size.
+    /// The `MarketOrder` is submitted in the closing direction and never opens a position on the opposite side.
+    ///
+    /// # Returns:
+    /// The filled `MarketOrder` or `Error::NoPositionToReduce` if the position is neutral.
+    pub fn reduce_position(
+        &mut self,
+        quantity: BaseOrQuote,
+    ) -> Result<MarketOrder<I, D, BaseOrQuote, UserOrderId, Filled<I, D, BaseOrQuote>>> {
+        let (side, position_qty) = match &self.position {
+            Position::Long(pos) => (Side::Sell, pos.quantity()),
+            Position::Short(pos) => (Side::Buy, pos.quantity()),
+            Position::Neutral => return Err(Error::NoPositionToReduce),
+        };
+        let quantity = if quantity > position_qty {
+            position_qty
+        } else {
+            quantity
+        };
+        let order = MarketOrder::new(side, quantity)?;
+        self.submit_market_order(order)
+    }
+
+    /// Submit a `MarketOrder` in hedge mode, which fills against the `leg` of the `hedge_position`.
+    /// A buy increases the long leg or reduces the short leg,
+    /// while a sell increases the short leg or reduces the long leg.
+    ///
+    /// # Returns:
+    /// The filled `MarketOrder`, or `Error::HedgeModeDisabled` unless `Config::hedge_mode` is enabled.
+    /// `Error::NoPositionToReduce` or `Error::HedgeLegQuantityExceeded` if the order reduces
+    /// more than the leg holds and `RiskError::NotEnoughAvailableBalance` if the available
+    /// balance does not cover the margin and fee of an increasing order.
+    pub fn submit_hedge_market_order(
+        &mut self,
+        order: MarketOrder<I, D, BaseOrQuote, UserOrderId, NewOrder>,
+        leg: HedgeLeg,
+    ) -> Result<MarketOrder<I, D, BaseOrQuote, UserOrderId, Filled<I, D, BaseOrQuote>>> {
+        if !self.config.hedge_mode() {
+            return Err(Error::HedgeModeDisabled);
+        }
+        if self.expired {
+            return Err(Error::ContractExpired);
+        }
+        if self.blown_up {
+            return Err(Error::AccountBlownUp);
+        }
+        self.check_market_initialized()?;
+        self.check_trading_halt()?;
+        self.check_reentry_cooldown()?;
+        self.check_rate_limit()?;
+        self.account_tracker.log_market_order_submission(&order);
+
+        self.config
+            .contract_spec()
+            .quantity_filter()
+            .validate_order_quantity_at(order.quantity(), self.market_state.mid_price())?;
+        let fill_price = self.market_order_fill_price(order.side(), order.quantity())?;
+        if leg.is_increased_by(order.side()) {
+            let value = BaseOrQuote::PairedCurrency::convert_from(order.quantity(), fill_price);
+            let init_margin = value * self.config.contract_spec().init_margin_req();
+            let fees = value * *self.config.contract_spec().fee_taker().as_ref();
+            if init_margin + fees > self.available_balance_for_new_orders()? {
+                return Err(RiskError::NotEnoughAvailableBalance.into());
+            }
+        } else {
+            let leg_qty = self
+                .hedge_position
+                .leg(leg)
+                .ok_or(Error::NoPositionToReduce)?
+                .quantity();
+            if order.quantity() > leg_qty {
+                return Err(Error::HedgeLegQuantityExceeded);
+            }
+        }
+
+        let meta = ExchangeOrderMeta::new(
+            self.next_order_id(),
+            self.market_state.current_timestamp_ns(),
+        );
+        self.record_step_submission(meta.id());
+        let filled_order = order
+            .into_pending(meta)
+            .into_filled(fill_price, self.market_state.current_timestamp_ns());
+        let fee_taker = self.config.contract_spec().fee_taker();
+        self.settle_filled_hedge_order(&filled_order, leg, fee_taker);
 
         Ok(filled_order)
     }
 
+    /// Settle a filled taker `order` against the `leg` of the `hedge_position`,
+    /// paying the taker `fee`.
+    fn settle_filled_hedge_order(
+        &mut self,
+        order: &MarketOrder<I, D, BaseOrQuote, UserOrderId, Filled<I, D, BaseOrQuote>>,
+        leg: HedgeLeg,
+        fee: Fee<I, D, Taker>,
+    ) {
+        let filled_qty = order.quantity();
+        let fill_price = order.state().avg_fill_price();
+        self.last_market_fill_price = Some(fill_price);
+
+        let value = BaseOrQuote::PairedCurrency::convert_from(filled_qty, fill_price);
+        let fees = value * *fee.as_ref();
+        self.account_tracker.log_fee(fees);
+
+        let treasury_balance = Self::treasury_balance(&self.transaction_accounting);
+        self.hedge_position
+            .change_position(
+                filled_qty,
+                fill_price,
+                order.side(),
+                leg,
+                &mut self.transaction_accounting,
+                self.config.contract_spec().init_margin_req(),
+                fees,
+                self.config.pnl_realization_policy(),
+                self.config.credit_maker_rebates(),
+            )
+            .expect("The hedge leg can be changed");
+        Self::segregate_realized_pnl(
+            &self.config,
+            &mut self.transaction_accounting,
+            treasury_balance,
+        );
+        self.fills.push(ExecutedTrade {
+            order_id: order.state().meta().id(),
+            side: order.side(),
+            price: fill_price,
+            quantity: filled_qty,
+            fee: fees,
+            timestamp_exchange_ns: self.market_state.current_timestamp_ns(),
+        });
+        self.account_tracker.log_market_order_fill();
+        self.account_tracker
+            .log_trade(order.side(), fill_price, filled_qty);
+        self.assert_wallet_balance();
+    }
+
+    /// Close every hedge leg which breached its liquidation price with a market order.
+    ///
+    /// # Returns:
+    /// Whether any leg was liquidated.
+    fn liquidate_hedge_legs(&mut self) -> bool {
+        let mut liquidated = false;
+        for leg in [HedgeLeg::Long, HedgeLeg::Short] {
+            let (position, side) = match (leg, self.hedge_position.leg(leg)) {
+                (_, None) => continue,
+                (HedgeLeg::Long, Some(inner)) => (Position::Long(inner.clone()), Side::Sell),
+                (HedgeLeg::Short, Some(inner)) => (Position::Short(inner.clone()), Side::Buy),
+            };
+            if <IsolatedMarginRiskEngine<I, D, BaseOrQuote> as RiskEngine<
+                I,
+                D,
+                BaseOrQuote,
+                UserOrderId,
+            >>::check_maintenance_margin(
+                &self.risk_engine, &self.market_state, &position
+            )
+            .is_ok()
+            {
+                continue;
+            }
+            warn!("liquidating hedge leg {leg:?} of {position}");
+            let quantity = position.quantity().abs();
+            let fill_price = match side {
+                Side::Buy => self.market_state.ask(),
+                Side::Sell => self.market_state.bid(),
+            };
+            let meta = ExchangeOrderMeta::new(
+                self.next_order_id(),
+                self.market_state.current_timestamp_ns(),
+            );
+            let filled_order = MarketOrder::new(side, quantity)
+                .expect("Can create market order.")
+                .into_pending(meta)
+                .into_filled(fill_price, self.market_state.current_timestamp_ns());
+            let fee_taker = self.config.contract_spec().fee_taker();
+            self.settle_filled_hedge_order(&filled_order, leg, fee_taker);
+            liquidated = true;
+        }
+        liquidated
+    }
+
+    /// The price at which a `MarketOrder` of `side` and `quantity` fills.
+    /// If `fill_market_orders_from_depth` is enabled and depth is available,
+    /// it's the volume weighted average price of walking the book levels,
+    /// where a quantity exceeding the book fills at the worst level.
+    /// Otherwise it's the best bid or ask.
+    ///
+    /// # Returns:
+    /// `RiskError::NoLiquidity` if the best bid or ask is not known yet,
+    /// or the most recent `Depth` update has no levels on the side the order fills against.
+    fn market_order_fill_price(
+        &self,
+        side: Side,
+        quantity: BaseOrQuote,
+    ) -> Result<QuoteCurrency<I, D>> {
+        let (touch_price, depth) = match side {
+            Side::Buy => (self.market_state.ask(), self.market_state.ask_depth()),
+            Side::Sell => (self.market_state.bid(), self.market_state.bid_depth()),
+        };
+        if touch_price.is_zero() {
+            return Err(RiskError::NoLiquidity.into());
+        }
+        if !self.config.fill_market_orders_from_depth() {
+            return Ok(touch_price);
+        }
+        if depth.is_empty() {
+            if self.market_state.has_depth() {
+                return Err(RiskError::NoLiquidity.into());
+            }
+            return Ok(touch_price);
+        }
+
+        let mut remaining_qty = *quantity.as_ref();
+        let mut notional = Decimal::zero();
+        let mut worst_price = touch_price;
+        for (price, level_qty) in depth {
+            if remaining_qty <= Decimal::zero() {
+                break;
+            }
+            let qty = min(*level_qty, remaining_qty);
+            notional = notional + *price.as_ref() * qty;
+            remaining_qty = remaining_qty - qty;
+            worst_price = *price;
+        }
+        if remaining_qty > Decimal::zero() {
+            notional = notional + *worst_price.as_ref() * remaining_qty;
+        }
+        Ok(QuoteCurrency::from(notional / *quantity.as_ref()))
+    }
+
+    /// Settle a filled taker `order`, paying the taker `fee`.
     fn settle_filled_market_order(
         &mut self,
         order: MarketOrder<I, D, BaseOrQuote, UserOrderId, Filled<I, D, BaseOrQuote>>,
+        fee: Fee<I, D, Taker>,
     ) {
         let filled_qty = order.quantity();
         assert!(filled_qty > BaseOrQuote::zero());
         let fill_price = order.state().avg_fill_price();
         assert!(fill_price > QuoteCurrency::zero());
+        self.last_market_fill_price = Some(fill_price);
 
         let value = BaseOrQuote::PairedCurrency::convert_from(filled_qty, fill_price);
-        let fees = value * *self.config.contract_spec().fee_taker().as_ref();
+        let fees = value * *fee.as_ref();
+        self.account_tracker.log_fee(fees);
 
+        let prev_qty = self.position.quantity();
+        let prev_entry_price = self.position.entry_price();
+        let treasury_balance = Self::treasury_balance(&self.transaction_accounting);
         self.position.change_position(
             filled_qty,
             fill_price,
@@ -295,12 +1544,159 @@ where
             &mut self.transaction_accounting,
             self.config.contract_spec().init_margin_req(),
             fees,
+            self.config.pnl_realization_policy(),
+            self.config.credit_maker_rebates(),
+        );
+        Self::segregate_realized_pnl(
+            &self.config,
+            &mut self.transaction_accounting,
+            treasury_balance,
+        );
+        Self::record_excursion(
+            prev_qty,
+            &self.position,
+            &mut self.current_trade_mfe,
+            &mut self.mfe_distribution,
+        );
+        Self::record_excursion(
+            prev_qty,
+            &self.position,
+            &mut self.current_trade_mae,
+            &mut self.mae_distribution,
         );
+        if let Some(realized_pnl) = Self::record_closed_position_pnl(
+            prev_qty,
+            prev_entry_price,
+            fill_price,
+            &self.position,
+            &mut self.current_position_pnl,
+            &mut self.last_closed_position_pnl,
+        ) {
+            *self
+                .realized_pnl_by_order
+                .entry(order.state().meta().id())
+                .or_insert_with(BaseOrQuote::PairedCurrency::zero) += realized_pnl;
+            self.total_realized_pnl += realized_pnl;
+        }
+        Self::settle_rebates(&self.config, &mut self.transaction_accounting);
+        self.fills.push(ExecutedTrade {
+            order_id: order.state().meta().id(),
+            side: order.side(),
+            price: fill_price,
+            quantity: filled_qty,
+            fee: fees,
+            timestamp_exchange_ns: self.market_state.current_timestamp_ns(),
+        });
         self.account_tracker.log_market_order_fill();
         self.account_tracker
             .log_trade(order.side(), fill_price, filled_qty);
     }
 
+    /// Credits any rebates to the users wallet, unless they should accrue separately.
+    #[inline]
+    fn settle_rebates(
+        config: &Config<I, D, BaseOrQuote::PairedCurrency>,
+        transaction_accounting: &mut TransactionAccountingT,
+    ) {
+        if config.separate_rebate_account() {
+            return;
+        }
+        let rebates = transaction_accounting
+            .margin_balance_of(REBATE_ACCOUNT)
+            .expect("is valid account");
+        if rebates > BaseOrQuote::PairedCurrency::zero() {
+            let transaction = Transaction::new(USER_WALLET_ACCOUNT, REBATE_ACCOUNT, rebates);
+            transaction_accounting
+                .create_margin_transfer(transaction)
+                .expect("margin transfer works");
+        }
+    }
+
+    /// The combined balance of the `TREASURY_ACCOUNT`, `REBATE_ACCOUNT` and `PENDING_PNL_ACCOUNT`.
+    /// A position change only moves realized profit and loss between the treasury and the user,
+    /// possibly by way of the `PENDING_PNL_ACCOUNT`,
+    /// and rebates from the treasury to the `REBATE_ACCOUNT`,
+    /// so the decrease of this balance is the profit realized by the user.
+    fn treasury_balance(
+        transaction_accounting: &TransactionAccountingT,
+    ) -> BaseOrQuote::PairedCurrency {
+        transaction_accounting
+            .margin_balance_of(TREASURY_ACCOUNT)
+            .expect("is valid account")
+            + transaction_accounting
+                .margin_balance_of(REBATE_ACCOUNT)
+                .expect("is valid account")
+            + transaction_accounting
+                .margin_balance_of(PENDING_PNL_ACCOUNT)
+                .expect("is valid account")
+    }
+
+    /// Move the profit realized by a position change from the wallet to the `REALIZED_PNL_ACCOUNT`,
+    /// if `Config::separate_realized_pnl_account` is enabled.
+    fn segregate_realized_pnl(
+        config: &Config<I, D, BaseOrQuote::PairedCurrency>,
+        transaction_accounting: &mut TransactionAccountingT,
+        treasury_balance_before: BaseOrQuote::PairedCurrency,
+    ) {
+        if !config.separate_realized_pnl_account() {
+            return;
+        }
+        let realized_profit =
+            treasury_balance_before - Self::treasury_balance(transaction_accounting);
+        if realized_profit > BaseOrQuote::PairedCurrency::zero() {
+            let transaction =
+                Transaction::new(REALIZED_PNL_ACCOUNT, USER_WALLET_ACCOUNT, realized_profit);
+            transaction_accounting
+                .create_margin_transfer(transaction)
+                .expect("margin transfer works");
+        }
+    }
+
+    /// The balance that can back new orders in the risk checks.
+    fn available_balance_for_new_orders(&self) -> Result<BaseOrQuote::PairedCurrency> {
+        let available_wallet_balance = self
+            .transaction_accounting
+            .margin_balance_of(USER_WALLET_ACCOUNT)?;
+        Ok(available_wallet_balance + self.unrealized_profit_credit() - self.reserved_fees())
+    }
+
+    /// The outstanding fees of the position that are reserved from the available wallet balance,
+    /// which is zero unless `Config::reserve_outstanding_fees` is enabled.
+    fn reserved_fees(&self) -> BaseOrQuote::PairedCurrency {
+        if !self.config.reserve_outstanding_fees() {
+            return BaseOrQuote::PairedCurrency::zero();
+        }
+        max(
+            self.position.outstanding_fees(),
+            BaseOrQuote::PairedCurrency::zero(),
+        )
+    }
+
+    /// The unrealized profit of the position that counts as available margin,
+    /// which is zero unless `Config::credit_unrealized_pnl` is enabled.
+    fn unrealized_profit_credit(&self) -> BaseOrQuote::PairedCurrency {
+        if !self.config.credit_unrealized_pnl() {
+            return BaseOrQuote::PairedCurrency::zero();
+        }
+        let upnl = self
+            .position
+            .unrealized_pnl(self.market_state.bid(), self.market_state.ask());
+        max(upnl, BaseOrQuote::PairedCurrency::zero())
+    }
+
+    /// Asserts that the wallet balance is not negative,
+    /// unless the `Config::credit_unrealized_pnl` covers it.
+    fn assert_wallet_balance(&self) {
+        if self.config.credit_unrealized_pnl() {
+            assert_user_wallet_balance_with_credit(
+                &self.transaction_accounting,
+                self.unrealized_profit_credit(),
+            );
+        } else {
+            assert_user_wallet_balance(&self.transaction_accounting);
+        }
+    }
+
     #[inline]
     fn next_order_id(&mut self) -> OrderId {
         let oid = self.next_order_id;
@@ -308,50 +1704,266 @@ where
         oid
     }
 
+    /// Remember the `order_id` as submitted in the current step of the `MarketState`.
+    fn record_step_submission(&mut self, order_id: OrderId) {
+        let step = self.market_state.num_updates();
+        if step != self.submission_step {
+            self.submission_step = step;
+            self.orders_submitted_this_step.clear();
+        }
+        self.orders_submitted_this_step.push(order_id);
+    }
+
     /// # Arguments:
     /// `order`: The order that is being submitted.
     ///
     /// # Returns:
     /// If Ok, the order with timestamp and id filled in.
-    /// Else its an error.
+    /// A marketable order with `RePricing::Marketable` has then already been filled
+    /// and its fill is recorded like the one of a `MarketOrder`.
+    /// Else its an error, e.g. `Error::DuplicateUserOrderId` if an active limit order
+    /// already uses the same non-default `user_order_id`.
     pub fn submit_limit_order(
         &mut self,
         order: LimitOrder<I, D, BaseOrQuote, UserOrderId, NewOrder>,
+    ) -> Result<LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>> {
+        self.submit_limit_order_with_volume_ahead(order, BaseOrQuote::zero())
+    }
+
+    /// Submit a `StopLimitOrder` to the exchange, which is held until the market reaches its
+    /// `trigger_price` during `update_state`, after which its `LimitOrder` is submitted
+    /// through `submit_limit_order`.
+    ///
+    /// # Arguments:
+    /// `order`: The stop-limit order that is being submitted.
+    ///
+    /// # Returns:
+    /// If Ok, the order is held until triggered.
+    /// Else its an error.
+    pub fn submit_stop_limit_order(
+        &mut self,
+        order: StopLimitOrder<I, D, BaseOrQuote, UserOrderId>,
+    ) -> Result<()> {
+        trace!("submit_stop_limit_order: {order:?}");
+        if self.expired {
+            return Err(Error::ContractExpired);
+        }
+        if self.blown_up {
+            return Err(Error::AccountBlownUp);
+        }
+        if self.config.hedge_mode() {
+            return Err(Error::HedgeModeEnabled);
+        }
+        self.check_market_initialized()?;
+        self.check_trading_halt()?;
+
+        // Basic checks
+        self.config
+            .contract_spec()
+            .quantity_filter()
+            .validate_order_quantity_at(order.quantity(), order.limit_price())?;
+        self.config
+            .contract_spec()
+            .price_filter()
+            .validate_limit_price(order.limit_price(), self.market_state.mid_price())?;
+
+        self.active_stop_orders.push(order);
+        Ok(())
+    }
+
+    /// Submit a `LimitOrder` with `volume_ahead` resting before it at its price level.
+    /// The `volume_ahead` is only used if `Config::queue_position_model` is enabled,
+    /// in which case trades at the limit price must consume it before the order fills.
+    ///
+    /// # Arguments:
+    /// `order`: The order that is being submitted.
+    /// `volume_ahead`: The non-negative quantity resting ahead of the order.
+    ///
+    /// # Returns:
+    /// If Ok, the order with timestamp and id filled in.
+    /// Else its an error.
+    pub fn submit_limit_order_with_volume_ahead(
+        &mut self,
+        order: LimitOrder<I, D, BaseOrQuote, UserOrderId, NewOrder>,
+        volume_ahead: BaseOrQuote,
+    ) -> Result<LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>> {
+        self.submit_limit_order_replacing(order, volume_ahead, None)
+    }
+
+    /// Submit a new limit order, which optionally replaces the active order `replaced_order_id`.
+    /// The replaced order leaves the book without releasing its order margin,
+    /// so only the net difference to the margin of the new order is transferred.
+    /// If the new order is rejected, the replaced order stays active.
+    fn submit_limit_order_replacing(
+        &mut self,
+        order: LimitOrder<I, D, BaseOrQuote, UserOrderId, NewOrder>,
+        volume_ahead: BaseOrQuote,
+        replaced_order_id: Option<OrderId>,
+    ) -> Result<LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>> {
+        let rejected_order = self.config.record_rejected_orders().then(|| order.clone());
+        let result = self.try_submit_limit_order(order, volume_ahead, replaced_order_id);
+        if let (Some(order), Err(err)) = (rejected_order, &result) {
+            self.rejected_orders
+                .push((RejectedOrder::Limit(order), err.clone()));
+        }
+        result
+    }
+
+    fn try_submit_limit_order(
+        &mut self,
+        order: LimitOrder<I, D, BaseOrQuote, UserOrderId, NewOrder>,
+        volume_ahead: BaseOrQuote,
+        replaced_order_id: Option<OrderId>,
     ) -> Result<LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>> {
         trace!("submit_order: {}", order);
+        if self.expired {
+            return Err(Error::ContractExpired);
+        }
+        if self.blown_up {
+            return Err(Error::AccountBlownUp);
+        }
+        if self.config.hedge_mode() {
+            return Err(Error::HedgeModeEnabled);
+        }
+        self.check_market_initialized()?;
+        self.check_trading_halt()?;
+        self.check_reentry_cooldown()?;
+        self.check_rate_limit()?;
         self.account_tracker.log_limit_order_submission(&order);
 
         // Basic checks
         self.config
             .contract_spec()
             .quantity_filter()
-            .validate_order_quantity(order.remaining_quantity())?;
+            .validate_order_quantity_at(order.remaining_quantity(), order.limit_price())?;
         self.config
             .contract_spec()
             .price_filter()
             .validate_limit_price(order.limit_price(), self.market_state.mid_price())?;
+        let user_order_id = order.user_order_id();
+        if user_order_id != UserOrderId::default()
+            && self.active_limit_orders.values().any(|active| {
+                active.user_order_id() == user_order_id && Some(active.id()) != replaced_order_id
+            })
+        {
+            return Err(Error::DuplicateUserOrderId);
+        }
+
+        let mut crossed_order_ids = self.crossed_own_order_ids(order.side(), order.limit_price());
+        crossed_order_ids.retain(|order_id| Some(*order_id) != replaced_order_id);
+        // The resting orders to cancel once the incoming order passed the risk checks.
+        let mut cancelled_order_ids = Vec::new();
+        if !crossed_order_ids.is_empty() {
+            match self.config.self_trade_prevention() {
+                SelfTradePrevention::Disabled => {}
+                SelfTradePrevention::CancelResting => cancelled_order_ids = crossed_order_ids,
+                SelfTradePrevention::CancelIncoming => {
+                    debug!("cancelling incoming order {order} to prevent a self trade");
+                    return Err(OrderError::SelfTradeIncomingCancelled.into());
+                }
+                SelfTradePrevention::Reject => {
+                    return Err(OrderError::SelfTradeRejected.into());
+                }
+            }
+        }
 
         let meta = ExchangeOrderMeta::new(
             self.next_order_id(),
             self.market_state.current_timestamp_ns(),
         );
-        let order = order.into_pending(meta);
+        self.record_step_submission(meta.id());
+        let mut order = order.into_pending(meta);
+        if self.config.queue_position_model() {
+            order.set_queue_position(QueuePosition::new(volume_ahead));
+        }
 
-        let available_wallet_balance = self
-            .transaction_accounting
-            .margin_balance_of(USER_WALLET_ACCOUNT)?;
-        self.risk_engine.check_limit_order(
-            &self.position,
-            &order,
-            available_wallet_balance,
-            &self.order_margin,
-        )?;
+        // The replaced and cancelled orders leave the book without releasing their order margin.
+        let removed_orders: Vec<_> = replaced_order_id
+            .into_iter()
+            .chain(cancelled_order_ids)
+            .map(|order_id| {
+                let removed_order = self
+                    .active_limit_orders
+                    .remove_by_order_id(order_id)
+                    .expect("The order is active");
+                self.order_margin.remove(CancelBy::OrderId(order_id));
+                removed_order
+            })
+            .collect();
+        let released_margin = if removed_orders.is_empty() {
+            BaseOrQuote::PairedCurrency::zero()
+        } else {
+            self.transaction_accounting
+                .margin_balance_of(USER_ORDER_MARGIN_ACCOUNT)
+                .expect("is valid")
+                - self.order_margin.order_margin(
+                    self.config.contract_spec().init_margin_req(),
+                    &self.position,
+                )
+        };
+        let marketable_fill_price = match self.check_new_limit_order(&order, released_margin) {
+            Ok(marketable_fill_price) => marketable_fill_price,
+            Err(err) => {
+                for removed_order in removed_orders {
+                    self.order_margin.update(&removed_order)?;
+                    self.active_limit_orders.insert(removed_order)?;
+                }
+                return Err(err);
+            }
+        };
+
+        match marketable_fill_price {
+            Some(fill_price) => self.execute_marketable_limit_order(order.clone(), fill_price),
+            None => self.append_limit_order(order.clone())?,
+        }
+        for _removed_order in removed_orders {
+            self.account_tracker.log_limit_order_cancellation();
+        }
 
+        Ok(order)
+    }
+
+    /// Check whether the pending limit `order` can be appended,
+    /// with the `released_margin` of the replaced and cancelled orders counting as available.
+    ///
+    /// A marketable order with `RePricing::Marketable` executes immediately like a `MarketOrder`,
+    /// so it is checked for liquidity and for its margin and `fee_taker_limit` instead.
+    ///
+    /// # Returns:
+    /// If Ok, the fill price if the order is marketable.
+    fn check_new_limit_order(
+        &self,
+        order: &LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>,
+        released_margin: BaseOrQuote::PairedCurrency,
+    ) -> Result<Option<QuoteCurrency<I, D>>> {
+        let available_wallet_balance = self.available_balance_for_new_orders()? + released_margin;
         // If a limit order is marketable, it will take liquidity from the book at the `limit_price` price level and pay the taker fee,
         let marketable = match order.side() {
             Side::Buy => order.limit_price() >= self.market_state.ask(),
             Side::Sell => order.limit_price() <= self.market_state.bid(),
         };
+        if marketable && matches!(order.re_pricing(), RePricing::Marketable) {
+            let fill_price = self.marketable_limit_order_fill_price(order.side())?;
+            let position_margin = self
+                .transaction_accounting
+                .margin_balance_of(USER_POSITION_MARGIN_ACCOUNT)?;
+            self.risk_engine.check_market_order(
+                &self.position,
+                position_margin,
+                &Self::marketable_limit_order_as_market_order(order)?,
+                fill_price,
+                available_wallet_balance,
+                self.config.contract_spec().fee_taker_limit(),
+            )?;
+            return Ok(Some(fill_price));
+        }
+        self.risk_engine.check_limit_order(
+            &self.position,
+            order,
+            available_wallet_balance,
+            &self.order_margin,
+        )?;
+
         match order.re_pricing() {
             RePricing::GoodTilCrossing => {
                 if marketable {
@@ -366,11 +1978,76 @@ where
                     ));
                 }
             }
+            RePricing::Marketable => {}
         }
 
-        self.append_limit_order(order.clone(), marketable)?;
+        Ok(None)
+    }
 
-        Ok(order)
+    /// The best bid or ask a marketable limit order of `side` fills at.
+    ///
+    /// # Returns:
+    /// `RiskError::NoLiquidity` if the depth of that side of the book has been consumed,
+    /// with `Config::fill_market_orders_from_depth` enabled.
+    fn marketable_limit_order_fill_price(&self, side: Side) -> Result<QuoteCurrency<I, D>> {
+        let (touch_price, depth) = match side {
+            Side::Buy => (self.market_state.ask(), self.market_state.ask_depth()),
+            Side::Sell => (self.market_state.bid(), self.market_state.bid_depth()),
+        };
+        if touch_price.is_zero()
+            || (self.config.fill_market_orders_from_depth()
+                && self.market_state.has_depth()
+                && depth.is_empty())
+        {
+            return Err(RiskError::NoLiquidity.into());
+        }
+        Ok(touch_price)
+    }
+
+    /// The `MarketOrder` a marketable limit `order` executes as.
+    fn marketable_limit_order_as_market_order(
+        order: &LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>,
+    ) -> Result<MarketOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>> {
+        Ok(MarketOrder::new_with_user_order_id(
+            order.side(),
+            order.remaining_quantity(),
+            order.user_order_id(),
+        )?
+        .into_pending(order.state().meta().clone()))
+    }
+
+    /// Fill the marketable limit `order` immediately in full at the `fill_price`,
+    /// paying the `fee_taker_limit` of the contract. The order never becomes active.
+    /// The order margin of any orders it replaced or cancelled is released to the wallet.
+    fn execute_marketable_limit_order(
+        &mut self,
+        order: LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>,
+        fill_price: QuoteCurrency<I, D>,
+    ) {
+        debug!("executing marketable limit order {order}");
+        let order_margin = self
+            .transaction_accounting
+            .margin_balance_of(USER_ORDER_MARGIN_ACCOUNT)
+            .expect("is valid");
+        let new_order_margin = self.order_margin.order_margin(
+            self.config.contract_spec().init_margin_req(),
+            &self.position,
+        );
+        if new_order_margin < order_margin {
+            let delta = order_margin - new_order_margin;
+            let transaction =
+                Transaction::new(USER_WALLET_ACCOUNT, USER_ORDER_MARGIN_ACCOUNT, delta);
+            self.transaction_accounting
+                .create_margin_transfer(transaction)
+                .expect("margin transfer works.");
+        }
+
+        let filled_order = Self::marketable_limit_order_as_market_order(&order)
+            .expect("The limit order has a positive quantity")
+            .into_filled(fill_price, self.market_state.current_timestamp_ns());
+        let fee_taker_limit = self.config.contract_spec().fee_taker_limit();
+        self.settle_filled_market_order(filled_order, fee_taker_limit);
+        self.assert_wallet_balance();
     }
 
     /// Amend an existing limit order.
@@ -384,6 +2061,7 @@ where
         existing_order_id: OrderId,
         mut new_order: LimitOrder<I, D, BaseOrQuote, UserOrderId, NewOrder>,
     ) -> Result<LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>> {
+        self.check_trading_halt()?;
         let existing_order = self
             .active_limit_orders
             .get_by_id(existing_order_id)
@@ -419,16 +2097,111 @@ where
         self.submit_limit_order(new_order)
     }
 
-    /// Append a new limit order as active order.
-    /// If limit order is `marketable`, the order will take liquidity from the book at the `limit_price` price level.
-    /// Then it pays the taker fee for the quantity that was taken from the book, the rest of the quantity (if any)
-    /// will be placed into the book as a passive order.
+    /// Replace the active limit order `old_id` with `new_order` in one call.
+    /// Unlike cancelling and resubmitting, the order margin of the old order is not released
+    /// and re-reserved, only the net difference to the margin of the new order is transferred.
+    ///
+    /// If the new order is rejected, the old order stays active.
+    /// The new order gets a new `OrderId`.
+    pub fn replace_order(
+        &mut self,
+        old_id: OrderId,
+        new_order: LimitOrder<I, D, BaseOrQuote, UserOrderId, NewOrder>,
+    ) -> Result<LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>> {
+        if self.active_limit_orders.get_by_id(old_id).is_none() {
+            return Err(if old_id < self.next_order_id {
+                Error::OrderNoLongerActive
+            } else {
+                Error::OrderIdNotFound { order_id: old_id }
+            });
+        }
+        self.submit_limit_order_replacing(new_order, BaseOrQuote::zero(), Some(old_id))
+    }
+
+    /// Reduce the remaining quantity of an active limit order to `new_qty`,
+    /// keeping the order in the book and releasing the freed order margin.
+    /// This is cheaper than cancelling and resubmitting the order to downsize it.
+    ///
+    /// # Returns:
+    /// If Ok, the order with its reduced remaining quantity.
+    /// `OrderError::ReducedQuantityNotLess` if `new_qty` is not less than the remaining quantity.
+    pub fn reduce_limit_order(
+        &mut self,
+        order_id: OrderId,
+        new_qty: BaseOrQuote,
+    ) -> Result<LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>> {
+        if new_qty <= BaseOrQuote::zero() {
+            return Err(OrderError::OrderQuantityLTEZero.into());
+        }
+        let mut order = self
+            .active_limit_orders
+            .get_by_id(order_id)
+            .ok_or_else(|| {
+                if order_id < self.next_order_id {
+                    Error::OrderNoLongerActive
+                } else {
+                    Error::OrderIdNotFound { order_id }
+                }
+            })?
+            .clone();
+        self.config
+            .contract_spec()
+            .quantity_filter()
+            .validate_order_quantity_at(new_qty, order.limit_price())?;
+        if new_qty >= order.remaining_quantity() {
+            return Err(OrderError::ReducedQuantityNotLess.into());
+        }
+        order.set_remaining_quantity(new_qty);
+
+        let order_margin = self
+            .transaction_accounting
+            .margin_balance_of(USER_ORDER_MARGIN_ACCOUNT)
+            .expect("is valid");
+        self.order_margin.update(&order)?;
+        self.active_limit_orders.insert(order.clone())?;
+        let new_order_margin = self.order_margin.order_margin(
+            self.config.contract_spec().init_margin_req(),
+            &self.position,
+        );
+        assert!(
+            new_order_margin <= order_margin,
+            "Reducing a limit order can not increase the order margin"
+        );
+        if new_order_margin < order_margin {
+            let delta = order_margin - new_order_margin;
+            let transaction =
+                Transaction::new(USER_WALLET_ACCOUNT, USER_ORDER_MARGIN_ACCOUNT, delta);
+            self.transaction_accounting
+                .create_margin_transfer(transaction)
+                .expect("margin transfer works.");
+        }
+
+        assert_eq!(
+            self.order_margin.active_limit_orders(),
+            &self.active_limit_orders
+        );
+
+        Ok(order)
+    }
+
+    /// The ids of the active limit orders which a new order of `side` at `limit_price` would cross.
+    fn crossed_own_order_ids(&self, side: Side, limit_price: QuoteCurrency<I, D>) -> Vec<OrderId> {
+        self.active_limit_orders
+            .values()
+            .filter(|resting| match side {
+                Side::Buy => resting.side() == Side::Sell && limit_price >= resting.limit_price(),
+                Side::Sell => resting.side() == Side::Buy && limit_price <= resting.limit_price(),
+            })
+            .map(|resting| resting.id())
+            .collect()
+    }
+
+    /// Append a new limit order as active order, which rests in the book as a passive order.
     fn append_limit_order(
         &mut self,
         order: LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>,
-        marketable: bool,
     ) -> Result<()> {
-        debug!("append_limit_order: order: {order}, marketable: {marketable}");
+        debug!("append_limit_order: order: {order}");
         debug!(
             "active_limit_orders: {}, market_state: {}, position: {}",
             self.active_limit_orders, self.market_state, self.position,
@@ -452,8 +2225,8 @@ where
                 new_order_margin - order_margin,
             ),
             Ordering::Less => Transaction::new(
-                USER_ORDER_MARGIN_ACCOUNT,
                 USER_WALLET_ACCOUNT,
+                USER_ORDER_MARGIN_ACCOUNT,
                 order_margin - new_order_margin,
             ),
             Ordering::Equal => return Ok(()),
@@ -474,7 +2247,7 @@ where
         } else {
             true
         });
-        assert_user_wallet_balance(&self.transaction_accounting);
+        self.assert_wallet_balance();
 
         Ok(())
     }
@@ -548,6 +2321,68 @@ where
         Ok(removed_order)
     }
 
+    /// Cancel all active limit orders whose `UserOrderId` matches the `predicate`,
+    /// e.g to cancel a group of orders sharing a common tag.
+    /// The order margin is released once for all cancelled orders.
+    ///
+    /// # Returns:
+    /// The cancelled orders, which is empty if none matched.
+    pub fn cancel_orders_by_predicate<F>(
+        &mut self,
+        predicate: F,
+    ) -> Vec<LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>>
+    where
+        F: Fn(UserOrderId) -> bool,
+    {
+        let order_margin = self
+            .transaction_accounting
+            .margin_balance_of(USER_ORDER_MARGIN_ACCOUNT)
+            .expect("is valid");
+
+        let order_ids: Vec<OrderId> = self
+            .active_limit_orders
+            .values()
+            .filter(|order| predicate(order.user_order_id()))
+            .map(|order| order.id())
+            .collect();
+        let mut cancelled = Vec::with_capacity(order_ids.len());
+        for order_id in order_ids {
+            let order = self
+                .active_limit_orders
+                .remove_by_order_id(order_id)
+                .expect("The order is active");
+            self.order_margin.remove(CancelBy::OrderId(order_id));
+            self.account_tracker.log_limit_order_cancellation();
+            cancelled.push(order);
+        }
+        if cancelled.is_empty() {
+            return cancelled;
+        }
+
+        let new_order_margin = self.order_margin.order_margin(
+            self.config.contract_spec().init_margin_req(),
+            &self.position,
+        );
+        assert!(
+            new_order_margin <= order_margin,
+            "When cancelling limit orders, the new order margin is smaller or equal the old order margin"
+        );
+        if new_order_margin < order_margin {
+            let delta = order_margin - new_order_margin;
+            let transaction =
+                Transaction::new(USER_WALLET_ACCOUNT, USER_ORDER_MARGIN_ACCOUNT, delta);
+            self.transaction_accounting
+                .create_margin_transfer(transaction)
+                .expect("margin transfer works.");
+        }
+        debug_assert_eq!(
+            self.order_margin.active_limit_orders(),
+            &self.active_limit_orders
+        );
+
+        cancelled
+    }
+
     /// Removes an executed limit order from the list of active ones.
     /// order margin updates are handled separately.
     #[inline]
@@ -561,6 +2396,32 @@ where
         debug_assert_eq!(order.id(), order_id);
     }
 
+    /// Record the quantity traded at the limit price in the orders `QueuePosition`.
+    /// Returns the quantity that trades through the volume ahead and fills the order, if any.
+    fn fill_from_queue<U>(
+        order: &mut LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>,
+        market_update: &U,
+        order_margin: &mut OrderMargin<I, D, BaseOrQuote, UserOrderId>,
+    ) -> Option<BaseOrQuote>
+    where
+        U: MarketUpdate<I, D, BaseOrQuote>,
+    {
+        let traded_qty =
+            market_update.traded_quantity_at_price(order.limit_price(), order.side())?;
+        let mut queue_position = (*order.state().queue_position())?;
+        let passed_qty = queue_position.record_traded_volume(traded_qty);
+        order.set_queue_position(queue_position);
+        // Keep the copy of the order in `OrderMargin` in sync.
+        order_margin.update_queue_position(order);
+        trace!(
+            "order {} queue position: {} ahead",
+            order.id(),
+            queue_position.remaining_volume_ahead()
+        );
+
+        (!passed_qty.is_zero()).then(|| min(passed_qty, order.remaining_quantity()))
+    }
+
     /// Checks for the execution of active limit orders in the account.
     /// NOTE: only public for benchmarking purposes.
     pub fn check_active_orders<U>(&mut self, market_update: &U)
@@ -579,8 +2440,55 @@ where
             &self.active_limit_orders
         );
 
+        let batch_order_margin = self.config.batch_order_margin_updates();
         for order in self.active_limit_orders.values_mut() {
-            if let Some(filled_qty) = market_update.limit_order_filled(order) {
+            let mut filled_qty = market_update.limit_order_filled(order);
+            let mut prev_queue_position = None;
+            if filled_qty.is_none() && self.config.queue_position_model() {
+                prev_queue_position = *order.state().queue_position();
+                filled_qty = Self::fill_from_queue(order, market_update, &mut self.order_margin);
+            }
+            if let Some(mut filled_qty) = filled_qty {
+                let tick_size = self
+                    .config
+                    .contract_spec()
+                    .quantity_filter()
+                    .tick_size_at(order.limit_price());
+                if let Some(fraction) = self.config.max_fill_fraction_per_tick() {
+                    // At least one tick fills per tick, so small orders are not starved.
+                    let max_fill_qty = min(
+                        max(order.total_quantity() * fraction, tick_size),
+                        order.remaining_quantity(),
+                    );
+                    filled_qty = min(filled_qty, max_fill_qty);
+                }
+                // Round the fill to the quantity tick, the residue stays in the remaining quantity.
+                let filled_qty = BaseOrQuote::from(
+                    filled_qty
+                        .as_ref()
+                        .quantize_round_to_zero(*tick_size.as_ref()),
+                );
+                if filled_qty.is_zero() {
+                    trace!("filled quantity of order {} rounds to zero", order.id());
+                    continue;
+                }
+                if let Some(min_fill_qty) = self.config.min_fill_qty() {
+                    if filled_qty < min_fill_qty && filled_qty < order.remaining_quantity() {
+                        trace!(
+                            "deferring fill of order {}: {filled_qty} < min_fill_qty {min_fill_qty}",
+                            order.id()
+                        );
+                        if let Some(mut queue_position) = prev_queue_position {
+                            // Only the volume ahead is consumed, the deferred quantity stays
+                            // available to fill the order.
+                            queue_position
+                                .record_traded_volume(queue_position.remaining_volume_ahead());
+                            order.set_queue_position(queue_position);
+                            self.order_margin.update_queue_position(order);
+                        }
+                        continue;
+                    }
+                }
                 trace!(
                     "filled limit {} order {}: {filled_qty}/{} @ {}",
                     order.side(),
@@ -597,12 +2505,13 @@ where
                     .transaction_accounting
                     .margin_balance_of(USER_ORDER_MARGIN_ACCOUNT)
                     .expect("is valid");
-                debug_assert_eq!(
-                    order_margin,
-                    self.order_margin.order_margin(
-                        self.config.contract_spec().init_margin_req(),
-                        &self.position
-                    )
+                debug_assert!(
+                    batch_order_margin
+                        || order_margin
+                            == self.order_margin.order_margin(
+                                self.config.contract_spec().init_margin_req(),
+                                &self.position
+                            )
                 );
 
                 if let Some(filled_order) =
@@ -617,7 +2526,7 @@ where
                     debug_assert!(order.remaining_quantity() > BaseOrQuote::zero());
                     self.account_tracker.log_limit_order_fill(false, filled_qty);
                     self.limit_order_updates
-                        .push(LimitOrderUpdate::PartiallyFilled(order.clone()));
+                        .push(LimitOrderUpdate::PartiallyFilled(order.clone(), filled_qty));
                     self.order_margin
                         .update(order)
                         .expect("Can update an existing order");
@@ -626,6 +2535,10 @@ where
                 let value =
                     BaseOrQuote::PairedCurrency::convert_from(filled_qty, order.limit_price());
                 let fees = value * *self.config.contract_spec().fee_maker().as_ref();
+                self.account_tracker.log_fee(fees);
+                let prev_qty = self.position.quantity();
+                let prev_entry_price = self.position.entry_price();
+                let treasury_balance = Self::treasury_balance(&self.transaction_accounting);
                 self.position.change_position(
                     filled_qty,
                     order.limit_price(),
@@ -633,7 +2546,49 @@ where
                     &mut self.transaction_accounting,
                     self.config.contract_spec().init_margin_req(),
                     fees,
+                    self.config.pnl_realization_policy(),
+                    self.config.credit_maker_rebates(),
+                );
+                Self::segregate_realized_pnl(
+                    &self.config,
+                    &mut self.transaction_accounting,
+                    treasury_balance,
+                );
+                Self::record_excursion(
+                    prev_qty,
+                    &self.position,
+                    &mut self.current_trade_mfe,
+                    &mut self.mfe_distribution,
+                );
+                Self::record_excursion(
+                    prev_qty,
+                    &self.position,
+                    &mut self.current_trade_mae,
+                    &mut self.mae_distribution,
                 );
+                if let Some(realized_pnl) = Self::record_closed_position_pnl(
+                    prev_qty,
+                    prev_entry_price,
+                    order.limit_price(),
+                    &self.position,
+                    &mut self.current_position_pnl,
+                    &mut self.last_closed_position_pnl,
+                ) {
+                    *self
+                        .realized_pnl_by_order
+                        .entry(order.id())
+                        .or_insert_with(BaseOrQuote::PairedCurrency::zero) += realized_pnl;
+                    self.total_realized_pnl += realized_pnl;
+                }
+                Self::settle_rebates(&self.config, &mut self.transaction_accounting);
+                self.fills.push(ExecutedTrade {
+                    order_id: order.id(),
+                    side: order.side(),
+                    price: order.limit_price(),
+                    quantity: filled_qty,
+                    fee: fees,
+                    timestamp_exchange_ns: self.market_state.current_timestamp_ns(),
+                });
                 self.account_tracker
                     .log_trade(order.side(), order.limit_price(), filled_qty);
 
@@ -646,7 +2601,7 @@ where
                     "The order margin does not increase with a filled limit order event."
                 );
 
-                if new_order_margin < order_margin {
+                if !batch_order_margin && new_order_margin < order_margin {
                     let delta = order_margin - new_order_margin;
                     let transaction =
                         Transaction::new(USER_WALLET_ACCOUNT, USER_ORDER_MARGIN_ACCOUNT, delta);
@@ -656,10 +2611,33 @@ where
                 }
             }
         }
+        if batch_order_margin {
+            // Release the order margin of all the fills in this tick with a single transfer.
+            let order_margin = self
+                .transaction_accounting
+                .margin_balance_of(USER_ORDER_MARGIN_ACCOUNT)
+                .expect("is valid");
+            let new_order_margin = self.order_margin.order_margin(
+                self.config.contract_spec().init_margin_req(),
+                &self.position,
+            );
+            debug_assert!(new_order_margin <= order_margin);
+            if new_order_margin < order_margin {
+                let delta = order_margin - new_order_margin;
+                let transaction =
+                    Transaction::new(USER_WALLET_ACCOUNT, USER_ORDER_MARGIN_ACCOUNT, delta);
+                self.transaction_accounting
+                    .create_margin_transfer(transaction)
+                    .expect("margin transfer works");
+            }
+        }
         self.ids_to_remove.iter().for_each(|id| {
             Self::remove_executed_order_from_active(*id, &mut self.active_limit_orders)
         });
         self.ids_to_remove.clear();
+        // The active orders are iterated in arbitrary order, so sort for reproducible results.
+        self.limit_order_updates
+            .sort_unstable_by_key(|update| update.order_id());
         debug_assert_eq!(
             self.ids_to_remove.capacity(),
             self.config.max_num_open_orders()
@@ -686,7 +2664,7 @@ where
                 &self.position
             )
         );
-        assert_user_wallet_balance(&self.transaction_accounting);
+        self.assert_wallet_balance();
     }
 
     /// Get the balances of the user account.
@@ -696,7 +2674,8 @@ where
             available_wallet_balance: self
                 .transaction_accounting
                 .margin_balance_of(USER_WALLET_ACCOUNT)
-                .expect("is a valid account"),
+                .expect("is a valid account")
+                - self.reserved_fees(),
             position_margin: self
                 .transaction_accounting
                 .margin_balance_of(USER_POSITION_MARGIN_ACCOUNT)
@@ -708,6 +2687,233 @@ where
             _q: std::marker::PhantomData,
         }
     }
+
+    /// A serializable snapshot of the current position, marked to market at the current bid and ask.
+    pub fn position_snapshot(&self) -> PositionSnapshot<I, D, BaseOrQuote> {
+        let side = match &self.position {
+            Position::Neutral => None,
+            Position::Long(_) => Some(Side::Buy),
+            Position::Short(_) => Some(Side::Sell),
+        };
+        PositionSnapshot {
+            side,
+            quantity: self.position.quantity().abs(),
+            entry_price: self.position.entry_price(),
+            unrealized_pnl: self
+                .position
+                .unrealized_pnl(self.market_state.bid(), self.market_state.ask()),
+            margin: self
+                .transaction_accounting
+                .margin_balance_of(USER_POSITION_MARGIN_ACCOUNT)
+                .expect("is a valid account"),
+        }
+    }
+
+    /// The fees paid to build up the current position, which are outstanding until it is reduced.
+    /// Is zero for a neutral position.
+    pub fn position_fees(&self) -> BaseOrQuote::PairedCurrency {
+        self.position.outstanding_fees()
+    }
+
+    /// The rejected market and limit order submissions with the `Error` they were rejected with,
+    /// in chronological order. Is empty unless `Config::record_rejected_orders` is enabled.
+    #[inline]
+    pub fn rejected_orders(&self) -> &[(RejectedOrder<I, D, BaseOrQuote, UserOrderId>, Error)] {
+        &self.rejected_orders
+    }
+
+    /// The ids of the orders submitted since the last market update, i.e. in the current step.
+    /// Orders submitted in prior steps are excluded, so strategies can tell same-step orders apart.
+    pub fn orders_submitted_this_step(&self) -> &[OrderId] {
+        if self.submission_step != self.market_state.num_updates() {
+            return &[];
+        }
+        &self.orders_submitted_this_step
+    }
+
+    /// How much of a hypothetical limit order of `quantity` at `limit_price` would fill
+    /// immediately, helping to decide between passive and aggressive execution.
+    /// With order book depth, the opposite side is walked up to the `limit_price`.
+    /// Otherwise the full `quantity` is returned if the order is marketable at the touch,
+    /// as the size at the touch is unknown.
+    pub fn marketable_quantity(
+        &self,
+        side: Side,
+        limit_price: QuoteCurrency<I, D>,
+        quantity: BaseOrQuote,
+    ) -> BaseOrQuote {
+        let (touch_price, depth) = match side {
+            Side::Buy => (self.market_state.ask(), self.market_state.ask_depth()),
+            Side::Sell => (self.market_state.bid(), self.market_state.bid_depth()),
+        };
+        let marketable = |price: QuoteCurrency<I, D>| match side {
+            Side::Buy => price <= limit_price,
+            Side::Sell => price >= limit_price,
+        };
+        if touch_price.is_zero() || !marketable(touch_price) {
+            return BaseOrQuote::zero();
+        }
+        if !self.market_state.has_depth() {
+            return quantity;
+        }
+
+        let available = depth
+            .iter()
+            .take_while(|(price, _)| marketable(*price))
+            .fold(Decimal::zero(), |acc, (_, level_qty)| acc + *level_qty);
+        min(quantity, BaseOrQuote::from(available))
+    }
+
+    /// The current best bid and ask of the market, in that order.
+    pub fn bba(&self) -> (QuoteCurrency<I, D>, QuoteCurrency<I, D>) {
+        (self.market_state.bid(), self.market_state.ask())
+    }
+
+    /// The maker and taker fee of the contract, in that order.
+    pub fn fees(&self) -> (Fee<I, D, Maker>, Fee<I, D, Taker>) {
+        let contract_spec = self.config.contract_spec();
+        (contract_spec.fee_maker(), contract_spec.fee_taker())
+    }
+
+    /// The value of the position marked to market at the current bid and ask,
+    /// being the notional value at the entry price plus the unrealized pnl.
+    /// Is zero for a neutral position.
+    pub fn position_value(&self) -> BaseOrQuote::PairedCurrency {
+        self.position
+            .value(self.market_state.bid(), self.market_state.ask())
+    }
+
+    /// The leverage currently in use, being the notional value of the position at the mid price
+    /// relative to the total equity (all user balances, unswept realized profits
+    /// and the unrealized pnl).
+    /// Is zero for a neutral position.
+    pub fn current_leverage(&self) -> f64 {
+        let quantity = self.position.quantity();
+        if quantity.is_zero() {
+            return 0.0;
+        }
+        let notional = BaseOrQuote::PairedCurrency::convert_from(
+            quantity.abs(),
+            self.market_state.mid_price(),
+        );
+        let total_equity = self.total_equity();
+        Into::<f64>::into(notional) / Into::<f64>::into(total_equity)
+    }
+
+    /// The leverage that would be reached in the worst case of all resting limit orders filling,
+    /// being the notional value of either the position plus all buy orders or the position minus
+    /// all sell orders at the mid price, whichever is larger, relative to the total equity.
+    /// Unlike `current_leverage`, resting orders are included. Is zero without any exposure.
+    pub fn potential_leverage(&self) -> f64 {
+        let (buy_qty, sell_qty) = self.active_limit_orders.values().fold(
+            (BaseOrQuote::zero(), BaseOrQuote::zero()),
+            |(buy_qty, sell_qty), order| match order.side() {
+                Side::Buy => (buy_qty + order.remaining_quantity(), sell_qty),
+                Side::Sell => (buy_qty, sell_qty + order.remaining_quantity()),
+            },
+        );
+        let quantity = self.position.quantity();
+        let worst_case_qty = max((quantity + buy_qty).abs(), (quantity - sell_qty).abs());
+        if worst_case_qty.is_zero() {
+            return 0.0;
+        }
+        let notional = BaseOrQuote::PairedCurrency::convert_from(
+            worst_case_qty,
+            self.market_state.mid_price(),
+        );
+        let total_equity = self.total_equity();
+        Into::<f64>::into(notional) / Into::<f64>::into(total_equity)
+    }
+
+    /// The unrealized pnl of the position at the current bid and ask relative to its
+    /// initial margin, being the leverage amplified return traders see as their position pnl.
+    /// Is zero while the position is neutral.
+    pub fn position_return_pct(&self) -> f64 {
+        if self.position.quantity().is_zero() {
+            return 0.0;
+        }
+        let init_margin =
+            self.position.total_cost() * self.config.contract_spec().init_margin_req();
+        let upnl = self
+            .position
+            .unrealized_pnl(self.market_state.bid(), self.market_state.ask());
+        Into::<f64>::into(upnl) / Into::<f64>::into(init_margin)
+    }
+
+    /// The fractional return of the account, being the total equity (all user balances,
+    /// unswept realized profits and the unrealized pnl) relative to the starting wallet balance.
+    pub fn total_return_pct(&self) -> f64 {
+        let starting_balance = self.config.starting_wallet_balance();
+        Into::<f64>::into(self.total_equity() - starting_balance)
+            / Into::<f64>::into(starting_balance)
+    }
+
+    /// The current drawdown as the fraction by which the total equity is below its
+    /// high-water mark, which is maintained in `update_state`.
+    /// Is zero while the total equity is at its high-water mark.
+    pub fn current_drawdown(&self) -> f64 {
+        let high_water_mark = Into::<f64>::into(self.equity_high_water_mark);
+        if high_water_mark <= 0.0 {
+            return 0.0;
+        }
+        let drawdown = (high_water_mark - Into::<f64>::into(self.total_equity())) / high_water_mark;
+        drawdown.max(0.0)
+    }
+
+    /// The wallet balances, including any unswept and pending realized profit,
+    /// plus the unrealized pnl of the position and any hedge legs at the current bid and ask.
+    fn total_equity(&self) -> BaseOrQuote::PairedCurrency {
+        let (bid, ask) = (self.market_state.bid(), self.market_state.ask());
+        self.user_balances().sum()
+            + self.unswept_realized_pnl()
+            + self.pending_pnl()
+            + self.position.unrealized_pnl(bid, ask)
+            + self.hedge_position.unrealized_pnl(bid, ask)
+    }
+
+    /// The unrealized pnl of the position at the current bid and ask, rounded towards zero
+    /// to the `margin_display_quantum` of the `Config`.
+    /// Without a `margin_display_quantum`, the full precision value is returned.
+    pub fn rounded_unrealized_pnl(&self) -> BaseOrQuote::PairedCurrency {
+        let upnl = self
+            .position
+            .unrealized_pnl(self.market_state.bid(), self.market_state.ask());
+        match self.config.margin_display_quantum() {
+            Some(quantum) => BaseOrQuote::PairedCurrency::from(
+                upnl.as_ref().quantize_round_to_zero(*quantum.as_ref()),
+            ),
+            None => upnl,
+        }
+    }
+
+    /// Format the `UserBalances` like its `Display` implementation,
+    /// but with the `margin_currency_label` of the `ContractSpecification` if one is set.
+    pub fn user_balances_report(&self) -> String {
+        let balances = self.user_balances();
+        let contract_spec = self.config.contract_spec();
+        format!(
+            "available_balance: {}, position_margin: {}, order_margin: {}",
+            contract_spec.format_margin(balances.available_wallet_balance),
+            contract_spec.format_margin(balances.position_margin),
+            contract_spec.format_margin(balances.order_margin),
+        )
+    }
+}
+
+impl<I, const D: u8, UserOrderId, TransactionAccountingT, A>
+    Exchange<I, D, QuoteCurrency<I, D>, UserOrderId, TransactionAccountingT, A>
+where
+    I: Mon<D>,
+    A: AccountTracker<I, D, BaseCurrency<I, D>, UserOrderId>,
+    UserOrderId: UserOrderIdT,
+    TransactionAccountingT: TransactionAccounting<I, D, BaseCurrency<I, D>> + std::fmt::Debug,
+{
+    /// The realized profit and loss of inverse futures, excluding fees, converted from the
+    /// base currency it is denominated in to the quote currency at the `mark` price.
+    /// The sign is unchanged, so a profitable round trip is positive in both currencies.
+    pub fn realized_pnl_in_quote(&self, mark: QuoteCurrency<I, D>) -> QuoteCurrency<I, D> {
+        QuoteCurrency::convert_from(self.total_realized_pnl, mark)
+    }
 }
 
 #[cfg(test)]
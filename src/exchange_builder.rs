@@ -0,0 +1,188 @@
+use std::marker::PhantomData;
+
+use crate::{
+    account_tracker::{AccountTracker, NoAccountTracker},
+    accounting::{InMemoryTransactionAccounting, TransactionAccounting},
+    config::Config,
+    exchange::Exchange,
+    types::{Currency, MarginCurrency, Mon, UserOrderIdT},
+    utils::NoUserOrderId,
+};
+
+/// Constructs an `Exchange` with less boilerplate.
+/// By default it uses `NoUserOrderId`, `InMemoryTransactionAccounting` and `NoAccountTracker`,
+/// each of which can be overridden.
+///
+/// Generics:
+/// - `I`: The numeric data type of currencies.
+/// - `D`: The constant decimal precision of the currencies.
+/// - `BaseOrQuote`: Either `BaseCurrency` or `QuoteCurrency` depending on the futures type.
+/// - `UserOrderId`: The type of user order id to use.
+/// - `TransactionAccountingT`: The `TransactionAccounting` implementation.
+/// - `A`: An `AccountTracker`
+///
+/// # Example:
+/// ```rust
+/// use lfest::prelude::*;
+///
+/// let config = Config::new(
+///     QuoteCurrency::<i64, 5>::new(1000, 0),
+///     10,
+///     ContractSpecification::default(),
+///     3600,
+/// )
+/// .unwrap();
+/// let exchange = ExchangeBuilder::<i64, 5, BaseCurrency<i64, 5>>::new(config).build();
+/// assert_eq!(exchange.position(), &Position::Neutral);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExchangeBuilder<
+    I,
+    const D: u8,
+    BaseOrQuote,
+    UserOrderId = NoUserOrderId,
+    TransactionAccountingT = InMemoryTransactionAccounting<
+        I,
+        D,
+        <BaseOrQuote as Currency<I, D>>::PairedCurrency,
+    >,
+    A = NoAccountTracker,
+> where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    BaseOrQuote::PairedCurrency: MarginCurrency<I, D>,
+    UserOrderId: UserOrderIdT,
+{
+    config: Config<I, D, BaseOrQuote::PairedCurrency>,
+    account_tracker: A,
+    _marker: PhantomData<(BaseOrQuote, UserOrderId, TransactionAccountingT)>,
+}
+
+impl<I, const D: u8, BaseOrQuote> ExchangeBuilder<I, D, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    BaseOrQuote::PairedCurrency: MarginCurrency<I, D>,
+{
+    /// Create a new builder from the `Config`, using the default generics.
+    pub fn new(config: Config<I, D, BaseOrQuote::PairedCurrency>) -> Self {
+        Self {
+            config,
+            account_tracker: NoAccountTracker,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<I, const D: u8, BaseOrQuote, UserOrderId, TransactionAccountingT, A>
+    ExchangeBuilder<I, D, BaseOrQuote, UserOrderId, TransactionAccountingT, A>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    BaseOrQuote::PairedCurrency: MarginCurrency<I, D>,
+    UserOrderId: UserOrderIdT,
+{
+    /// Use a different `AccountTracker`.
+    pub fn with_account_tracker<A2>(
+        self,
+        account_tracker: A2,
+    ) -> ExchangeBuilder<I, D, BaseOrQuote, UserOrderId, TransactionAccountingT, A2> {
+        ExchangeBuilder {
+            config: self.config,
+            account_tracker,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Use a different `TransactionAccounting` implementation.
+    pub fn with_transaction_accounting<T2>(
+        self,
+    ) -> ExchangeBuilder<I, D, BaseOrQuote, UserOrderId, T2, A> {
+        ExchangeBuilder {
+            config: self.config,
+            account_tracker: self.account_tracker,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Use a custom user order id type.
+    pub fn with_user_order_id<U2>(
+        self,
+    ) -> ExchangeBuilder<I, D, BaseOrQuote, U2, TransactionAccountingT, A>
+    where
+        U2: UserOrderIdT,
+    {
+        ExchangeBuilder {
+            config: self.config,
+            account_tracker: self.account_tracker,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Construct the fully typed `Exchange`.
+    pub fn build(self) -> Exchange<I, D, BaseOrQuote, UserOrderId, TransactionAccountingT, A>
+    where
+        A: AccountTracker<I, D, BaseOrQuote::PairedCurrency, UserOrderId>,
+        TransactionAccountingT:
+            TransactionAccounting<I, D, BaseOrQuote::PairedCurrency> + std::fmt::Debug,
+    {
+        Exchange::new(self.account_tracker, self.config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use const_decimal::Decimal;
+
+    use super::*;
+    use crate::{mock_exchange_linear, prelude::*, test_fee_maker, test_fee_taker, DECIMALS};
+
+    #[test]
+    fn exchange_builder_linear() {
+        let contract_spec = ContractSpecification::new(
+            leverage!(1),
+            Decimal::try_from_scaled(5, 1).unwrap(),
+            PriceFilter::default(),
+            QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
+            test_fee_maker(),
+            test_fee_taker(),
+        )
+        .unwrap();
+        let config = Config::new(QuoteCurrency::new(1000, 0), 10, contract_spec, 3600).unwrap();
+        let mut built =
+            ExchangeBuilder::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::new(config).build();
+        let mut explicit = mock_exchange_linear();
+
+        let bba = Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        };
+        built.update_state(&bba).unwrap();
+        explicit.update_state(&bba).unwrap();
+
+        let order = MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap();
+        assert_eq!(
+            built.submit_market_order(order.clone()).unwrap(),
+            explicit.submit_market_order(order).unwrap()
+        );
+        assert_eq!(built.position(), explicit.position());
+        assert_eq!(built.user_balances(), explicit.user_balances());
+        assert_eq!(built.fees_paid(), explicit.fees_paid());
+    }
+
+    #[test]
+    fn exchange_builder_with_account_tracker() {
+        let starting_balance = QuoteCurrency::<i64, DECIMALS>::new(1000, 0);
+        let config =
+            Config::new(starting_balance, 10, ContractSpecification::default(), 3600).unwrap();
+        let exchange = ExchangeBuilder::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::new(config)
+            .with_account_tracker(FullAccountTracker::new(starting_balance))
+            .with_user_order_id::<i64>()
+            .build();
+        assert_eq!(
+            exchange.account_tracker().wallet_balance_start(),
+            starting_balance
+        );
+    }
+}
@@ -0,0 +1,345 @@
+use std::ops::Neg;
+
+use const_decimal::Decimal;
+use num_traits::Zero;
+
+use crate::{
+    position_inner::PositionInner,
+    prelude::{Currency, Mon, QuoteCurrency, TransactionAccounting},
+    types::{Error, MarginCurrency, PnlRealizationPolicy, Result, Side},
+};
+
+/// The leg of a `HedgePosition` a fill is routed to, like the `positionSide` of binance hedge mode.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum HedgeLeg {
+    /// Buys open and sells close the long leg.
+    Long,
+    /// Sells open and buys close the short leg.
+    Short,
+}
+
+impl HedgeLeg {
+    /// Whether a fill of `side` increases the leg, rather than reducing it.
+    pub fn is_increased_by(&self, side: Side) -> bool {
+        matches!(
+            (self, side),
+            (HedgeLeg::Long, Side::Buy) | (HedgeLeg::Short, Side::Sell)
+        )
+    }
+}
+
+/// A hedge-mode position, which holds a long and a short leg simultaneously.
+/// Each leg reserves its own position margin and realizes its own profit and loss,
+/// so the legs never offset each other.
+/// The `Exchange` uses it instead of the one-way `Position` if `Config::hedge_mode` is enabled.
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct HedgePosition<I, const D: u8, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    long: Option<PositionInner<I, D, BaseOrQuote>>,
+    short: Option<PositionInner<I, D, BaseOrQuote>>,
+}
+
+impl<I, const D: u8, BaseOrQuote> HedgePosition<I, D, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    BaseOrQuote::PairedCurrency: MarginCurrency<I, D>,
+{
+    /// The long leg, if any.
+    pub fn long(&self) -> Option<&PositionInner<I, D, BaseOrQuote>> {
+        self.long.as_ref()
+    }
+
+    /// The short leg, if any.
+    pub fn short(&self) -> Option<&PositionInner<I, D, BaseOrQuote>> {
+        self.short.as_ref()
+    }
+
+    /// The given `leg`, if any.
+    pub fn leg(&self, leg: HedgeLeg) -> Option<&PositionInner<I, D, BaseOrQuote>> {
+        match leg {
+            HedgeLeg::Long => self.long(),
+            HedgeLeg::Short => self.short(),
+        }
+    }
+
+    /// The quantity of the long leg minus the quantity of the short leg.
+    pub fn net_quantity(&self) -> BaseOrQuote {
+        let long_qty = self
+            .long
+            .as_ref()
+            .map_or(BaseOrQuote::zero(), |inner| inner.quantity());
+        let short_qty = self
+            .short
+            .as_ref()
+            .map_or(BaseOrQuote::zero(), |inner| inner.quantity());
+        long_qty - short_qty
+    }
+
+    /// The unrealized profit and loss of both legs,
+    /// with the long leg marked at the `bid` and the short leg at the `ask`.
+    pub fn unrealized_pnl(
+        &self,
+        bid: QuoteCurrency<I, D>,
+        ask: QuoteCurrency<I, D>,
+    ) -> BaseOrQuote::PairedCurrency {
+        let long_pnl = self
+            .long
+            .as_ref()
+            .map_or(BaseOrQuote::PairedCurrency::zero(), |inner| {
+                inner.unrealized_pnl(bid)
+            });
+        let short_pnl = self
+            .short
+            .as_ref()
+            .map_or(BaseOrQuote::PairedCurrency::zero(), |inner| {
+                inner.unrealized_pnl(ask).neg()
+            });
+        long_pnl + short_pnl
+    }
+
+    /// Change the `leg` while doing proper accounting and balance transfers.
+    /// A fill in the direction of the leg increases it and a fill against it reduces it,
+    /// which can never flip the leg to the other side.
+    ///
+    /// # Returns:
+    /// `Error::NoPositionToReduce` if the fill reduces a leg that is not open,
+    /// `Error::HedgeLegQuantityExceeded` if it reduces more than the leg holds.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn change_position<Acc>(
+        &mut self,
+        filled_qty: BaseOrQuote,
+        fill_price: QuoteCurrency<I, D>,
+        side: Side,
+        leg: HedgeLeg,
+        transaction_accounting: &mut Acc,
+        init_margin_req: Decimal<I, D>,
+        fees: BaseOrQuote::PairedCurrency,
+        pnl_realization_policy: PnlRealizationPolicy,
+        credit_maker_rebates: bool,
+    ) -> Result<()>
+    where
+        Acc: TransactionAccounting<I, D, BaseOrQuote::PairedCurrency>,
+    {
+        assert2::debug_assert!(
+            filled_qty > BaseOrQuote::zero(),
+            "The filled_qty must be greater than zero"
+        );
+        let (position, direction_multiplier) = match leg {
+            HedgeLeg::Long => (&mut self.long, 1),
+            HedgeLeg::Short => (&mut self.short, -1),
+        };
+        if leg.is_increased_by(side) {
+            match position {
+                Some(inner) => inner.increase_contracts(
+                    filled_qty,
+                    fill_price,
+                    transaction_accounting,
+                    init_margin_req,
+                    fees,
+                ),
+                None => {
+                    *position = Some(PositionInner::new(
+                        filled_qty,
+                        fill_price,
+                        transaction_accounting,
+                        init_margin_req,
+                        fees,
+                    ))
+                }
+            }
+            return Ok(());
+        }
+
+        let inner = position.as_mut().ok_or(Error::NoPositionToReduce)?;
+        if filled_qty > inner.quantity() {
+            return Err(Error::HedgeLegQuantityExceeded);
+        }
+        inner.decrease_contracts(
+            filled_qty,
+            fill_price,
+            transaction_accounting,
+            init_margin_req,
+            direction_multiplier,
+            fees,
+            pnl_realization_policy,
+            credit_maker_rebates,
+        );
+        if inner.quantity().is_zero() {
+            *position = None;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, DECIMALS};
+
+    #[test]
+    #[tracing_test::traced_test]
+    fn hedge_position_open_both_legs_and_close_each() {
+        let mut accounting =
+            InMemoryTransactionAccounting::new(QuoteCurrency::<i64, DECIMALS>::new(1000, 0));
+        let mut position = HedgePosition::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::default();
+        let init_margin_req = Decimal::one();
+        let policy = PnlRealizationPolicy::default();
+        let balance_of = |accounting: &InMemoryTransactionAccounting<_, DECIMALS, _>, account| {
+            accounting.margin_balance_of(account).unwrap()
+        };
+
+        // Both legs reserve their own margin.
+        position
+            .change_position(
+                BaseCurrency::new(2, 0),
+                QuoteCurrency::new(100, 0),
+                Side::Buy,
+                HedgeLeg::Long,
+                &mut accounting,
+                init_margin_req,
+                QuoteCurrency::zero(),
+                policy,
+                false,
+            )
+            .unwrap();
+        position
+            .change_position(
+                BaseCurrency::new(1, 0),
+                QuoteCurrency::new(100, 0),
+                Side::Sell,
+                HedgeLeg::Short,
+                &mut accounting,
+                init_margin_req,
+                QuoteCurrency::zero(),
+                policy,
+                false,
+            )
+            .unwrap();
+        assert_eq!(position.long().unwrap().quantity(), BaseCurrency::new(2, 0));
+        assert_eq!(
+            position.short().unwrap().quantity(),
+            BaseCurrency::new(1, 0)
+        );
+        assert_eq!(position.net_quantity(), BaseCurrency::new(1, 0));
+        assert_eq!(
+            balance_of(&accounting, USER_POSITION_MARGIN_ACCOUNT),
+            QuoteCurrency::new(300, 0)
+        );
+        assert_eq!(
+            balance_of(&accounting, USER_WALLET_ACCOUNT),
+            QuoteCurrency::new(700, 0)
+        );
+        assert_eq!(
+            position.unrealized_pnl(QuoteCurrency::new(110, 0), QuoteCurrency::new(110, 0)),
+            QuoteCurrency::new(10, 0)
+        );
+
+        // Closing the long leg with a profit of 20 keeps the short leg.
+        position
+            .change_position(
+                BaseCurrency::new(2, 0),
+                QuoteCurrency::new(110, 0),
+                Side::Sell,
+                HedgeLeg::Long,
+                &mut accounting,
+                init_margin_req,
+                QuoteCurrency::zero(),
+                policy,
+                false,
+            )
+            .unwrap();
+        assert!(position.long().is_none());
+        assert_eq!(
+            position.short().unwrap().quantity(),
+            BaseCurrency::new(1, 0)
+        );
+        assert_eq!(
+            balance_of(&accounting, USER_POSITION_MARGIN_ACCOUNT),
+            QuoteCurrency::new(100, 0)
+        );
+        assert_eq!(
+            balance_of(&accounting, USER_WALLET_ACCOUNT),
+            QuoteCurrency::new(920, 0)
+        );
+
+        // Closing the short leg with a loss of 10.
+        position
+            .change_position(
+                BaseCurrency::new(1, 0),
+                QuoteCurrency::new(110, 0),
+                Side::Buy,
+                HedgeLeg::Short,
+                &mut accounting,
+                init_margin_req,
+                QuoteCurrency::zero(),
+                policy,
+                false,
+            )
+            .unwrap();
+        assert_eq!(position, HedgePosition::default());
+        assert_eq!(
+            balance_of(&accounting, USER_POSITION_MARGIN_ACCOUNT),
+            QuoteCurrency::zero()
+        );
+        assert_eq!(
+            balance_of(&accounting, USER_WALLET_ACCOUNT),
+            QuoteCurrency::new(1010, 0)
+        );
+    }
+
+    #[test]
+    fn hedge_position_reduce_errors() {
+        let mut accounting =
+            InMemoryTransactionAccounting::new(QuoteCurrency::<i64, DECIMALS>::new(1000, 0));
+        let mut position = HedgePosition::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::default();
+        let init_margin_req = Decimal::one();
+        let policy = PnlRealizationPolicy::default();
+
+        assert_eq!(
+            position.change_position(
+                BaseCurrency::new(1, 0),
+                QuoteCurrency::new(100, 0),
+                Side::Buy,
+                HedgeLeg::Short,
+                &mut accounting,
+                init_margin_req,
+                QuoteCurrency::zero(),
+                policy,
+                false,
+            ),
+            Err(Error::NoPositionToReduce)
+        );
+        position
+            .change_position(
+                BaseCurrency::new(1, 0),
+                QuoteCurrency::new(100, 0),
+                Side::Buy,
+                HedgeLeg::Long,
+                &mut accounting,
+                init_margin_req,
+                QuoteCurrency::zero(),
+                policy,
+                false,
+            )
+            .unwrap();
+        assert_eq!(
+            position.change_position(
+                BaseCurrency::new(2, 0),
+                QuoteCurrency::new(100, 0),
+                Side::Sell,
+                HedgeLeg::Long,
+                &mut accounting,
+                init_margin_req,
+                QuoteCurrency::zero(),
+                policy,
+                false,
+            ),
+            Err(Error::HedgeLegQuantityExceeded)
+        );
+        assert_eq!(position.long().unwrap().quantity(), BaseCurrency::new(1, 0));
+    }
+}
@@ -13,6 +13,8 @@ mod active_limit_orders;
 mod config;
 mod contract_specification;
 mod exchange;
+mod exchange_builder;
+mod hedge_position;
 mod load_trades_from_csv;
 mod market_state;
 mod market_update;
@@ -40,12 +42,14 @@ pub mod prelude {
     pub use num_traits::{One, Zero};
 
     pub use crate::{
-        account_tracker::{AccountTracker, FullAccountTracker, NoAccountTracker},
+        account_tracker::{AccountTracker, FullAccountTracker, NoAccountTracker, ReturnsSource},
         accounting::*,
         active_limit_orders::ActiveLimitOrders,
         config::Config,
         contract_specification::*,
         exchange::{Account, CancelBy, Exchange},
+        exchange_builder::ExchangeBuilder,
+        hedge_position::{HedgeLeg, HedgePosition},
         leverage,
         market_state::MarketState,
         market_update::*,
@@ -27,9 +27,27 @@ where
     #[getset(get_copy = "pub")]
     current_ts_ns: TimestampNs,
 
+    /// The timestamp in nanoseconds of the first market update.
+    #[getset(get_copy = "pub")]
+    first_ts_ns: TimestampNs,
+
     /// Used for synchronizing orders.
     #[getset(get_copy = "pub")]
     step: u64,
+
+    /// The bid levels of price and quantity of the most recent `Depth` update, best price first.
+    /// Every other market update clears them.
+    #[getset(get = "pub")]
+    bid_depth: Vec<(QuoteCurrency<I, D>, Decimal<I, D>)>,
+
+    /// The ask levels of price and quantity of the most recent `Depth` update, best price first.
+    /// Every other market update clears them.
+    #[getset(get = "pub")]
+    ask_depth: Vec<(QuoteCurrency<I, D>, Decimal<I, D>)>,
+
+    /// Whether the most recent market update was a `Depth` update.
+    #[getset(get_copy = "pub")]
+    has_depth: bool,
 }
 
 impl<I: Mon<D>, const D: u8> std::fmt::Display for MarketState<I, D> {
@@ -64,20 +82,49 @@ where
     {
         // Only in debug mode do we care to validate the market update, because usually the update comes from an exchange source.
         debug_assert!(market_update.validate_market_update(price_filter).is_ok());
+        self.bid_depth.clear();
+        self.ask_depth.clear();
+        self.has_depth = false;
         market_update.update_market_state(self);
 
         self.current_ts_ns = market_update.timestamp_exchange_ns();
+        if self.step == 0 {
+            self.first_ts_ns = self.current_ts_ns;
+        }
         self.step += 1;
 
         Ok(())
     }
 
+    /// Replace the order book depth with the levels of a `Depth` update.
+    pub(crate) fn set_depth<BaseOrQuote>(
+        &mut self,
+        bids: &[(QuoteCurrency<I, D>, BaseOrQuote)],
+        asks: &[(QuoteCurrency<I, D>, BaseOrQuote)],
+    ) where
+        BaseOrQuote: Currency<I, D>,
+    {
+        self.bid_depth.clear();
+        self.bid_depth
+            .extend(bids.iter().map(|(price, qty)| (*price, *qty.as_ref())));
+        self.ask_depth.clear();
+        self.ask_depth
+            .extend(asks.iter().map(|(price, qty)| (*price, *qty.as_ref())));
+        self.has_depth = true;
+    }
+
     /// Get the mid price
     #[inline(always)]
     pub fn mid_price(&self) -> QuoteCurrency<I, D> {
         (self.bid + self.ask) / Decimal::TWO
     }
 
+    /// The number of market updates processed so far.
+    #[inline(always)]
+    pub fn num_updates(&self) -> u64 {
+        self.step
+    }
+
     /// Get the last observed timestamp in nanoseconts
     #[inline(always)]
     pub fn current_timestamp_ns(&self) -> TimestampNs {
@@ -95,7 +142,11 @@ where
             bid,
             ask,
             current_ts_ns,
+            first_ts_ns: current_ts_ns,
             step,
+            bid_depth: Vec::new(),
+            ask_depth: Vec::new(),
+            has_depth: false,
         }
     }
 }
@@ -0,0 +1,169 @@
+use super::{Bba, MarketUpdate, Trade};
+use crate::{
+    prelude::{Currency, LimitOrder, MarketState, Mon, Pending, PriceFilter, QuoteCurrency, Side},
+    types::{TimestampNs, UserOrderIdT},
+    utils::min,
+    Result,
+};
+
+/// A best bid and ask update together with the taker `Trade`s that occurred in the same tick,
+/// so the quotes and limit order fills advance atomically within one `update_state` call.
+/// The `trades` are applied in order.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct BbaWithTrades<'a, I, const D: u8, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    /// The new best bid and ask.
+    pub bba: Bba<I, D>,
+    /// The trades that occurred alongside the `bba` update.
+    pub trades: &'a [Trade<I, D, BaseOrQuote>],
+}
+
+impl<I, const D: u8, BaseOrQuote> std::fmt::Display for BbaWithTrades<'_, I, D, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}, num_trades: {}", self.bba, self.trades.len())
+    }
+}
+
+impl<I, const D: u8, BaseOrQuote> MarketUpdate<I, D, BaseOrQuote>
+    for BbaWithTrades<'_, I, D, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    const CAN_FILL_LIMIT_ORDERS: bool = true;
+
+    fn limit_order_filled<UserOrderId: UserOrderIdT>(
+        &self,
+        limit_order: &LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>,
+    ) -> Option<BaseOrQuote> {
+        let mut filled_qty = BaseOrQuote::zero();
+        for trade in self.trades {
+            if let Some(qty) = trade.limit_order_filled(limit_order) {
+                filled_qty += qty;
+            }
+        }
+        if filled_qty.is_zero() {
+            return None;
+        }
+        // Multiple trades may execute against the order, but never more than what remains.
+        Some(min(filled_qty, limit_order.remaining_quantity()))
+    }
+
+    fn traded_quantity_at_price(
+        &self,
+        price: QuoteCurrency<I, D>,
+        maker_side: Side,
+    ) -> Option<BaseOrQuote> {
+        let traded_qty = self
+            .trades
+            .iter()
+            .filter_map(|trade| trade.traded_quantity_at_price(price, maker_side))
+            .fold(BaseOrQuote::zero(), |acc, qty| acc + qty);
+        (!traded_qty.is_zero()).then_some(traded_qty)
+    }
+
+    fn validate_market_update(&self, price_filter: &PriceFilter<I, D>) -> Result<()> {
+        <Bba<I, D> as MarketUpdate<I, D, BaseOrQuote>>::validate_market_update(
+            &self.bba,
+            price_filter,
+        )?;
+        for trade in self.trades {
+            trade.validate_market_update(price_filter)?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn update_market_state(&self, market_state: &mut MarketState<I, D>) {
+        <Bba<I, D> as MarketUpdate<I, D, BaseOrQuote>>::update_market_state(
+            &self.bba,
+            market_state,
+        );
+    }
+
+    #[inline(always)]
+    fn timestamp_exchange_ns(&self) -> TimestampNs {
+        self.bba.timestamp_exchange_ns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn bba_with_trades_limit_order_filled() {
+        let bba = Bba {
+            bid: QuoteCurrency::<i64, 4>::new(99, 0),
+            ask: QuoteCurrency::new(100, 0),
+            timestamp_exchange_ns: 0.into(),
+        };
+        let trades = [
+            Trade {
+                price: QuoteCurrency::new(99, 0),
+                quantity: BaseCurrency::new(1, 0),
+                side: Side::Sell,
+                timestamp_exchange_ns: 0.into(),
+            },
+            // Does not trade through the limit price.
+            Trade {
+                price: QuoteCurrency::new(100, 0),
+                quantity: BaseCurrency::new(5, 0),
+                side: Side::Sell,
+                timestamp_exchange_ns: 0.into(),
+            },
+            Trade {
+                price: QuoteCurrency::new(98, 0),
+                quantity: BaseCurrency::new(2, 0),
+                side: Side::Sell,
+                timestamp_exchange_ns: 0.into(),
+            },
+        ];
+        let update = BbaWithTrades {
+            bba,
+            trades: &trades,
+        };
+
+        let meta = ExchangeOrderMeta::new(0.into(), 0.into());
+        let order = LimitOrder::new(
+            Side::Buy,
+            QuoteCurrency::new(100, 0),
+            BaseCurrency::new(5, 0),
+        )
+        .unwrap()
+        .into_pending(meta.clone());
+        assert_eq!(
+            update.limit_order_filled(&order),
+            Some(BaseCurrency::new(3, 0))
+        );
+
+        let order = LimitOrder::new(
+            Side::Buy,
+            QuoteCurrency::new(100, 0),
+            BaseCurrency::new(2, 0),
+        )
+        .unwrap()
+        .into_pending(meta.clone());
+        assert_eq!(
+            update.limit_order_filled(&order),
+            Some(BaseCurrency::new(2, 0))
+        );
+
+        let order = LimitOrder::new(
+            Side::Sell,
+            QuoteCurrency::new(101, 0),
+            BaseCurrency::new(2, 0),
+        )
+        .unwrap()
+        .into_pending(meta);
+        assert_eq!(update.limit_order_filled(&order), None);
+    }
+}
@@ -26,6 +26,10 @@ where
     pub high: QuoteCurrency<I, D>,
     /// The nanosecond timestamp at which this event occurred at the exchange.
     pub timestamp_exchange_ns: TimestampNs,
+    /// If `true`, a candle merely touching the limit price
+    /// (e.g. `low == limit_price` for a buy order) fills the order.
+    /// Otherwise the candle must trade through the limit price.
+    pub fill_on_touch: bool,
 }
 
 impl<I, const D: u8> std::fmt::Display for Candle<I, D>
@@ -56,10 +60,13 @@ where
         debug_assert!(order.remaining_quantity() > BaseOrQuote::zero());
 
         // As a simplifying assumption, the order always get executed fully when using candles if the price is right.
-        if match order.side() {
-            Side::Buy => self.low < order.limit_price(),
-            Side::Sell => self.high > order.limit_price(),
-        } {
+        let filled = match (order.side(), self.fill_on_touch) {
+            (Side::Buy, false) => self.low < order.limit_price(),
+            (Side::Buy, true) => self.low <= order.limit_price(),
+            (Side::Sell, false) => self.high > order.limit_price(),
+            (Side::Sell, true) => self.high >= order.limit_price(),
+        };
+        if filled {
             // Order is executed fully with candles.
             Some(match order.side() {
                 Side::Buy => order.remaining_quantity(),
@@ -109,6 +116,62 @@ macro_rules! candle {
             ask: $a,
             low: $l,
             high: $h,
+            fill_on_touch: false,
         }
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    fn candle(fill_on_touch: bool) -> Candle<i64, 2> {
+        Candle {
+            bid: QuoteCurrency::new(105, 0),
+            ask: QuoteCurrency::new(106, 0),
+            low: QuoteCurrency::new(100, 0),
+            high: QuoteCurrency::new(110, 0),
+            timestamp_exchange_ns: 0.into(),
+            fill_on_touch,
+        }
+    }
+
+    #[test_case::test_case(Side::Buy, 100)]
+    #[test_case::test_case(Side::Sell, 110)]
+    fn candle_touch_fill_allowed(side: Side, limit_price: i64) {
+        let limit_price = QuoteCurrency::new(limit_price, 0);
+        let order = LimitOrder::new(side, limit_price, BaseCurrency::new(1, 0)).unwrap();
+        let meta = ExchangeOrderMeta::new(0.into(), 0.into());
+        let order = order.into_pending(meta);
+        assert_eq!(
+            candle(true).limit_order_filled(&order),
+            Some(BaseCurrency::new(1, 0))
+        );
+    }
+
+    #[test_case::test_case(Side::Buy, 100)]
+    #[test_case::test_case(Side::Sell, 110)]
+    fn candle_touch_fill_disallowed(side: Side, limit_price: i64) {
+        let limit_price = QuoteCurrency::new(limit_price, 0);
+        let order = LimitOrder::new(side, limit_price, BaseCurrency::new(1, 0)).unwrap();
+        let meta = ExchangeOrderMeta::new(0.into(), 0.into());
+        let order = order.into_pending(meta);
+        assert_eq!(candle(false).limit_order_filled(&order), None);
+    }
+
+    #[test_case::test_case(Side::Buy, 101)]
+    #[test_case::test_case(Side::Sell, 109)]
+    fn candle_trade_through_fills(side: Side, limit_price: i64) {
+        let limit_price = QuoteCurrency::new(limit_price, 0);
+        let order = LimitOrder::new(side, limit_price, BaseCurrency::new(1, 0)).unwrap();
+        let meta = ExchangeOrderMeta::new(0.into(), 0.into());
+        let order = order.into_pending(meta);
+        for fill_on_touch in [false, true] {
+            assert_eq!(
+                candle(fill_on_touch).limit_order_filled(&order),
+                Some(BaseCurrency::new(1, 0))
+            );
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use super::MarketUpdate;
+use crate::{
+    order_filters::{
+        enforce_bid_ask_spread, enforce_max_price, enforce_min_price, enforce_step_size,
+    },
+    prelude::{Currency, LimitOrder, MarketState, Mon, Pending, PriceFilter, QuoteCurrency},
+    types::{TimestampNs, UserOrderIdT},
+    Result,
+};
+
+/// A snapshot of the order book with the resting quantity at each price level.
+/// The best bid and ask are taken from the first levels.
+/// If enabled in the `Config`, market orders walk these levels until the next market update,
+/// instead of filling entirely at the touch price.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Depth<I, const D: u8, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    /// The bid levels of price and quantity, best (highest) price first.
+    pub bids: Vec<(QuoteCurrency<I, D>, BaseOrQuote)>,
+    /// The ask levels of price and quantity, best (lowest) price first.
+    pub asks: Vec<(QuoteCurrency<I, D>, BaseOrQuote)>,
+    /// The nanosecond timestamp at which this event occurred at the exchange.
+    pub timestamp_exchange_ns: TimestampNs,
+}
+
+impl<I, const D: u8, BaseOrQuote> std::fmt::Display for Depth<I, D, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Depth with {} bid levels and {} ask levels",
+            self.bids.len(),
+            self.asks.len()
+        )
+    }
+}
+
+impl<I, const D: u8, BaseOrQuote> MarketUpdate<I, D, BaseOrQuote> for Depth<I, D, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    const CAN_FILL_LIMIT_ORDERS: bool = false;
+
+    #[inline(always)]
+    fn limit_order_filled<UserOrderId: UserOrderIdT>(
+        &self,
+        _limit_order: &LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>,
+    ) -> Option<BaseOrQuote> {
+        unreachable!(
+            "This should never be called, because a depth update can never fill a limit order."
+        );
+    }
+
+    fn validate_market_update(&self, price_filter: &PriceFilter<I, D>) -> Result<()> {
+        for (price, _) in self.bids.iter().chain(self.asks.iter()) {
+            enforce_min_price(price_filter.min_price(), *price)?;
+            enforce_max_price(price_filter.max_price(), *price)?;
+            enforce_step_size(price_filter.tick_size(), *price)?;
+        }
+        if let (Some(bid), Some(ask)) = (self.bids.first(), self.asks.first()) {
+            enforce_bid_ask_spread(bid.0, ask.0)?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn update_market_state(&self, market_state: &mut MarketState<I, D>) {
+        if let Some((bid, _)) = self.bids.first() {
+            market_state.set_bid(*bid);
+        }
+        if let Some((ask, _)) = self.asks.first() {
+            market_state.set_ask(*ask);
+        }
+        market_state.set_depth(&self.bids, &self.asks);
+    }
+
+    #[inline(always)]
+    fn timestamp_exchange_ns(&self) -> TimestampNs {
+        self.timestamp_exchange_ns
+    }
+}
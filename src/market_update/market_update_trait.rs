@@ -1,5 +1,5 @@
 use crate::{
-    prelude::{Currency, LimitOrder, MarketState, Mon, Pending, PriceFilter},
+    prelude::{Currency, LimitOrder, MarketState, Mon, Pending, PriceFilter, QuoteCurrency, Side},
     types::{TimestampNs, UserOrderIdT},
     Result,
 };
@@ -20,6 +20,17 @@ where
         limit_order: &LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>,
     ) -> Option<BaseOrQuote>;
 
+    /// The quantity traded exactly at `price` against resting orders of side `maker_side`, if any.
+    /// Used by the queue position model, as the strict model does not fill orders at their limit price.
+    #[inline(always)]
+    fn traded_quantity_at_price(
+        &self,
+        _price: QuoteCurrency<I, D>,
+        _maker_side: Side,
+    ) -> Option<BaseOrQuote> {
+        None
+    }
+
     /// Checks if the market update satisfies the `PriceFilter`.
     fn validate_market_update(&self, price_filter: &PriceFilter<I, D>) -> Result<()>;
 
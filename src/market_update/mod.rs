@@ -1,11 +1,15 @@
 mod bba_update;
+mod bba_with_trades;
 mod candle_update;
+mod depth_update;
 mod market_update_trait;
 mod smart_candle;
 mod trade_update;
 
 pub use bba_update::Bba;
+pub use bba_with_trades::BbaWithTrades;
 pub use candle_update::Candle;
+pub use depth_update::Depth;
 pub use market_update_trait::MarketUpdate;
 pub use smart_candle::SmartCandle;
 pub use trade_update::Trade;
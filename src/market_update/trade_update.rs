@@ -72,6 +72,15 @@ where
         }
     }
 
+    #[inline]
+    fn traded_quantity_at_price(
+        &self,
+        price: QuoteCurrency<I, D>,
+        maker_side: Side,
+    ) -> Option<BaseOrQuote> {
+        (self.price == price && self.side == maker_side.inverted()).then_some(self.quantity)
+    }
+
     fn validate_market_update(&self, price_filter: &PriceFilter<I, D>) -> Result<()> {
         enforce_min_price(price_filter.min_price(), self.price)?;
         enforce_max_price(price_filter.max_price(), self.price)?;
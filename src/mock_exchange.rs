@@ -31,6 +31,21 @@ pub fn mock_exchange_linear() -> Exchange<
     NoUserOrderId,
     InMemoryTransactionAccounting<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>,
     NoAccountTracker,
+> {
+    mock_exchange_linear_with_config(|_| {})
+}
+
+/// Constructs a mock exchange (for linear futures) for testing,
+/// like `mock_exchange_linear` but with its `Config` adjusted by `configure`.
+pub fn mock_exchange_linear_with_config(
+    configure: impl FnOnce(&mut Config<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>),
+) -> Exchange<
+    i64,
+    DECIMALS,
+    BaseCurrency<i64, DECIMALS>,
+    NoUserOrderId,
+    InMemoryTransactionAccounting<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>,
+    NoAccountTracker,
 > {
     let acc_tracker = NoAccountTracker;
     let contract_spec = ContractSpecification::new(
@@ -42,7 +57,8 @@ pub fn mock_exchange_linear() -> Exchange<
         test_fee_taker(),
     )
     .expect("works");
-    let config = Config::new(QuoteCurrency::new(1000, 0), 10, contract_spec, 3600).unwrap();
+    let mut config = Config::new(QuoteCurrency::new(1000, 0), 10, contract_spec, 3600).unwrap();
+    configure(&mut config);
     Exchange::new(acc_tracker, config)
 }
 
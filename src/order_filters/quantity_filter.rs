@@ -25,6 +25,11 @@ where
     #[getset(get_copy = "pub")]
     tick_size: BaseOrQuote,
 
+    /// If true, the `min_quantity`, `max_quantity` and `tick_size` are interpreted
+    /// in terms of the notional value of an order, e.g. in `Quote` for linear futures.
+    #[getset(get_copy = "pub")]
+    notional_mode: bool,
+
     _quote: std::marker::PhantomData<QuoteCurrency<I, D>>,
 }
 
@@ -38,6 +43,7 @@ where
             min_quantity: None,
             max_quantity: None,
             tick_size: BaseOrQuote::one(),
+            notional_mode: false,
             _quote: std::marker::PhantomData,
         }
     }
@@ -68,10 +74,58 @@ where
             min_quantity,
             max_quantity,
             tick_size,
+            notional_mode: false,
             _quote: std::marker::PhantomData,
         })
     }
 
+    /// Interpret the `min_quantity`, `max_quantity` and `tick_size` in terms of the notional value
+    /// of an order, which is converted from its quantity at a reference price during validation.
+    pub fn with_notional_mode(mut self) -> Self {
+        self.notional_mode = true;
+        self
+    }
+
+    /// Validate the order `quantity`, which in `notional_mode` is converted
+    /// into its notional value at the `reference_price` first.
+    pub(crate) fn validate_order_quantity_at(
+        &self,
+        quantity: BaseOrQuote,
+        reference_price: QuoteCurrency<I, D>,
+    ) -> std::result::Result<(), OrderError> {
+        if !self.notional_mode {
+            return self.validate_order_quantity(quantity);
+        }
+        let notional = BaseOrQuote::PairedCurrency::convert_from(quantity, reference_price);
+        self.validate_order_quantity(BaseOrQuote::from(*notional.as_ref()))
+    }
+
+    /// The `tick_size` as an order quantity, which in `notional_mode` is converted
+    /// from its notional value at the `reference_price`.
+    pub(crate) fn tick_size_at(&self, reference_price: QuoteCurrency<I, D>) -> BaseOrQuote {
+        self.quantity_at(self.tick_size, reference_price)
+    }
+
+    /// The `min_quantity` as an order quantity, which in `notional_mode` is converted
+    /// from its notional value at the `reference_price`.
+    pub(crate) fn min_quantity_at(
+        &self,
+        reference_price: QuoteCurrency<I, D>,
+    ) -> Option<BaseOrQuote> {
+        self.min_quantity
+            .map(|min_qty| self.quantity_at(min_qty, reference_price))
+    }
+
+    fn quantity_at(&self, value: BaseOrQuote, reference_price: QuoteCurrency<I, D>) -> BaseOrQuote {
+        if !self.notional_mode {
+            return value;
+        }
+        BaseOrQuote::convert_from(
+            BaseOrQuote::PairedCurrency::from(*value.as_ref()),
+            reference_price,
+        )
+    }
+
     pub(crate) fn validate_order_quantity(
         &self,
         quantity: BaseOrQuote,
@@ -115,6 +169,7 @@ mod tests {
             min_quantity: Some(QuoteCurrency::<i32, 2>::new(10, 0)),
             max_quantity: Some(QuoteCurrency::new(1000, 0)),
             tick_size: QuoteCurrency::one(),
+            notional_mode: false,
             _quote: std::marker::PhantomData,
         };
 
@@ -149,6 +204,7 @@ mod tests {
             min_quantity: None,
             max_quantity: None,
             tick_size: QuoteCurrency::one(),
+            notional_mode: false,
             _quote: std::marker::PhantomData::<QuoteCurrency<i32, 2>>::default(),
         };
         assert_eq!(
@@ -160,4 +216,64 @@ mod tests {
             Err(OrderError::InvalidQuantityStepSize)
         );
     }
+
+    #[test]
+    fn quantity_filter_notional_mode() {
+        let filter = QuantityFilter::new(
+            Some(BaseCurrency::<i64, 5>::new(10, 0)),
+            Some(BaseCurrency::new(1000, 0)),
+            BaseCurrency::one(),
+        )
+        .unwrap()
+        .with_notional_mode();
+        assert!(filter.notional_mode());
+        let price = QuoteCurrency::new(100, 0);
+
+        // The bounds are in units of `Quote`, so a quantity of 0.5 is worth 50.
+        filter
+            .validate_order_quantity_at(BaseCurrency::new(5, 1), price)
+            .unwrap();
+        assert_eq!(
+            filter.validate_order_quantity_at(BaseCurrency::new(5, 2), price),
+            Err(OrderError::QuantityTooLow)
+        );
+        assert_eq!(
+            filter.validate_order_quantity_at(BaseCurrency::new(20, 0), price),
+            Err(OrderError::QuantityTooHigh)
+        );
+        assert_eq!(
+            filter.validate_order_quantity_at(BaseCurrency::new(505, 3), price),
+            Err(OrderError::InvalidQuantityStepSize)
+        );
+
+        // A notional of 50 passes, no matter the quantity it converts to.
+        let order = LimitOrder::from_notional(Side::Buy, price, QuoteCurrency::new(50, 0), &filter)
+            .unwrap();
+        assert_eq!(order.remaining_quantity(), BaseCurrency::new(5, 1));
+        assert_eq!(
+            LimitOrder::from_notional(Side::Buy, price, QuoteCurrency::new(5, 0), &filter),
+            Err(OrderError::QuantityTooLow)
+        );
+
+        // The notional tick of 1 and minimum of 10 are worth 0.01 and 0.1 at a price of 100.
+        assert_eq!(filter.tick_size_at(price), BaseCurrency::new(1, 2));
+        assert_eq!(filter.min_quantity_at(price), Some(BaseCurrency::new(1, 1)));
+
+        // Without the notional mode, the same quantity is too low.
+        let filter = QuantityFilter::new(
+            Some(BaseCurrency::<i64, 5>::new(10, 0)),
+            Some(BaseCurrency::new(1000, 0)),
+            BaseCurrency::one(),
+        )
+        .unwrap();
+        assert_eq!(
+            filter.validate_order_quantity_at(BaseCurrency::new(5, 1), price),
+            Err(OrderError::QuantityTooLow)
+        );
+        assert_eq!(filter.tick_size_at(price), BaseCurrency::one());
+        assert_eq!(
+            filter.min_quantity_at(price),
+            Some(BaseCurrency::new(10, 0))
+        );
+    }
 }
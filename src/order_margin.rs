@@ -59,6 +59,22 @@ where
         Ok(())
     }
 
+    /// Replace the tracked copy of an order whose `QueuePosition` changed while its quantity did not.
+    pub(crate) fn update_queue_position(
+        &mut self,
+        order: &LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>,
+    ) {
+        let active_order = self
+            .active_limit_orders
+            .insert(order.clone())
+            .expect("Its an internal method call; it must work")
+            .expect("The order must be tracked");
+        debug_assert_eq!(
+            active_order.remaining_quantity(),
+            order.remaining_quantity()
+        );
+    }
+
     /// Remove an order from being tracked for margin purposes.
     pub(crate) fn remove(&mut self, by: CancelBy<UserOrderId>) {
         match by {
@@ -639,4 +655,120 @@ mod tests {
             QuoteCurrency::new(220, 0)
         );
     }
+
+    #[test_case::test_matrix([1, 2, 5])]
+    #[tracing_test::traced_test]
+    fn order_margin_long_partially_offset_by_sell_orders(leverage: u8) {
+        let mut accounting =
+            InMemoryTransactionAccounting::new(QuoteCurrency::<i64, DECIMALS>::new(1000, 0));
+        let mut order_margin = OrderMargin::<_, DECIMALS, _, NoUserOrderId>::new(10);
+        let init_margin_req = Leverage::new(leverage).unwrap().init_margin_req();
+
+        let position = Position::Long(PositionInner::new(
+            BaseCurrency::new(2, 0),
+            QuoteCurrency::new(100, 0),
+            &mut accounting,
+            init_margin_req,
+            QuoteCurrency::zero(),
+        ));
+
+        // A sell order equal to the position only reduces risk.
+        let order = LimitOrder::new(
+            Side::Sell,
+            QuoteCurrency::new(110, 0),
+            BaseCurrency::new(2, 0),
+        )
+        .unwrap()
+        .into_pending(ExchangeOrderMeta::new(0.into(), 0.into()));
+        order_margin.update(&order).unwrap();
+        assert_eq!(
+            order_margin.order_margin(init_margin_req, &position),
+            QuoteCurrency::zero()
+        );
+        order_margin.remove(CancelBy::OrderId(0.into()));
+
+        // The lowest sell order is offset first, the excess of the next one requires margin.
+        let order = LimitOrder::new(
+            Side::Sell,
+            QuoteCurrency::new(110, 0),
+            BaseCurrency::new(1, 0),
+        )
+        .unwrap()
+        .into_pending(ExchangeOrderMeta::new(1.into(), 0.into()));
+        order_margin.update(&order).unwrap();
+        assert_eq!(
+            order_margin.order_margin(init_margin_req, &position),
+            QuoteCurrency::zero()
+        );
+        let order = LimitOrder::new(
+            Side::Sell,
+            QuoteCurrency::new(120, 0),
+            BaseCurrency::new(3, 0),
+        )
+        .unwrap()
+        .into_pending(ExchangeOrderMeta::new(2.into(), 0.into()));
+        order_margin.update(&order).unwrap();
+        assert_eq!(
+            order_margin.order_margin(init_margin_req, &position),
+            QuoteCurrency::new(240, 0) * init_margin_req
+        );
+    }
+
+    #[test_case::test_matrix([1, 2, 5])]
+    #[tracing_test::traced_test]
+    fn order_margin_short_partially_offset_by_buy_orders(leverage: u8) {
+        let mut accounting =
+            InMemoryTransactionAccounting::new(QuoteCurrency::<i64, DECIMALS>::new(1000, 0));
+        let mut order_margin = OrderMargin::<_, DECIMALS, _, NoUserOrderId>::new(10);
+        let init_margin_req = Leverage::new(leverage).unwrap().init_margin_req();
+
+        let position = Position::Short(PositionInner::new(
+            BaseCurrency::new(2, 0),
+            QuoteCurrency::new(100, 0),
+            &mut accounting,
+            init_margin_req,
+            QuoteCurrency::zero(),
+        ));
+
+        // A buy order equal to the position only reduces risk.
+        let order = LimitOrder::new(
+            Side::Buy,
+            QuoteCurrency::new(90, 0),
+            BaseCurrency::new(2, 0),
+        )
+        .unwrap()
+        .into_pending(ExchangeOrderMeta::new(0.into(), 0.into()));
+        order_margin.update(&order).unwrap();
+        assert_eq!(
+            order_margin.order_margin(init_margin_req, &position),
+            QuoteCurrency::zero()
+        );
+        order_margin.remove(CancelBy::OrderId(0.into()));
+
+        // The highest buy order is offset first, the excess of the next one requires margin.
+        let order = LimitOrder::new(
+            Side::Buy,
+            QuoteCurrency::new(90, 0),
+            BaseCurrency::new(1, 0),
+        )
+        .unwrap()
+        .into_pending(ExchangeOrderMeta::new(1.into(), 0.into()));
+        order_margin.update(&order).unwrap();
+        assert_eq!(
+            order_margin.order_margin(init_margin_req, &position),
+            QuoteCurrency::zero()
+        );
+        let order = LimitOrder::new(
+            Side::Buy,
+            QuoteCurrency::new(80, 0),
+            BaseCurrency::new(3, 0),
+        )
+        .unwrap()
+        .into_pending(ExchangeOrderMeta::new(2.into(), 0.into()));
+        order_margin.update(&order).unwrap();
+        assert_eq!(
+            order_margin.order_margin(init_margin_req, &position),
+            QuoteCurrency::new(160, 0) * init_margin_req
+        );
+    }
 }
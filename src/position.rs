@@ -6,7 +6,7 @@ use num_traits::Zero;
 use crate::{
     position_inner::PositionInner,
     prelude::{Currency, Mon, QuoteCurrency, TransactionAccounting, USER_POSITION_MARGIN_ACCOUNT},
-    types::{MarginCurrency, Side},
+    types::{MarginCurrency, PnlRealizationPolicy, Side},
 };
 
 /// A futures position can be one of three variants.
@@ -80,7 +80,19 @@ where
         }
     }
 
+    /// The value of the position, being its total cost at the entry price plus the unrealized pnl
+    /// when marked to market with the `bid` for a long and the `ask` for a short position.
+    pub fn value(
+        &self,
+        bid: QuoteCurrency<I, D>,
+        ask: QuoteCurrency<I, D>,
+    ) -> BaseOrQuote::PairedCurrency {
+        self.total_cost() + self.unrealized_pnl(bid, ask)
+    }
+
     /// Change a position while doing proper accounting and balance transfers.
+    /// Reducing the position credits rebates only if `credit_maker_rebates`.
+    #[allow(clippy::too_many_arguments)]
     #[tracing::instrument(level = "debug")]
     pub(crate) fn change_position<Acc>(
         &mut self,
@@ -90,6 +102,8 @@ where
         transaction_accounting: &mut Acc,
         init_margin_req: Decimal<I, D>,
         fees: BaseOrQuote::PairedCurrency,
+        pnl_realization_policy: PnlRealizationPolicy,
+        credit_maker_rebates: bool,
     ) where
         Acc: TransactionAccounting<I, D, BaseOrQuote::PairedCurrency>,
     {
@@ -145,6 +159,8 @@ where
                             init_margin_req,
                             1,
                             fees,
+                            pnl_realization_policy,
+                            credit_maker_rebates,
                         );
                     }
                     Ordering::Equal => {
@@ -155,6 +171,8 @@ where
                             init_margin_req,
                             1,
                             fees,
+                            pnl_realization_policy,
+                            credit_maker_rebates,
                         );
                         *self = Position::Neutral;
                         debug_assert_eq!(
@@ -173,6 +191,8 @@ where
                             init_margin_req,
                             1,
                             fees,
+                            pnl_realization_policy,
+                            credit_maker_rebates,
                         );
                         assert_eq!(inner.quantity(), BaseOrQuote::zero());
                         debug_assert_eq!(
@@ -201,6 +221,8 @@ where
                             init_margin_req,
                             -1,
                             fees,
+                            pnl_realization_policy,
+                            credit_maker_rebates,
                         );
                     }
                     Ordering::Equal => {
@@ -211,6 +233,8 @@ where
                             init_margin_req,
                             -1,
                             fees,
+                            pnl_realization_policy,
+                            credit_maker_rebates,
                         );
                         *self = Position::Neutral;
                         debug_assert_eq!(
@@ -229,6 +253,8 @@ where
                             init_margin_req,
                             -1,
                             fees,
+                            pnl_realization_policy,
+                            credit_maker_rebates,
                         );
                         assert_eq!(inner.quantity(), BaseOrQuote::zero());
                         debug_assert_eq!(
@@ -302,6 +328,8 @@ mod tests {
             &mut acc,
             init_margin_req,
             fees,
+            PnlRealizationPolicy::default(),
+            false,
         );
     }
 
@@ -343,6 +371,10 @@ mod tests {
                 QuoteCurrency::from(Decimal::try_from_scaled(0, 5).unwrap()),
                 QuoteCurrency::from(Decimal::try_from_scaled(1000000000, 5).unwrap()),
             ),
+            TAccount::from_parts(QuoteCurrency::zero(), QuoteCurrency::zero()),
+            TAccount::from_parts(QuoteCurrency::zero(), QuoteCurrency::zero()),
+            TAccount::from_parts(QuoteCurrency::zero(), QuoteCurrency::zero()),
+            TAccount::from_parts(QuoteCurrency::zero(), QuoteCurrency::zero()),
         ]);
         let init_margin_req = Decimal::ONE;
         let fees = QuoteCurrency::zero();
@@ -353,6 +385,8 @@ mod tests {
             &mut acc,
             init_margin_req,
             fees,
+            PnlRealizationPolicy::default(),
+            false,
         );
     }
 }
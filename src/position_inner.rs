@@ -6,11 +6,13 @@ use num_traits::{Signed, Zero};
 use tracing::{debug, trace};
 
 use crate::{
+    accounting::AccountId,
     prelude::{
         Currency, Mon, QuoteCurrency, Transaction, TransactionAccounting, EXCHANGE_FEE_ACCOUNT,
-        TREASURY_ACCOUNT, USER_POSITION_MARGIN_ACCOUNT, USER_WALLET_ACCOUNT,
+        PENDING_PNL_ACCOUNT, REBATE_ACCOUNT, TREASURY_ACCOUNT, USER_POSITION_MARGIN_ACCOUNT,
+        USER_WALLET_ACCOUNT,
     },
-    types::MarginCurrency,
+    types::{MarginCurrency, PnlRealizationPolicy},
 };
 
 /// Describes the position information of the account.
@@ -32,6 +34,12 @@ where
     /// The outstanding fees of the position that will be payed when reducing the position.
     #[getset(get_copy = "pub")]
     outstanding_fees: BaseOrQuote::PairedCurrency,
+
+    /// The profit and loss of partial closes that has not been realized yet,
+    /// which is held in the `PENDING_PNL_ACCOUNT`.
+    /// Only accrues when using `PnlRealizationPolicy::OnFullClose`.
+    #[getset(get_copy = "pub")]
+    deferred_pnl: BaseOrQuote::PairedCurrency,
 }
 
 impl<I, const D: u8, BaseOrQuote> std::fmt::Display for PositionInner<I, D, BaseOrQuote>
@@ -64,6 +72,7 @@ where
             quantity,
             entry_price,
             outstanding_fees,
+            deferred_pnl: BaseOrQuote::PairedCurrency::zero(),
         }
     }
 
@@ -97,6 +106,7 @@ where
             quantity,
             entry_price,
             outstanding_fees: fees,
+            deferred_pnl: BaseOrQuote::PairedCurrency::zero(),
         }
     }
 
@@ -155,6 +165,9 @@ where
     }
 
     /// Decrease the position.
+    /// Net negative outstanding fees are rebates, which are only paid out if `credit_maker_rebates`.
+    /// Otherwise they keep offsetting the fees of the position.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn decrease_contracts<Acc>(
         &mut self,
         qty: BaseOrQuote,
@@ -163,6 +176,8 @@ where
         init_margin_req: Decimal<I, D>,
         direction_multiplier: i8,
         fees: BaseOrQuote::PairedCurrency,
+        pnl_realization_policy: PnlRealizationPolicy,
+        credit_maker_rebates: bool,
     ) where
         Acc: TransactionAccounting<I, D, BaseOrQuote::PairedCurrency>,
     {
@@ -186,6 +201,20 @@ where
             liquidation_price,
             if direction_multiplier == 1 { qty } else { -qty },
         );
+        let pnl = match pnl_realization_policy {
+            PnlRealizationPolicy::Proportional => pnl,
+            PnlRealizationPolicy::OnFullClose if self.quantity.is_zero() => {
+                // The pending profit and loss of the partial closes is realized as well.
+                let deferred_pnl = std::mem::take(&mut self.deferred_pnl);
+                Self::transfer_pending_pnl(accounting, USER_WALLET_ACCOUNT, -deferred_pnl);
+                pnl
+            }
+            PnlRealizationPolicy::OnFullClose => {
+                self.deferred_pnl += pnl;
+                Self::transfer_pending_pnl(accounting, TREASURY_ACCOUNT, pnl);
+                BaseOrQuote::PairedCurrency::zero()
+            }
+        };
         match pnl.cmp(&BaseOrQuote::PairedCurrency::zero()) {
             Ordering::Greater => {
                 let transaction = Transaction::new(USER_WALLET_ACCOUNT, TREASURY_ACCOUNT, pnl);
@@ -214,17 +243,53 @@ where
             .create_margin_transfer(transaction)
             .expect("margin transfer must work");
 
-        if self.outstanding_fees > BaseOrQuote::PairedCurrency::zero() {
-            let transaction = Transaction::new(
-                EXCHANGE_FEE_ACCOUNT,
-                USER_WALLET_ACCOUNT,
-                self.outstanding_fees,
-            );
-            accounting
-                .create_margin_transfer(transaction)
-                .expect("margin transfer must work");
-            self.outstanding_fees = BaseOrQuote::PairedCurrency::zero();
+        match self
+            .outstanding_fees
+            .cmp(&BaseOrQuote::PairedCurrency::zero())
+        {
+            Ordering::Greater => {
+                let transaction = Transaction::new(
+                    EXCHANGE_FEE_ACCOUNT,
+                    USER_WALLET_ACCOUNT,
+                    self.outstanding_fees,
+                );
+                accounting
+                    .create_margin_transfer(transaction)
+                    .expect("margin transfer must work");
+            }
+            Ordering::Less if credit_maker_rebates => {
+                // Negative fees are rebates, which accrue to their own account.
+                let transaction = Transaction::new(
+                    REBATE_ACCOUNT,
+                    TREASURY_ACCOUNT,
+                    self.outstanding_fees.abs(),
+                );
+                accounting
+                    .create_margin_transfer(transaction)
+                    .expect("margin transfer must work");
+            }
+            Ordering::Less | Ordering::Equal => return,
         }
+        self.outstanding_fees = BaseOrQuote::PairedCurrency::zero();
+    }
+
+    /// Moves a positive `pnl` from the `counterparty` into the `PENDING_PNL_ACCOUNT`,
+    /// and a negative one the other way around.
+    fn transfer_pending_pnl<Acc>(
+        accounting: &mut Acc,
+        counterparty: AccountId,
+        pnl: BaseOrQuote::PairedCurrency,
+    ) where
+        Acc: TransactionAccounting<I, D, BaseOrQuote::PairedCurrency>,
+    {
+        let transaction = match pnl.cmp(&BaseOrQuote::PairedCurrency::zero()) {
+            Ordering::Greater => Transaction::new(PENDING_PNL_ACCOUNT, counterparty, pnl),
+            Ordering::Less => Transaction::new(counterparty, PENDING_PNL_ACCOUNT, pnl.abs()),
+            Ordering::Equal => return,
+        };
+        accounting
+            .create_margin_transfer(transaction)
+            .expect("margin transfer must work");
     }
 }
 
@@ -251,6 +316,7 @@ mod tests {
                 quantity: qty,
                 entry_price,
                 outstanding_fees: fees,
+                deferred_pnl: QuoteCurrency::zero(),
             }
         );
         assert_eq!(pos.entry_price(), QuoteCurrency::new(100, 0));
@@ -281,7 +347,8 @@ mod tests {
             PositionInner {
                 quantity: BaseCurrency::one(),
                 entry_price: QuoteCurrency::new(125, 0),
-                outstanding_fees: fee_0 + fee_1
+                outstanding_fees: fee_0 + fee_1,
+                deferred_pnl: QuoteCurrency::zero(),
             }
         );
         assert_eq!(pos.entry_price(), QuoteCurrency::new(125, 0));
@@ -310,6 +377,8 @@ mod tests {
             init_margin_req,
             1,
             fees / QuoteCurrency::new(2, 0),
+            PnlRealizationPolicy::default(),
+            false,
         );
         assert_eq!(
             pos,
@@ -317,6 +386,7 @@ mod tests {
                 quantity: BaseCurrency::new(25, 1),
                 entry_price: QuoteCurrency::new(100, 0),
                 outstanding_fees: QuoteCurrency::new(0, 0),
+                deferred_pnl: QuoteCurrency::zero(),
             }
         );
         assert_eq!(pos.entry_price(), QuoteCurrency::new(100, 0));
@@ -337,6 +407,8 @@ mod tests {
             init_margin_req,
             1,
             fees / QuoteCurrency::new(2, 0),
+            PnlRealizationPolicy::default(),
+            false,
         );
         assert_eq!(
             pos,
@@ -344,6 +416,7 @@ mod tests {
                 quantity: BaseCurrency::new(0, 0),
                 entry_price: QuoteCurrency::new(100, 0),
                 outstanding_fees: QuoteCurrency::new(0, 0),
+                deferred_pnl: QuoteCurrency::zero(),
             }
         );
         assert_eq!(pos.entry_price(), QuoteCurrency::new(100, 0));
@@ -381,6 +454,8 @@ mod tests {
             init_margin_req,
             side_mult,
             fees / QuoteCurrency::new(2, 0),
+            PnlRealizationPolicy::default(),
+            false,
         );
 
         assert_eq!(pos.quantity(), BaseCurrency::new(25, 1));
@@ -424,6 +499,8 @@ mod tests {
             init_margin_req,
             side_mult,
             fees / QuoteCurrency::new(2, 0),
+            PnlRealizationPolicy::default(),
+            false,
         );
 
         assert_eq!(pos.quantity(), BaseCurrency::new(25, 1));
@@ -444,6 +521,76 @@ mod tests {
         );
     }
 
+    #[test_case::test_matrix([PnlRealizationPolicy::Proportional, PnlRealizationPolicy::OnFullClose])]
+    fn position_inner_decrease_contracts_pnl_realization_policy(policy: PnlRealizationPolicy) {
+        let mut ta = InMemoryTransactionAccounting::new(QuoteCurrency::<_, DECIMALS>::new(1000, 0));
+        let init_margin_req = Decimal::one();
+        let qty = BaseCurrency::new(5, 0);
+        let fees = QuoteCurrency::zero();
+        let mut pos = PositionInner::new(
+            qty,
+            QuoteCurrency::new(100, 0),
+            &mut ta,
+            init_margin_req,
+            fees,
+        );
+
+        let exit_price = QuoteCurrency::new(110, 0);
+        pos.decrease_contracts(
+            BaseCurrency::new(25, 1),
+            exit_price,
+            &mut ta,
+            init_margin_req,
+            1,
+            fees,
+            policy,
+            false,
+        );
+        let profit = QuoteCurrency::new(25, 0);
+        match policy {
+            PnlRealizationPolicy::Proportional => {
+                assert_eq!(pos.deferred_pnl(), QuoteCurrency::zero());
+                assert_eq!(
+                    ta.margin_balance_of(USER_WALLET_ACCOUNT).unwrap(),
+                    QuoteCurrency::new(750, 0) + profit
+                );
+            }
+            PnlRealizationPolicy::OnFullClose => {
+                assert_eq!(pos.deferred_pnl(), profit);
+                assert_eq!(
+                    ta.margin_balance_of(USER_WALLET_ACCOUNT).unwrap(),
+                    QuoteCurrency::new(750, 0)
+                );
+                assert_eq!(ta.margin_balance_of(PENDING_PNL_ACCOUNT).unwrap(), profit);
+            }
+        }
+        assert_eq!(
+            ta.margin_balance_of(USER_POSITION_MARGIN_ACCOUNT).unwrap(),
+            QuoteCurrency::new(250, 0)
+        );
+
+        // Fully closing the position realizes everything, regardless of the policy.
+        pos.decrease_contracts(
+            BaseCurrency::new(25, 1),
+            exit_price,
+            &mut ta,
+            init_margin_req,
+            1,
+            fees,
+            policy,
+            false,
+        );
+        assert_eq!(pos.deferred_pnl(), QuoteCurrency::zero());
+        assert_eq!(
+            ta.margin_balance_of(USER_WALLET_ACCOUNT).unwrap(),
+            QuoteCurrency::new(1000, 0) + profit * Decimal::TWO
+        );
+        assert_eq!(
+            ta.margin_balance_of(PENDING_PNL_ACCOUNT).unwrap(),
+            QuoteCurrency::zero()
+        );
+    }
+
     #[tracing_test::traced_test]
     #[test_case::test_matrix([1, 2, 5])]
     #[ignore]
@@ -465,6 +612,8 @@ mod tests {
             init_margin_req,
             1,
             fees / BaseCurrency::new(2, 0),
+            PnlRealizationPolicy::default(),
+            false,
         );
 
         assert_eq!(pos.quantity(), QuoteCurrency::new(250, 0));
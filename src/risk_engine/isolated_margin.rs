@@ -6,7 +6,7 @@ use crate::{
     market_state::MarketState,
     order_margin::OrderMargin,
     prelude::{Currency, Mon, Position, QuoteCurrency, RiskError},
-    types::{LimitOrder, MarginCurrency, MarketOrder, Pending, Side, UserOrderIdT},
+    types::{Fee, LimitOrder, MarginCurrency, MarketOrder, Pending, Side, Taker, UserOrderIdT},
 };
 
 #[derive(Debug, Clone)]
@@ -43,6 +43,7 @@ where
         order: &MarketOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>,
         fill_price: QuoteCurrency<I, D>,
         available_wallet_balance: BaseOrQuote::PairedCurrency,
+        fee: Fee<I, D, Taker>,
     ) -> Result<(), RiskError> {
         match order.side() {
             Side::Buy => self.check_market_buy_order(
@@ -51,6 +52,7 @@ where
                 order,
                 fill_price,
                 available_wallet_balance,
+                fee,
             ),
             Side::Sell => self.check_market_sell_order(
                 position,
@@ -58,6 +60,7 @@ where
                 order,
                 fill_price,
                 available_wallet_balance,
+                fee,
             ),
         }
     }
@@ -90,7 +93,7 @@ where
         market_state: &MarketState<I, D>,
         position: &Position<I, D, BaseOrQuote>,
     ) -> Result<(), RiskError> {
-        let maint_margin_req = self.contract_spec.maintenance_margin();
+        let maint_margin_req = self.contract_spec.liquidation_margin_req();
         match position {
             Position::Neutral => return Ok(()),
             Position::Long(inner) => {
@@ -126,6 +129,7 @@ where
         order: &MarketOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>,
         fill_price: QuoteCurrency<I, D>,
         available_wallet_balance: BaseOrQuote::PairedCurrency,
+        fee_taker: Fee<I, D, Taker>,
     ) -> Result<(), RiskError>
     where
         UserOrderId: UserOrderIdT,
@@ -139,7 +143,7 @@ where
                     BaseOrQuote::PairedCurrency::convert_from(order.quantity(), fill_price);
                 let margin_req = notional_value * self.contract_spec.init_margin_req();
 
-                let fee = notional_value * *self.contract_spec.fee_taker().as_ref();
+                let fee = notional_value * *fee_taker.as_ref();
                 if margin_req + fee > available_wallet_balance {
                     return Err(RiskError::NotEnoughAvailableBalance);
                 }
@@ -157,7 +161,7 @@ where
                     BaseOrQuote::PairedCurrency::convert_from(new_long_size, fill_price);
                 let new_margin_req = new_notional_value * self.contract_spec.init_margin_req();
 
-                let fee = new_notional_value * *self.contract_spec.fee_taker().as_ref();
+                let fee = new_notional_value * *fee_taker.as_ref();
 
                 if new_margin_req + fee > available_wallet_balance + released_from_old_pos {
                     return Err(RiskError::NotEnoughAvailableBalance);
@@ -175,6 +179,7 @@ where
         order: &MarketOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>,
         fill_price: QuoteCurrency<I, D>,
         available_wallet_balance: BaseOrQuote::PairedCurrency,
+        fee_taker: Fee<I, D, Taker>,
     ) -> Result<(), RiskError>
     where
         UserOrderId: UserOrderIdT,
@@ -186,7 +191,7 @@ where
                 let notional_value =
                     BaseOrQuote::PairedCurrency::convert_from(order.quantity(), fill_price);
                 let margin_req = notional_value * self.contract_spec.init_margin_req();
-                let fee = notional_value * *self.contract_spec.fee_taker().as_ref();
+                let fee = notional_value * *fee_taker.as_ref();
 
                 if margin_req + fee > available_wallet_balance {
                     return Err(RiskError::NotEnoughAvailableBalance);
@@ -206,7 +211,7 @@ where
                     BaseOrQuote::PairedCurrency::convert_from(new_short_size, fill_price);
                 let new_margin_req = new_notional_value * self.contract_spec.init_margin_req();
 
-                let fee = new_notional_value * *self.contract_spec.fee_taker().as_ref();
+                let fee = new_notional_value * *fee_taker.as_ref();
 
                 if new_margin_req + fee > available_wallet_balance + released_from_old_pos {
                     return Err(RiskError::NotEnoughAvailableBalance);
@@ -2,7 +2,7 @@ use crate::{
     market_state::MarketState,
     order_margin::OrderMargin,
     prelude::{Currency, Mon, Position, QuoteCurrency, RiskError},
-    types::{LimitOrder, MarginCurrency, MarketOrder, Pending, UserOrderIdT},
+    types::{Fee, LimitOrder, MarginCurrency, MarketOrder, Pending, Taker, UserOrderIdT},
 };
 
 pub(crate) trait RiskEngine<I, const D: u8, BaseOrQuote, UserOrderId>
@@ -23,6 +23,7 @@ where
     /// the risk engine will typically calculate the margin requirements as if the order is executed and reduces the size of their existing position.
     /// The risk engine will consider the notional value of the order, the current market price,
     /// and the leverage used to determine the new required margin for the remaining position.
+    /// The taker `fee` of the order has to be covered as well.
     ///
     /// # Returns:
     /// If Err, the account cannot satisfy the margin requirements.
@@ -33,6 +34,7 @@ where
         order: &MarketOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>,
         fill_price: QuoteCurrency<I, D>,
         available_wallet_balance: BaseOrQuote::PairedCurrency,
+        fee: Fee<I, D, Taker>,
     ) -> Result<(), RiskError>;
 
     /// Checks if the account it able to satisfy the margin requirements for a new limit order.
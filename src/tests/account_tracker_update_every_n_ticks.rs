@@ -0,0 +1,97 @@
+use const_decimal::Decimal;
+
+use crate::{prelude::*, test_fee_maker, test_fee_taker, DECIMALS};
+
+/// Feeds 1000 updates with a rising price over two days.
+/// Returns the number of updates the tracker processed, the number of trading days
+/// and the buy and hold return.
+fn run_ticks(update_every_n_ticks: u64) -> (u64, u32, f64) {
+    let contract_spec = ContractSpecification::new(
+        leverage!(1),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
+        test_fee_maker(),
+        test_fee_taker(),
+    )
+    .unwrap();
+    let starting_balance = QuoteCurrency::new(1000, 0);
+    let mut config = Config::new(starting_balance, 10, contract_spec, 3600).unwrap();
+    config.set_account_tracker_update_every_n_ticks(update_every_n_ticks);
+    let mut exchange = ExchangeBuilder::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::new(config)
+        .with_account_tracker(FullAccountTracker::new(starting_balance))
+        .build();
+    for i in 0..1000 {
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(1000 + i, 1),
+                ask: QuoteCurrency::new(1001 + i, 1),
+                timestamp_exchange_ns: (i * 172_800_000_000).into(),
+            })
+            .unwrap();
+    }
+    let tracker = exchange.account_tracker();
+    (
+        tracker.num_updates(),
+        tracker.num_trading_days(),
+        tracker.buy_and_hold_return().into(),
+    )
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn account_tracker_update_every_n_ticks() {
+    let (full_updates, full_days, full_bh) = run_ticks(1);
+    let (throttled_updates, throttled_days, throttled_bh) = run_ticks(10);
+    assert_eq!(full_updates, 1000);
+    assert_eq!(throttled_updates, 100);
+
+    assert_eq!(full_days, throttled_days);
+    assert!((full_bh - throttled_bh).abs() / full_bh < 0.01);
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn account_tracker_update_every_n_ticks_daily_realized_pnl() {
+    const DAY_NS: i64 = 86_400_000_000_000;
+    let contract_spec = ContractSpecification::new(
+        leverage!(1),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
+        test_fee_maker(),
+        test_fee_taker(),
+    )
+    .unwrap();
+    let starting_balance = QuoteCurrency::new(1000, 0);
+    let mut config = Config::new(starting_balance, 10, contract_spec, 3600).unwrap();
+    config.set_account_tracker_update_every_n_ticks(10);
+    let mut exchange = ExchangeBuilder::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::new(config)
+        .with_account_tracker(FullAccountTracker::new(starting_balance))
+        .build();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    // The tracker skips the update on the next day, but the trade is still bucketed into it.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(111, 0),
+            ask: QuoteCurrency::new(112, 0),
+            timestamp_exchange_ns: (DAY_NS + 1).into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(1, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.account_tracker().num_updates(), 1);
+    assert_eq!(
+        exchange.account_tracker().daily_realized_pnl(),
+        &[(DAY_NS as u64, 10.0)]
+    );
+}
@@ -0,0 +1,52 @@
+use crate::{mock_exchange_linear, mock_exchange_linear_with_config, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn batch_order_margin_updates() {
+    let mut batched = mock_exchange_linear_with_config(|config| {
+        config.set_batch_order_margin_updates(true);
+    });
+    let mut per_fill = mock_exchange_linear();
+
+    for exchange in [&mut batched, &mut per_fill] {
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(100, 0),
+                ask: QuoteCurrency::new(101, 0),
+                timestamp_exchange_ns: 0.into(),
+            })
+            .unwrap();
+        for price in [98, 99, 100] {
+            let order = LimitOrder::new(
+                Side::Buy,
+                QuoteCurrency::new(price, 0),
+                BaseCurrency::new(2, 0),
+            )
+            .unwrap();
+            exchange.submit_limit_order(order).unwrap();
+        }
+        let order = LimitOrder::new(
+            Side::Sell,
+            QuoteCurrency::new(105, 0),
+            BaseCurrency::new(1, 0),
+        )
+        .unwrap();
+        exchange.submit_limit_order(order).unwrap();
+
+        // A single trade fills all the buy orders at once.
+        let updates = exchange
+            .update_state(&Trade {
+                price: QuoteCurrency::new(97, 0),
+                quantity: BaseCurrency::new(10, 0),
+                side: Side::Sell,
+                timestamp_exchange_ns: 1.into(),
+            })
+            .unwrap();
+        assert_eq!(updates.len(), 3);
+        assert_eq!(exchange.active_limit_orders().len(), 1);
+    }
+
+    assert_eq!(batched.position(), per_fill.position());
+    assert_eq!(batched.user_balances(), per_fill.user_balances());
+    assert_eq!(batched.fees_paid(), per_fill.fees_paid());
+}
@@ -0,0 +1,26 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+fn bba() {
+    let mut exchange = mock_exchange_linear();
+    assert_eq!(
+        exchange.bba(),
+        (QuoteCurrency::zero(), QuoteCurrency::zero())
+    );
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        exchange.bba(),
+        (QuoteCurrency::new(100, 0), QuoteCurrency::new(101, 0))
+    );
+    assert_eq!(
+        exchange.bba(),
+        (exchange.market_state().bid(), exchange.market_state().ask())
+    );
+}
@@ -0,0 +1,58 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn bba_with_trades_fills_and_updates_book() {
+    let mut exchange = mock_exchange_linear();
+    assert!(exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into()
+        })
+        .unwrap()
+        .is_empty());
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(100, 0),
+        BaseCurrency::new(2, 0),
+    )
+    .unwrap();
+    exchange.submit_limit_order(order).unwrap();
+
+    let trades = [
+        Trade {
+            price: QuoteCurrency::new(99, 0),
+            quantity: BaseCurrency::new(1, 0),
+            side: Side::Sell,
+            timestamp_exchange_ns: 1.into(),
+        },
+        Trade {
+            price: QuoteCurrency::new(98, 0),
+            quantity: BaseCurrency::new(3, 0),
+            side: Side::Sell,
+            timestamp_exchange_ns: 1.into(),
+        },
+    ];
+    let update = BbaWithTrades {
+        bba: Bba {
+            bid: QuoteCurrency::new(97, 0),
+            ask: QuoteCurrency::new(98, 0),
+            timestamp_exchange_ns: 1.into(),
+        },
+        trades: &trades,
+    };
+    let exec_orders = exchange.update_state(&update).unwrap();
+    assert_eq!(exec_orders.len(), 1);
+    assert!(matches!(exec_orders[0], LimitOrderUpdate::FullyFilled(_)));
+
+    assert_eq!(exchange.market_state().bid(), QuoteCurrency::new(97, 0));
+    assert_eq!(exchange.market_state().ask(), QuoteCurrency::new(98, 0));
+    assert_eq!(exchange.market_state().current_timestamp_ns(), 1.into());
+    assert!(exchange.active_limit_orders().is_empty());
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(2, 0));
+    assert_eq!(
+        exchange.position().entry_price(),
+        QuoteCurrency::new(100, 0)
+    );
+}
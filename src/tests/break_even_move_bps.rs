@@ -0,0 +1,20 @@
+use const_decimal::Decimal;
+
+use crate::{mock_exchange_inverse, mock_exchange_linear, prelude::*};
+
+#[test]
+fn break_even_move_bps() {
+    // A maker fee of 2 bps and a taker fee of 6 bps.
+    let exchange = mock_exchange_linear();
+    assert_eq!(
+        exchange.break_even_move_bps(),
+        Decimal::try_from_scaled(8, 0).unwrap()
+    );
+
+    // Independent of the margin currency and balance.
+    let exchange = mock_exchange_inverse(BaseCurrency::new(10, 0));
+    assert_eq!(
+        exchange.break_even_move_bps(),
+        Decimal::try_from_scaled(8, 0).unwrap()
+    );
+}
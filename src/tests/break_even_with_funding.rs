@@ -0,0 +1,43 @@
+use const_decimal::Decimal;
+
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn break_even_with_funding() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let funding_rate = Decimal::try_from_scaled(1, 4).unwrap();
+    assert_eq!(exchange.break_even_with_funding(3, funding_rate), None);
+
+    // A long entered at 101 must cover the round-trip fees of 8 bps.
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    let fee_only = exchange
+        .break_even_with_funding(3, Decimal::zero())
+        .unwrap();
+    assert_eq!(fee_only, QuoteCurrency::new(1010808, 4));
+    // Paying 3 bps of funding moves its break-even up.
+    assert_eq!(
+        exchange.break_even_with_funding(3, funding_rate),
+        Some(QuoteCurrency::new(1011111, 4))
+    );
+
+    // A short entered at 100 receives the funding, which also moves its break-even up.
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(
+        exchange.break_even_with_funding(3, Decimal::zero()),
+        Some(QuoteCurrency::new(9992, 2))
+    );
+    assert_eq!(
+        exchange.break_even_with_funding(3, funding_rate),
+        Some(QuoteCurrency::new(9995, 2))
+    );
+}
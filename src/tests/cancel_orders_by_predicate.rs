@@ -0,0 +1,69 @@
+use const_decimal::Decimal;
+
+use crate::{prelude::*, test_fee_maker, test_fee_taker, DECIMALS};
+
+#[test]
+#[tracing_test::traced_test]
+fn cancel_orders_by_predicate() {
+    let contract_spec = ContractSpecification::new(
+        leverage!(1),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
+        test_fee_maker(),
+        test_fee_taker(),
+    )
+    .unwrap();
+    let config = Config::new(QuoteCurrency::new(1000, 0), 10, contract_spec, 3600).unwrap();
+    let mut exchange = ExchangeBuilder::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::new(config)
+        .with_user_order_id::<i64>()
+        .build();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    // Group 1 has user order ids 100.., group 2 has 200..
+    for user_order_id in [100, 101, 200, 201] {
+        let order = LimitOrder::new_with_user_order_id(
+            Side::Buy,
+            QuoteCurrency::new(100, 0),
+            BaseCurrency::new(1, 0),
+            user_order_id,
+        )
+        .unwrap();
+        exchange.submit_limit_order(order).unwrap();
+    }
+    assert_eq!(
+        exchange.user_balances().order_margin,
+        QuoteCurrency::new(400, 0)
+    );
+
+    let cancelled = exchange.cancel_orders_by_predicate(|uid| uid / 100 == 1);
+    assert_eq!(cancelled.len(), 2);
+    assert!(cancelled
+        .iter()
+        .all(|order| order.user_order_id() / 100 == 1));
+    assert_eq!(exchange.active_limit_orders().len(), 2);
+    assert!(exchange
+        .active_limit_orders()
+        .values()
+        .all(|order| order.user_order_id() / 100 == 2));
+    assert_eq!(
+        exchange.user_balances(),
+        UserBalances {
+            available_wallet_balance: QuoteCurrency::new(800, 0),
+            position_margin: QuoteCurrency::zero(),
+            order_margin: QuoteCurrency::new(200, 0),
+            _q: std::marker::PhantomData
+        }
+    );
+
+    // Nothing matches anymore.
+    assert!(exchange
+        .cancel_orders_by_predicate(|uid| uid / 100 == 1)
+        .is_empty());
+}
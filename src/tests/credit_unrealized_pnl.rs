@@ -0,0 +1,49 @@
+use test_case::test_case;
+
+use crate::{mock_exchange_linear_with_config, prelude::*};
+
+#[test_case(true; "With unrealized pnl credit")]
+#[test_case(false; "Without unrealized pnl credit")]
+#[tracing_test::traced_test]
+fn credit_unrealized_pnl(credit_unrealized_pnl: bool) {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_credit_unrealized_pnl(credit_unrealized_pnl);
+    });
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(9, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(
+        exchange.user_balances().available_wallet_balance,
+        QuoteCurrency::new(91, 0)
+    );
+
+    // The position is now in profit by 9 * (150 - 101) = 441.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(150, 0),
+            ask: QuoteCurrency::new(151, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+
+    // Requires 453 margin, more than the wallet balance of 91.
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(3, 0)).unwrap();
+    let result = exchange.submit_market_order(order);
+    if credit_unrealized_pnl {
+        result.unwrap();
+        assert_eq!(exchange.position().quantity(), BaseCurrency::new(12, 0));
+    } else {
+        assert_eq!(
+            result,
+            Err(Error::RiskError(RiskError::NotEnoughAvailableBalance))
+        );
+        assert_eq!(exchange.position().quantity(), BaseCurrency::new(9, 0));
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn current_drawdown() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.current_drawdown(), 0.0);
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    // Marked at the bid of 100, the equity is 995.
+    assert!((exchange.current_drawdown() - 0.005).abs() < 1e-9);
+
+    // The equity rises to a new high-water mark of 1045.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(110, 0),
+            ask: QuoteCurrency::new(111, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.current_drawdown(), 0.0);
+
+    // The equity falls to 1020.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(105, 0),
+            ask: QuoteCurrency::new(106, 0),
+            timestamp_exchange_ns: 2.into(),
+        })
+        .unwrap();
+    assert!((exchange.current_drawdown() - 25.0 / 1045.0).abs() < 1e-9);
+
+    // Recovering part of the loss keeps the high-water mark.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(108, 0),
+            ask: QuoteCurrency::new(109, 0),
+            timestamp_exchange_ns: 3.into(),
+        })
+        .unwrap();
+    assert!((exchange.current_drawdown() - 10.0 / 1045.0).abs() < 1e-9);
+}
@@ -0,0 +1,32 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn current_leverage() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.current_leverage(), 0.0);
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    // The notional value at the mid price of 100.5.
+    let notional = 201.0;
+    let upnl = exchange
+        .position()
+        .unrealized_pnl(QuoteCurrency::new(100, 0), QuoteCurrency::new(101, 0));
+    assert_eq!(upnl, QuoteCurrency::new(-2, 0));
+    let total_equity: f64 = (exchange.user_balances().sum() + upnl).into();
+    assert_eq!(exchange.current_leverage(), notional / total_equity);
+    assert!((exchange.current_leverage() - 0.2014).abs() < 0.001);
+
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.current_leverage(), 0.0);
+}
@@ -0,0 +1,59 @@
+use crate::{mock_exchange_linear, prelude::*, test_fee_taker};
+
+#[test]
+#[tracing_test::traced_test]
+fn drain_fills() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert!(exchange.drain_fills().is_empty());
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    let market_order = exchange.submit_market_order(order).unwrap();
+    let order = LimitOrder::new(
+        Side::Sell,
+        QuoteCurrency::new(110, 0),
+        BaseCurrency::new(2, 0),
+    )
+    .unwrap();
+    let limit_order = exchange.submit_limit_order(order).unwrap();
+
+    let fills = exchange.drain_fills();
+    assert_eq!(
+        fills,
+        vec![ExecutedTrade {
+            order_id: market_order.state().meta().id(),
+            side: Side::Buy,
+            price: QuoteCurrency::new(101, 0),
+            quantity: BaseCurrency::new(2, 0),
+            fee: QuoteCurrency::convert_from(BaseCurrency::new(2, 0), QuoteCurrency::new(101, 0))
+                * *test_fee_taker().as_ref(),
+            timestamp_exchange_ns: 0.into(),
+        }]
+    );
+    assert!(exchange.drain_fills().is_empty());
+
+    let updates = exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(111, 0),
+            quantity: BaseCurrency::new(1, 0),
+            side: Side::Buy,
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap()
+        .clone();
+    assert_eq!(updates.len(), 1);
+    let fills = exchange.drain_fills();
+    assert_eq!(fills.len(), updates.len());
+    assert_eq!(fills[0].order_id, limit_order.id());
+    assert_eq!(fills[0].side, Side::Sell);
+    assert_eq!(fills[0].price, QuoteCurrency::new(110, 0));
+    assert_eq!(fills[0].quantity, BaseCurrency::new(1, 0));
+    assert_eq!(fills[0].timestamp_exchange_ns, 1.into());
+    assert!(exchange.drain_fills().is_empty());
+}
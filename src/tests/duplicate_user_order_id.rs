@@ -0,0 +1,57 @@
+use const_decimal::Decimal;
+
+use crate::{prelude::*, test_fee_maker, test_fee_taker, DECIMALS};
+
+#[test]
+#[tracing_test::traced_test]
+fn duplicate_user_order_id() {
+    let contract_spec = ContractSpecification::new(
+        leverage!(1),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
+        test_fee_maker(),
+        test_fee_taker(),
+    )
+    .unwrap();
+    let config = Config::new(QuoteCurrency::new(1000, 0), 10, contract_spec, 3600).unwrap();
+    let mut exchange = ExchangeBuilder::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::new(config)
+        .with_user_order_id::<i64>()
+        .build();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    let order = |user_order_id| {
+        LimitOrder::new_with_user_order_id(
+            Side::Buy,
+            QuoteCurrency::new(100, 0),
+            BaseCurrency::new(1, 0),
+            user_order_id,
+        )
+        .unwrap()
+    };
+    exchange.submit_limit_order(order(42)).unwrap();
+    assert_eq!(
+        exchange.submit_limit_order(order(42)),
+        Err(Error::DuplicateUserOrderId)
+    );
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+    exchange.submit_limit_order(order(43)).unwrap();
+
+    // The default user order id is not considered unique.
+    exchange.submit_limit_order(order(0)).unwrap();
+    exchange.submit_limit_order(order(0)).unwrap();
+    assert_eq!(exchange.active_limit_orders().len(), 4);
+
+    // Once cancelled, the user order id can be reused.
+    exchange
+        .cancel_limit_order(CancelBy::UserOrderId(42))
+        .unwrap();
+    exchange.submit_limit_order(order(42)).unwrap();
+    assert_eq!(exchange.active_limit_orders().len(), 4);
+}
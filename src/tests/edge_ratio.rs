@@ -0,0 +1,51 @@
+use crate::{mock_exchange_linear, prelude::*, DECIMALS};
+
+#[test]
+#[tracing_test::traced_test]
+fn edge_ratio() {
+    let mut exchange = mock_exchange_linear();
+    let mut ts: i64 = 0;
+    let mut update = |exchange: &mut Exchange<_, DECIMALS, _, _, _, _>, bid: i64| {
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(bid, 0),
+                ask: QuoteCurrency::new(bid + 1, 0),
+                timestamp_exchange_ns: ts.into(),
+            })
+            .unwrap();
+        ts += 1;
+    };
+    update(&mut exchange, 100);
+    assert_eq!(exchange.edge_ratio(), 0.0);
+
+    // A long of 5 entered at 101 moves to an unrealized pnl of -30, then 95 and closes at 20.
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    for bid in [95, 120, 105] {
+        update(&mut exchange, bid);
+    }
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.mfe_distribution(), &[QuoteCurrency::new(95, 0)]);
+    assert_eq!(exchange.mae_distribution(), &[QuoteCurrency::new(30, 0)]);
+
+    // A short of 1 entered at 105 moves to an unrealized pnl of -6, then closes at 4.
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(1, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    for bid in [110, 100] {
+        update(&mut exchange, bid);
+    }
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(
+        exchange.mfe_distribution(),
+        &[QuoteCurrency::new(95, 0), QuoteCurrency::new(4, 0)]
+    );
+    assert_eq!(
+        exchange.mae_distribution(),
+        &[QuoteCurrency::new(30, 0), QuoteCurrency::new(6, 0)]
+    );
+
+    // The average MFE of 49.5 over the average MAE of 18.
+    assert_eq!(exchange.edge_ratio(), 2.75);
+}
@@ -0,0 +1,20 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn elapsed_ns() {
+    let mut exchange = mock_exchange_linear();
+    assert_eq!(exchange.elapsed_ns(), 0);
+
+    for (i, ts) in [1_000, 1_500, 4_000].into_iter().enumerate() {
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(100 + i as i64, 0),
+                ask: QuoteCurrency::new(101 + i as i64, 0),
+                timestamp_exchange_ns: ts.into(),
+            })
+            .unwrap();
+    }
+    assert_eq!(exchange.market_state().first_ts_ns(), 1_000.into());
+    assert_eq!(exchange.elapsed_ns(), 3_000);
+}
@@ -0,0 +1,17 @@
+use crate::{mock_exchange_linear, test_fee_maker, test_fee_taker};
+
+#[test]
+fn fees() {
+    let exchange = mock_exchange_linear();
+    let (fee_maker, fee_taker) = exchange.fees();
+    assert_eq!(fee_maker.as_ref(), test_fee_maker().as_ref());
+    assert_eq!(fee_taker.as_ref(), test_fee_taker().as_ref());
+    assert_eq!(
+        fee_maker.as_ref(),
+        exchange.config().contract_spec().fee_maker().as_ref()
+    );
+    assert_eq!(
+        fee_taker.as_ref(),
+        exchange.config().contract_spec().fee_taker().as_ref()
+    );
+}
@@ -0,0 +1,52 @@
+use const_decimal::Decimal;
+use test_case::test_case;
+
+use crate::{mock_exchange_linear_with_config, prelude::*};
+
+#[test_case(true; "With accrued funding")]
+#[test_case(false; "Without accrued funding")]
+#[tracing_test::traced_test]
+fn funding_aware_unrealized_pnl(funding_aware_unrealized_pnl: bool) {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_funding_aware_unrealized_pnl(funding_aware_unrealized_pnl);
+    });
+    exchange.set_funding_rates(vec![(100.into(), Decimal::try_from_scaled(1, 3).unwrap())]);
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.accrued_funding(), QuoteCurrency::zero());
+    assert_eq!(exchange.unrealized_pnl(), QuoteCurrency::new(-5, 0));
+
+    // Halfway through the funding period, the long accrued half of 5 * 100.5 * 0.001.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 50.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.accrued_funding(), QuoteCurrency::new(-25125, 5));
+    if funding_aware_unrealized_pnl {
+        assert_eq!(exchange.unrealized_pnl(), QuoteCurrency::new(-525125, 5));
+    } else {
+        assert_eq!(exchange.unrealized_pnl(), QuoteCurrency::new(-5, 0));
+    }
+
+    // Once settled, the funding is paid from the wallet and no longer accrued.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 100.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.accrued_funding(), QuoteCurrency::zero());
+    assert_eq!(exchange.unrealized_pnl(), QuoteCurrency::new(-5, 0));
+}
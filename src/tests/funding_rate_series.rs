@@ -0,0 +1,43 @@
+use const_decimal::Decimal;
+
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn funding_rate_series() {
+    let mut exchange = mock_exchange_linear();
+    let bba = |ts: i64| Bba {
+        bid: QuoteCurrency::new(100, 0),
+        ask: QuoteCurrency::new(101, 0),
+        timestamp_exchange_ns: ts.into(),
+    };
+    exchange.update_state(&bba(0)).unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    // Supplied out of order on purpose.
+    exchange.set_funding_rates(vec![
+        (20.into(), Decimal::try_from_scaled(-2, 3).unwrap()),
+        (10.into(), Decimal::try_from_scaled(1, 3).unwrap()),
+    ]);
+    let balance = exchange.user_balances().available_wallet_balance;
+
+    exchange.update_state(&bba(5)).unwrap();
+    assert_eq!(exchange.user_balances().available_wallet_balance, balance);
+
+    // The long pays 2 * 100.5 * 0.001.
+    exchange.update_state(&bba(10)).unwrap();
+    let balance = balance - QuoteCurrency::new(201, 3);
+    assert_eq!(exchange.user_balances().available_wallet_balance, balance);
+
+    exchange.update_state(&bba(15)).unwrap();
+    assert_eq!(exchange.user_balances().available_wallet_balance, balance);
+
+    // The long receives 2 * 100.5 * 0.002.
+    exchange.update_state(&bba(25)).unwrap();
+    let balance = balance + QuoteCurrency::new(402, 3);
+    assert_eq!(exchange.user_balances().available_wallet_balance, balance);
+
+    // The series is exhausted.
+    exchange.update_state(&bba(30)).unwrap();
+    assert_eq!(exchange.user_balances().available_wallet_balance, balance);
+}
@@ -0,0 +1,170 @@
+use crate::{mock_exchange_linear, mock_exchange_linear_with_config, prelude::*};
+
+#[test]
+fn hedge_mode_disabled() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap();
+    assert_eq!(
+        exchange.submit_hedge_market_order(order, HedgeLeg::Long),
+        Err(Error::HedgeModeDisabled)
+    );
+    assert_eq!(exchange.hedge_position(), &HedgePosition::default());
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn hedge_mode_holds_both_legs() {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_hedge_mode(true);
+    });
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    // Orders on the one-way position are rejected.
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap();
+    assert_eq!(
+        exchange.submit_market_order(order),
+        Err(Error::HedgeModeEnabled)
+    );
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(99, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    assert_eq!(
+        exchange.submit_limit_order(order),
+        Err(Error::HedgeModeEnabled)
+    );
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    exchange
+        .submit_hedge_market_order(order, HedgeLeg::Long)
+        .unwrap();
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(1, 0)).unwrap();
+    exchange
+        .submit_hedge_market_order(order, HedgeLeg::Short)
+        .unwrap();
+    assert_eq!(exchange.position(), &Position::Neutral);
+    let hedge_position = exchange.hedge_position();
+    assert_eq!(
+        hedge_position.long().unwrap().quantity(),
+        BaseCurrency::new(2, 0)
+    );
+    assert_eq!(
+        hedge_position.short().unwrap().quantity(),
+        BaseCurrency::new(1, 0)
+    );
+    // Both legs reserve their own margin and pay 0.1212 + 0.06 in taker fees.
+    assert_eq!(
+        exchange.user_balances().position_margin,
+        QuoteCurrency::new(302, 0)
+    );
+    assert_eq!(
+        exchange.user_balances().available_wallet_balance,
+        QuoteCurrency::new(6978188, 4)
+    );
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(110, 0),
+            ask: QuoteCurrency::new(111, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    // Closing the long leg realizes a profit of 18 and pays 0.132 in fees.
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(2, 0)).unwrap();
+    exchange
+        .submit_hedge_market_order(order, HedgeLeg::Long)
+        .unwrap();
+    assert!(exchange.hedge_position().long().is_none());
+    assert_eq!(
+        exchange.hedge_position().short().unwrap().quantity(),
+        BaseCurrency::new(1, 0)
+    );
+    assert_eq!(
+        exchange.user_balances().position_margin,
+        QuoteCurrency::new(100, 0)
+    );
+    assert_eq!(
+        exchange.user_balances().available_wallet_balance,
+        QuoteCurrency::new(9176868, 4)
+    );
+    assert_eq!(exchange.drain_fills().len(), 3);
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    assert_eq!(
+        exchange.submit_hedge_market_order(order, HedgeLeg::Short),
+        Err(Error::HedgeLegQuantityExceeded)
+    );
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(1, 0)).unwrap();
+    assert_eq!(
+        exchange.submit_hedge_market_order(order, HedgeLeg::Long),
+        Err(Error::NoPositionToReduce)
+    );
+
+    // Settling closes the short leg with a loss of 5.
+    exchange
+        .settle_at_expiry(QuoteCurrency::new(105, 0))
+        .unwrap();
+    assert_eq!(exchange.hedge_position(), &HedgePosition::default());
+    assert_eq!(
+        exchange.user_balances().position_margin,
+        QuoteCurrency::zero()
+    );
+    assert_eq!(
+        exchange.user_balances().available_wallet_balance,
+        QuoteCurrency::new(10126868, 4)
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn hedge_mode_liquidates_breached_leg() {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_hedge_mode(true);
+    });
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap();
+    exchange
+        .submit_hedge_market_order(order, HedgeLeg::Long)
+        .unwrap();
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(1, 0)).unwrap();
+    exchange
+        .submit_hedge_market_order(order, HedgeLeg::Short)
+        .unwrap();
+
+    // The bid breaches the liquidation price of the long leg at 101 * 0.5 = 50.5,
+    // while the short leg stays below its liquidation price of 100 * 1.5 = 150.
+    assert_eq!(
+        exchange.update_state(&Bba {
+            bid: QuoteCurrency::new(49, 0),
+            ask: QuoteCurrency::new(50, 0),
+            timestamp_exchange_ns: 1.into(),
+        }),
+        Err(Error::RiskError(RiskError::Liquidate))
+    );
+    assert!(exchange.hedge_position().long().is_none());
+    assert_eq!(
+        exchange.hedge_position().short().unwrap().quantity(),
+        BaseCurrency::new(1, 0)
+    );
+}
@@ -0,0 +1,43 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn last_closed_position_pnl() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.last_closed_position_pnl(), None);
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.last_closed_position_pnl(), None);
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(110, 0),
+            ask: QuoteCurrency::new(111, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(1, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.last_closed_position_pnl(), None);
+
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(1, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert_eq!(
+        exchange.last_closed_position_pnl(),
+        Some(QuoteCurrency::new(18, 0))
+    );
+
+    // Opening a new position resets it.
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(1, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.last_closed_position_pnl(), None);
+}
@@ -0,0 +1,46 @@
+use const_decimal::Decimal;
+
+use crate::{prelude::*, test_fee_maker, test_fee_taker, DECIMALS};
+
+#[test_case::test_case(Decimal::zero(), false)]
+#[test_case::test_case(Decimal::try_from_scaled(1, 1).unwrap(), true)]
+#[tracing_test::traced_test]
+fn liquidation_buffer(liquidation_buffer: Decimal<i64, DECIMALS>, expect_liquidation: bool) {
+    let contract_spec = ContractSpecification::new(
+        leverage!(1),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
+        test_fee_maker(),
+        test_fee_taker(),
+    )
+    .unwrap()
+    .with_liquidation_buffer(liquidation_buffer)
+    .unwrap();
+    let config = Config::new(QuoteCurrency::new(1000, 0), 10, contract_spec, 3600).unwrap();
+    let mut exchange =
+        ExchangeBuilder::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::new(config).build();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    // The strict liquidation price is 101 * 0.5 = 50.5, with the buffer it's 101 * 0.6 = 60.6.
+    let res = exchange.update_state(&Bba {
+        bid: QuoteCurrency::new(55, 0),
+        ask: QuoteCurrency::new(56, 0),
+        timestamp_exchange_ns: 1.into(),
+    });
+    if expect_liquidation {
+        assert_eq!(res, Err(Error::RiskError(RiskError::Liquidate)));
+        assert_eq!(exchange.position(), &Position::Neutral);
+    } else {
+        assert!(res.is_ok());
+        assert_eq!(exchange.position().quantity(), BaseCurrency::new(2, 0));
+    }
+}
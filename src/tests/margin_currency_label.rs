@@ -0,0 +1,38 @@
+use const_decimal::Decimal;
+
+use crate::{prelude::*, test_fee_maker, test_fee_taker, DECIMALS};
+
+#[test]
+#[tracing_test::traced_test]
+fn margin_currency_label() {
+    let mut contract_spec = ContractSpecification::new(
+        leverage!(1),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
+        test_fee_maker(),
+        test_fee_taker(),
+    )
+    .unwrap();
+    assert_eq!(
+        contract_spec.format_margin(QuoteCurrency::new(1000, 0)),
+        QuoteCurrency::<i64, DECIMALS>::new(1000, 0).to_string()
+    );
+
+    contract_spec.set_margin_currency_label(Some("USDT".to_string()));
+    let labelled = format!(
+        "{} USDT",
+        QuoteCurrency::<i64, DECIMALS>::new(1000, 0).as_ref()
+    );
+    assert_eq!(
+        contract_spec.format_margin(QuoteCurrency::new(1000, 0)),
+        labelled
+    );
+
+    let config = Config::new(QuoteCurrency::new(1000, 0), 10, contract_spec, 3600).unwrap();
+    let exchange =
+        ExchangeBuilder::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::new(config).build();
+    let report = exchange.user_balances_report();
+    assert!(report.contains(&format!("available_balance: {labelled}")));
+    assert!(!report.contains("Quote"));
+}
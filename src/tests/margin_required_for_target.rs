@@ -0,0 +1,59 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn margin_required_for_target_from_flat() {
+    let exchange = mock_exchange_linear();
+
+    assert_eq!(
+        exchange.margin_required_for_target(BaseCurrency::new(5, 0), QuoteCurrency::new(100, 0)),
+        QuoteCurrency::new(500, 0)
+    );
+    assert_eq!(
+        exchange.margin_required_for_target(BaseCurrency::new(-5, 0), QuoteCurrency::new(100, 0)),
+        QuoteCurrency::new(500, 0)
+    );
+    assert_eq!(
+        exchange.margin_required_for_target(BaseCurrency::zero(), QuoteCurrency::new(100, 0)),
+        QuoteCurrency::zero()
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn margin_required_for_target_from_long() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(
+        exchange.user_balances().position_margin,
+        QuoteCurrency::new(202, 0)
+    );
+
+    // Scaling in only requires margin for the additional quantity.
+    assert_eq!(
+        exchange.margin_required_for_target(BaseCurrency::new(5, 0), QuoteCurrency::new(100, 0)),
+        QuoteCurrency::new(300, 0)
+    );
+    // Reducing the position does not require any margin.
+    assert_eq!(
+        exchange.margin_required_for_target(BaseCurrency::new(1, 0), QuoteCurrency::new(100, 0)),
+        QuoteCurrency::zero()
+    );
+    // Flipping to a short releases the margin of the long first.
+    assert_eq!(
+        exchange.margin_required_for_target(BaseCurrency::new(-3, 0), QuoteCurrency::new(100, 0)),
+        QuoteCurrency::new(98, 0)
+    );
+    assert_eq!(
+        exchange.margin_required_for_target(BaseCurrency::new(-1, 0), QuoteCurrency::new(100, 0)),
+        QuoteCurrency::zero()
+    );
+}
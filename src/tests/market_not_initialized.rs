@@ -0,0 +1,34 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn market_not_initialized() {
+    let mut exchange = mock_exchange_linear();
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(100, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    assert_eq!(
+        exchange.submit_limit_order(order.clone()),
+        Err(Error::MarketNotInitialized)
+    );
+    let market_order = MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap();
+    assert_eq!(
+        exchange.submit_market_order(market_order.clone()),
+        Err(Error::MarketNotInitialized)
+    );
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    exchange.submit_limit_order(order).unwrap();
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+    exchange.submit_market_order(market_order).unwrap();
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(1, 0));
+}
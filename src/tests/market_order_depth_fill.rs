@@ -0,0 +1,69 @@
+use crate::{mock_exchange_linear_with_config, prelude::*, DECIMALS};
+
+fn depth(ts: i64) -> Depth<i64, DECIMALS, BaseCurrency<i64, DECIMALS>> {
+    Depth {
+        bids: vec![(QuoteCurrency::new(100, 0), BaseCurrency::new(5, 0))],
+        asks: vec![
+            (QuoteCurrency::new(101, 0), BaseCurrency::new(1, 0)),
+            (QuoteCurrency::new(102, 0), BaseCurrency::new(1, 0)),
+            (QuoteCurrency::new(103, 0), BaseCurrency::new(2, 0)),
+        ],
+        timestamp_exchange_ns: ts.into(),
+    }
+}
+
+#[test_case::test_case(true, QuoteCurrency::new(102, 0))]
+#[test_case::test_case(false, QuoteCurrency::new(101, 0))]
+#[tracing_test::traced_test]
+fn market_order_depth_fill(
+    fill_from_depth: bool,
+    expected_entry_price: QuoteCurrency<i64, DECIMALS>,
+) {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_fill_market_orders_from_depth(fill_from_depth);
+    });
+    exchange.update_state(&depth(0)).unwrap();
+    assert_eq!(exchange.market_state().bid(), QuoteCurrency::new(100, 0));
+    assert_eq!(exchange.market_state().ask(), QuoteCurrency::new(101, 0));
+
+    // Walking the book: 1 @ 101, 1 @ 102 and 1 @ 103.
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(3, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(3, 0));
+    assert_eq!(exchange.position().entry_price(), expected_entry_price);
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn market_order_depth_fill_exhausting_book() {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_fill_market_orders_from_depth(true);
+    });
+    exchange.update_state(&depth(0)).unwrap();
+
+    // Exceeds the 4 units of ask depth, so the remainder fills at the worst level.
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(
+        exchange.position().entry_price(),
+        QuoteCurrency::new(1024, 1)
+    );
+    assert!(exchange.position().entry_price() > exchange.market_state().ask());
+
+    // Any other market update clears the depth, so the touch is used again.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert!(exchange.market_state().ask_depth().is_empty());
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.position().quantity(), BaseCurrency::zero());
+    assert_eq!(
+        exchange.last_market_fill_price(),
+        Some(QuoteCurrency::new(100, 0))
+    );
+}
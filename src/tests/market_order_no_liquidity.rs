@@ -0,0 +1,51 @@
+use crate::{mock_exchange_linear, mock_exchange_linear_with_config, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn market_order_no_liquidity_uninitialized() {
+    let mut exchange = mock_exchange_linear();
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(1, 0)).unwrap();
+    assert_eq!(
+        exchange.submit_market_order(order),
+        Err(Error::MarketNotInitialized)
+    );
+    assert_eq!(exchange.position(), &Position::Neutral);
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn market_order_no_liquidity_empty_depth() {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_fill_market_orders_from_depth(true);
+    });
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    // The ask side of the book has been consumed.
+    exchange
+        .update_state(&Depth {
+            bids: vec![(QuoteCurrency::new(100, 0), BaseCurrency::new(5, 0))],
+            asks: Vec::new(),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap();
+    assert_eq!(
+        exchange.submit_market_order(order),
+        Err(Error::RiskError(RiskError::NoLiquidity))
+    );
+    assert_eq!(exchange.position(), &Position::Neutral);
+
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(1, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(-1, 0));
+    assert_eq!(
+        exchange.last_market_fill_price(),
+        Some(QuoteCurrency::new(100, 0))
+    );
+}
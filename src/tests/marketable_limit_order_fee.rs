@@ -0,0 +1,136 @@
+use const_decimal::Decimal;
+
+use crate::{
+    mock_exchange_linear_with_config, prelude::*, test_fee_maker, test_fee_taker, DECIMALS,
+};
+
+#[test]
+#[tracing_test::traced_test]
+fn marketable_limit_order_fee() {
+    let contract_spec = ContractSpecification::new(
+        leverage!(1),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
+        test_fee_maker(),
+        test_fee_taker(),
+    )
+    .unwrap()
+    .with_fee_taker_limit(Fee::from(Decimal::try_from_scaled(4, 4).unwrap()));
+    let config = Config::new(QuoteCurrency::new(1000, 0), 10, contract_spec, 3600).unwrap();
+    let mut exchange =
+        ExchangeBuilder::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::new(config).build();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    // The limit order crosses the ask, so it takes liquidity at the ask right away.
+    let mut order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(102, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    order.set_re_pricing(RePricing::Marketable);
+    exchange.submit_limit_order(order).unwrap();
+    assert!(exchange.active_limit_orders().is_empty());
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(2, 0));
+
+    let fills = exchange.drain_fills();
+    assert_eq!(fills.len(), 2);
+    assert_eq!(fills[0].price, QuoteCurrency::new(101, 0));
+    assert_eq!(fills[1].price, QuoteCurrency::new(101, 0));
+    // The market order pays the full taker fee of 101 * 0.0006,
+    // the marketable limit order the discounted one of 101 * 0.0004.
+    assert_eq!(fills[0].fee, QuoteCurrency::new(606, 4));
+    assert_eq!(fills[1].fee, QuoteCurrency::new(404, 4));
+    assert_eq!(
+        exchange.user_balances().available_wallet_balance,
+        QuoteCurrency::new(1000, 0) - QuoteCurrency::new(202, 0) - QuoteCurrency::new(101, 3)
+    );
+
+    // Without crossing the ask, the order rests in the book.
+    let mut order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(100, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    order.set_re_pricing(RePricing::Marketable);
+    exchange.submit_limit_order(order).unwrap();
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(2, 0));
+    assert!(exchange.drain_fills().is_empty());
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn marketable_limit_order_margin_and_fee() {
+    let mut exchange = mock_exchange_linear_with_config(|_| {});
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(99, 0),
+            ask: QuoteCurrency::new(100, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    // The margin of 1000 is covered by the balance, but not the taker fee on top.
+    let mut order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(100, 0),
+        BaseCurrency::new(10, 0),
+    )
+    .unwrap();
+    order.set_re_pricing(RePricing::Marketable);
+    assert_eq!(
+        exchange.submit_limit_order(order),
+        Err(Error::RiskError(RiskError::NotEnoughAvailableBalance))
+    );
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert!(exchange.active_limit_orders().is_empty());
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn marketable_limit_order_no_liquidity() {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_fill_market_orders_from_depth(true);
+    });
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    // The ask side of the book has been consumed.
+    exchange
+        .update_state(&Depth {
+            bids: vec![(QuoteCurrency::new(100, 0), BaseCurrency::new(5, 0))],
+            asks: Vec::new(),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+
+    let mut order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(102, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    order.set_re_pricing(RePricing::Marketable);
+    assert_eq!(
+        exchange.submit_limit_order(order),
+        Err(Error::RiskError(RiskError::NoLiquidity))
+    );
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert!(exchange.active_limit_orders().is_empty());
+}
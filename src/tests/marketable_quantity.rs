@@ -0,0 +1,84 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn marketable_quantity_bba() {
+    let mut exchange = mock_exchange_linear();
+    let qty = BaseCurrency::new(3, 0);
+    assert_eq!(
+        exchange.marketable_quantity(Side::Buy, QuoteCurrency::new(101, 0), qty),
+        BaseCurrency::zero()
+    );
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        exchange.marketable_quantity(Side::Buy, QuoteCurrency::new(101, 0), qty),
+        qty
+    );
+    assert_eq!(
+        exchange.marketable_quantity(Side::Buy, QuoteCurrency::new(100, 0), qty),
+        BaseCurrency::zero()
+    );
+    assert_eq!(
+        exchange.marketable_quantity(Side::Sell, QuoteCurrency::new(100, 0), qty),
+        qty
+    );
+    assert_eq!(
+        exchange.marketable_quantity(Side::Sell, QuoteCurrency::new(101, 0), qty),
+        BaseCurrency::zero()
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn marketable_quantity_depth() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Depth {
+            bids: vec![
+                (QuoteCurrency::new(100, 0), BaseCurrency::new(1, 0)),
+                (QuoteCurrency::new(99, 0), BaseCurrency::new(2, 0)),
+            ],
+            asks: vec![
+                (QuoteCurrency::new(101, 0), BaseCurrency::new(1, 0)),
+                (QuoteCurrency::new(102, 0), BaseCurrency::new(1, 0)),
+                (QuoteCurrency::new(103, 0), BaseCurrency::new(2, 0)),
+            ],
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(3, 0);
+
+    // The levels up to the limit price are walked.
+    assert_eq!(
+        exchange.marketable_quantity(Side::Buy, QuoteCurrency::new(100, 0), qty),
+        BaseCurrency::zero()
+    );
+    assert_eq!(
+        exchange.marketable_quantity(Side::Buy, QuoteCurrency::new(101, 0), qty),
+        BaseCurrency::new(1, 0)
+    );
+    assert_eq!(
+        exchange.marketable_quantity(Side::Buy, QuoteCurrency::new(102, 0), qty),
+        BaseCurrency::new(2, 0)
+    );
+    // Capped at the quantity of the hypothetical order.
+    assert_eq!(
+        exchange.marketable_quantity(Side::Buy, QuoteCurrency::new(103, 0), qty),
+        qty
+    );
+    assert_eq!(
+        exchange.marketable_quantity(Side::Sell, QuoteCurrency::new(100, 0), qty),
+        BaseCurrency::new(1, 0)
+    );
+    assert_eq!(
+        exchange.marketable_quantity(Side::Sell, QuoteCurrency::new(95, 0), qty),
+        qty
+    );
+}
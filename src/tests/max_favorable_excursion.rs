@@ -0,0 +1,49 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn max_favorable_excursion() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    for (i, bid) in [110, 120, 105].into_iter().enumerate() {
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(bid, 0),
+                ask: QuoteCurrency::new(bid + 1, 0),
+                timestamp_exchange_ns: (i as i64 + 1).into(),
+            })
+            .unwrap();
+    }
+    assert!(exchange.mfe_distribution().is_empty());
+
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    // The best unrealized pnl was reached at a bid of 120: 5 * (120 - 101) = 95.
+    assert_eq!(exchange.mfe_distribution(), &[QuoteCurrency::new(95, 0)]);
+
+    // The next trade starts tracking from scratch, with a short that only loses.
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(1, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(110, 0),
+            ask: QuoteCurrency::new(111, 0),
+            timestamp_exchange_ns: 10.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(
+        exchange.mfe_distribution(),
+        &[QuoteCurrency::new(95, 0), QuoteCurrency::zero()]
+    );
+}
@@ -0,0 +1,110 @@
+use const_decimal::Decimal;
+
+use crate::{mock_exchange_linear_with_config, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn max_fill_fraction_per_tick() {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_max_fill_fraction_per_tick(Some(Decimal::try_from_scaled(25, 2).unwrap()));
+    });
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(100, 0),
+        BaseCurrency::new(8, 0),
+    )
+    .unwrap();
+    let order_id = exchange.submit_limit_order(order).unwrap().id();
+
+    // Each trade could fill the whole order, but only a quarter of it fills per tick.
+    for (ts, remaining) in [(1, 6), (2, 4), (3, 2)] {
+        let updates = exchange
+            .update_state(&Trade {
+                price: QuoteCurrency::new(99, 0),
+                quantity: BaseCurrency::new(100, 0),
+                side: Side::Sell,
+                timestamp_exchange_ns: ts.into(),
+            })
+            .unwrap();
+        assert_eq!(updates.len(), 1);
+        let LimitOrderUpdate::PartiallyFilled(_, filled_qty) = &updates[0] else {
+            panic!("Expected a partial fill");
+        };
+        assert_eq!(*filled_qty, BaseCurrency::new(2, 0));
+        assert_eq!(
+            exchange
+                .active_limit_orders()
+                .get_by_id(order_id)
+                .unwrap()
+                .remaining_quantity(),
+            BaseCurrency::new(remaining, 0)
+        );
+    }
+    let updates = exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(99, 0),
+            quantity: BaseCurrency::new(100, 0),
+            side: Side::Sell,
+            timestamp_exchange_ns: 4.into(),
+        })
+        .unwrap();
+    assert!(matches!(&updates[0], LimitOrderUpdate::FullyFilled(_)));
+    assert!(exchange.active_limit_orders().is_empty());
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(8, 0));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn max_fill_fraction_per_tick_fills_at_least_one_tick() {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_max_fill_fraction_per_tick(Some(Decimal::try_from_scaled(25, 2).unwrap()));
+    });
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    // A quarter of the order is only half a quantity tick.
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(100, 0),
+        BaseCurrency::new(2, 2),
+    )
+    .unwrap();
+    let order_id = exchange.submit_limit_order(order).unwrap().id();
+
+    let trade = |ts: i64| Trade {
+        price: QuoteCurrency::new(99, 0),
+        quantity: BaseCurrency::new(100, 0),
+        side: Side::Sell,
+        timestamp_exchange_ns: ts.into(),
+    };
+    let updates = exchange.update_state(&trade(1)).unwrap();
+    assert_eq!(updates.len(), 1);
+    let LimitOrderUpdate::PartiallyFilled(_, filled_qty) = &updates[0] else {
+        panic!("Expected a partial fill");
+    };
+    assert_eq!(*filled_qty, BaseCurrency::new(1, 2));
+    assert_eq!(
+        exchange
+            .active_limit_orders()
+            .get_by_id(order_id)
+            .unwrap()
+            .remaining_quantity(),
+        BaseCurrency::new(1, 2)
+    );
+
+    let updates = exchange.update_state(&trade(2)).unwrap();
+    assert!(matches!(&updates[0], LimitOrderUpdate::FullyFilled(_)));
+    assert!(exchange.active_limit_orders().is_empty());
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(2, 2));
+}
@@ -0,0 +1,58 @@
+use crate::{mock_exchange_linear, prelude::*, DECIMALS};
+
+#[test_case::test_case(Side::Buy, BaseCurrency::new(989, 2))]
+#[test_case::test_case(Side::Sell, BaseCurrency::new(999, 2))]
+#[tracing_test::traced_test]
+fn max_openable_qty_flat(side: Side, expected: BaseCurrency<i64, DECIMALS>) {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let max_qty = exchange.max_openable_qty(side);
+    assert_eq!(max_qty, expected);
+
+    let tick_size = BaseCurrency::new(1, 2);
+    let order = MarketOrder::new(side, max_qty + tick_size).unwrap();
+    assert_eq!(
+        exchange.submit_market_order(order),
+        Err(Error::RiskError(RiskError::NotEnoughAvailableBalance))
+    );
+    let order = MarketOrder::new(side, max_qty).unwrap();
+    exchange.submit_market_order(order).unwrap();
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn max_openable_qty_with_position() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    // Closing the long releases its margin, so the full wallet backs the new short.
+    assert_eq!(
+        exchange.max_openable_qty(Side::Sell),
+        BaseCurrency::new(1199, 2)
+    );
+
+    // Only the remaining wallet balance backs the increase of the long.
+    let max_qty = exchange.max_openable_qty(Side::Buy);
+    assert_eq!(max_qty, BaseCurrency::new(789, 2));
+    let order = MarketOrder::new(Side::Buy, max_qty + BaseCurrency::new(1, 2)).unwrap();
+    assert_eq!(
+        exchange.submit_market_order(order),
+        Err(Error::RiskError(RiskError::NotEnoughAvailableBalance))
+    );
+    let order = MarketOrder::new(Side::Buy, max_qty).unwrap();
+    exchange.submit_market_order(order).unwrap();
+}
@@ -0,0 +1,102 @@
+use crate::{mock_exchange_linear_with_config, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn max_orders_per_second() {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_max_orders_per_second(Some(2));
+    });
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 1_000_000_000.into(),
+        })
+        .unwrap();
+
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(90, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    exchange.submit_limit_order(order.clone()).unwrap();
+    let market_order = MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap();
+    exchange.submit_market_order(market_order.clone()).unwrap();
+    assert_eq!(
+        exchange.submit_limit_order(order.clone()),
+        Err(Error::RateLimitExceeded)
+    );
+    assert_eq!(
+        exchange.submit_market_order(market_order),
+        Err(Error::RateLimitExceeded)
+    );
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+
+    // Still within the same second.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 1_999_999_999.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        exchange.submit_limit_order(order.clone()),
+        Err(Error::RateLimitExceeded)
+    );
+
+    // The next second allows new submissions again.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 2_000_000_000.into(),
+        })
+        .unwrap();
+    exchange.submit_limit_order(order.clone()).unwrap();
+    exchange.submit_limit_order(order.clone()).unwrap();
+    assert_eq!(
+        exchange.submit_limit_order(order),
+        Err(Error::RateLimitExceeded)
+    );
+    assert_eq!(exchange.active_limit_orders().len(), 3);
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn max_orders_per_second_excludes_liquidations() {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_max_orders_per_second(Some(2));
+        config.set_record_rejected_orders(true);
+    });
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 1_000_000_000.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    // The bid drops below the liquidation price of 50.5 within the same second.
+    assert_eq!(
+        exchange.update_state(&Bba {
+            bid: QuoteCurrency::new(50, 0),
+            ask: QuoteCurrency::new(51, 0),
+            timestamp_exchange_ns: 1_500_000_000.into(),
+        }),
+        Err(Error::RiskError(RiskError::Liquidate))
+    );
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert!(exchange.rejected_orders().is_empty());
+
+    // The liquidation did not count towards the limit.
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap();
+    exchange.submit_market_order(order.clone()).unwrap();
+    assert_eq!(
+        exchange.submit_market_order(order),
+        Err(Error::RateLimitExceeded)
+    );
+}
@@ -0,0 +1,58 @@
+use crate::{mock_exchange_linear_with_config, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn min_wallet_balance() {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_min_wallet_balance(Some(QuoteCurrency::new(900, 0)));
+    });
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    // The unrealized loss of 55 keeps the equity above the floor.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(90, 0),
+            ask: QuoteCurrency::new(91, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+
+    // The unrealized loss of 105 drops the equity below the floor.
+    let bba = Bba {
+        bid: QuoteCurrency::new(80, 0),
+        ask: QuoteCurrency::new(81, 0),
+        timestamp_exchange_ns: 2.into(),
+    };
+    assert_eq!(exchange.update_state(&bba), Err(Error::AccountBlownUp));
+
+    // The error is terminal.
+    let bba = Bba {
+        bid: QuoteCurrency::new(100, 0),
+        ask: QuoteCurrency::new(101, 0),
+        timestamp_exchange_ns: 3.into(),
+    };
+    assert_eq!(exchange.update_state(&bba), Err(Error::AccountBlownUp));
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(5, 0)).unwrap();
+    assert_eq!(
+        exchange.submit_market_order(order),
+        Err(Error::AccountBlownUp)
+    );
+    let order = LimitOrder::new(
+        Side::Sell,
+        QuoteCurrency::new(110, 0),
+        BaseCurrency::new(5, 0),
+    )
+    .unwrap();
+    assert_eq!(
+        exchange.submit_limit_order(order),
+        Err(Error::AccountBlownUp)
+    );
+}
@@ -1,7 +1,76 @@
+mod account_tracker_update_every_n_ticks;
 mod amend;
+mod batch_order_margin_updates;
+mod bba;
+mod bba_with_trades;
+mod break_even_move_bps;
+mod break_even_with_funding;
 mod cancel_limit_order;
+mod cancel_orders_by_predicate;
+mod credit_unrealized_pnl;
+mod current_drawdown;
+mod current_leverage;
+mod drain_fills;
+mod duplicate_user_order_id;
+mod edge_ratio;
+mod elapsed_ns;
+mod fees;
+mod funding_aware_unrealized_pnl;
+mod funding_rate_series;
+mod hedge_mode;
+mod last_closed_position_pnl;
+mod liquidation_buffer;
+mod margin_currency_label;
+mod margin_required_for_target;
+mod market_not_initialized;
+mod market_order_depth_fill;
+mod market_order_no_liquidity;
+mod marketable_limit_order_fee;
+mod marketable_quantity;
+mod max_favorable_excursion;
+mod max_fill_fraction_per_tick;
+mod max_openable_qty;
+mod max_orders_per_second;
+mod min_wallet_balance;
+mod num_ticks_processed;
+mod order_from_notional;
+mod order_update_ordering;
+mod orders_submitted_this_step;
+mod partial_liquidation;
 mod partial_order_fill;
+mod position_fees;
+mod position_flip_fees;
+mod position_return_pct;
+mod position_snapshot;
+mod position_twap_mark;
+mod position_value;
+mod potential_leverage;
+mod profit_withdrawal;
+mod queue_position;
+mod realized_pnl_in_quote;
+mod realized_pnl_of_order;
+mod realized_sharpe;
+mod rebates;
+mod reduce_limit_order;
+mod reduce_only_margin_ratio;
+mod reduce_position;
+mod reentry_cooldown;
+mod rejected_orders;
+mod replace_order;
+mod reserve_outstanding_fees;
+mod reset_account_tracker;
+mod rounded_unrealized_pnl;
+mod self_trade_prevention;
+mod separate_realized_pnl_account;
+mod settle_at_expiry;
+mod settle_funding_period;
+mod sharpe_excludes_unrealized_pnl;
+mod stop_limit_order;
 mod submit_limit_buy_order;
 mod submit_limit_sell_order;
 mod submit_market_buy_order;
 mod submit_market_sell_order;
+mod total_return_pct;
+mod trading_halt;
+mod trailing_stop;
+mod update_state_with_trades;
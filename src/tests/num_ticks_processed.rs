@@ -0,0 +1,20 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn num_ticks_processed() {
+    let mut exchange = mock_exchange_linear();
+    assert_eq!(exchange.num_ticks_processed(), 0);
+
+    for i in 0..5 {
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(100 + i, 0),
+                ask: QuoteCurrency::new(101 + i, 0),
+                timestamp_exchange_ns: i.into(),
+            })
+            .unwrap();
+        assert_eq!(exchange.num_ticks_processed(), i as u64 + 1);
+        assert_eq!(exchange.market_state().num_updates(), i as u64 + 1);
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn market_order_from_notional() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let quantity_filter = exchange.config().contract_spec().quantity_filter().clone();
+
+    // 500 USD at the ask of 101 is rounded down to the quantity tick size of 0.01.
+    let order = MarketOrder::from_notional(
+        Side::Buy,
+        QuoteCurrency::new(500, 0),
+        QuoteCurrency::new(101, 0),
+        &quantity_filter,
+    )
+    .unwrap();
+    assert_eq!(order.quantity(), BaseCurrency::new(495, 2));
+
+    let filled = exchange.submit_market_order(order).unwrap();
+    assert_eq!(filled.state().filled_qty(), BaseCurrency::new(495, 2));
+    assert_eq!(
+        QuoteCurrency::convert_from(filled.state().filled_qty(), filled.state().avg_fill_price()),
+        QuoteCurrency::new(49995, 2)
+    );
+
+    assert_eq!(
+        MarketOrder::<_, 5, _, NoUserOrderId, _>::from_notional(
+            Side::Buy,
+            QuoteCurrency::new(5, 1),
+            QuoteCurrency::new(101, 0),
+            &quantity_filter,
+        ),
+        Err(OrderError::QuantityTooLow)
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn limit_order_from_notional() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let quantity_filter = exchange.config().contract_spec().quantity_filter().clone();
+
+    let order = LimitOrder::from_notional(
+        Side::Buy,
+        QuoteCurrency::new(100, 0),
+        QuoteCurrency::new(250, 0),
+        &quantity_filter,
+    )
+    .unwrap();
+    assert_eq!(order.remaining_quantity(), BaseCurrency::new(25, 1));
+    exchange.submit_limit_order(order).unwrap();
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+
+    assert_eq!(
+        LimitOrder::from_notional(
+            Side::Buy,
+            QuoteCurrency::zero(),
+            QuoteCurrency::new(250, 0),
+            &quantity_filter,
+        ),
+        Err(OrderError::LimitPriceLTEZero)
+    );
+}
@@ -0,0 +1,39 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn order_update_ordering() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    for price in [96, 100, 98, 97, 99] {
+        let order = LimitOrder::new(
+            Side::Buy,
+            QuoteCurrency::new(price, 0),
+            BaseCurrency::new(1, 0),
+        )
+        .unwrap();
+        exchange.submit_limit_order(order).unwrap();
+    }
+    assert_eq!(exchange.active_limit_orders().len(), 5);
+
+    let updates = exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(95, 0),
+            quantity: BaseCurrency::new(10, 0),
+            side: Side::Sell,
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert_eq!(updates.len(), 5);
+    let ids: Vec<OrderId> = updates.iter().map(|update| update.order_id()).collect();
+    assert_eq!(ids, (0..5).map(OrderId::from).collect::<Vec<_>>());
+    assert!(updates
+        .iter()
+        .all(|update| matches!(update, LimitOrderUpdate::FullyFilled(_))));
+}
@@ -0,0 +1,43 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn orders_submitted_this_step() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert!(exchange.orders_submitted_this_step().is_empty());
+
+    let order = LimitOrder::new(Side::Buy, QuoteCurrency::new(99, 0), BaseCurrency::one()).unwrap();
+    let first = exchange.submit_limit_order(order).unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::one()).unwrap();
+    let second = exchange.submit_market_order(order).unwrap();
+    assert_eq!(
+        exchange.orders_submitted_this_step(),
+        &[first.id(), second.state().meta().id()]
+    );
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert!(exchange.orders_submitted_this_step().is_empty());
+
+    let order =
+        LimitOrder::new(Side::Sell, QuoteCurrency::new(102, 0), BaseCurrency::one()).unwrap();
+    let third = exchange.submit_limit_order(order).unwrap();
+    assert_eq!(exchange.orders_submitted_this_step(), &[third.id()]);
+    // The order of the prior step is still active, but not part of this step.
+    assert!(exchange
+        .active_limit_orders()
+        .get_by_id(first.id())
+        .is_some());
+}
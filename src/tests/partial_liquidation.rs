@@ -0,0 +1,153 @@
+use const_decimal::Decimal;
+
+use crate::{prelude::*, test_fee_maker, test_fee_taker, DECIMALS};
+
+#[test]
+#[tracing_test::traced_test]
+fn partial_liquidation() {
+    let contract_spec = ContractSpecification::new(
+        leverage!(5),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
+        test_fee_maker(),
+        test_fee_taker(),
+    )
+    .unwrap();
+    let mut config = Config::new(QuoteCurrency::new(1000, 0), 10, contract_spec, 3600).unwrap();
+    config.set_partial_liquidation_buffer(Some(Decimal::try_from_scaled(5, 2).unwrap()));
+    let mut exchange =
+        ExchangeBuilder::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::new(config).build();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(49, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    // The bid stays above the liquidation price of 101 * (1 - 0.1) = 90.9.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(91, 0),
+            ask: QuoteCurrency::new(92, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(49, 0));
+
+    // The bid breaches the liquidation price, leaving an equity of 997.0306 - 539 = 458.0306.
+    let bba = Bba {
+        bid: QuoteCurrency::new(90, 0),
+        ask: QuoteCurrency::new(91, 0),
+        timestamp_exchange_ns: 2.into(),
+    };
+    assert_eq!(
+        exchange.update_state(&bba),
+        Err(Error::RiskError(RiskError::Liquidate))
+    );
+    // Only the quantity above 458.0306 / 0.15 / 90.5 = 33.74 is closed.
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(3374, 2));
+
+    // Once the bid recovers above the liquidation price, the rest of the position is kept.
+    let bba = Bba {
+        bid: QuoteCurrency::new(95, 0),
+        ask: QuoteCurrency::new(96, 0),
+        timestamp_exchange_ns: 3.into(),
+    };
+    exchange.update_state(&bba).unwrap();
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(3374, 2));
+}
+
+#[test_case::test_case(Decimal::zero(), BaseCurrency::new(49, 0))]
+#[test_case::test_case(Decimal::try_from_scaled(2, 2).unwrap(), BaseCurrency::new(4007, 2))]
+#[tracing_test::traced_test]
+fn partial_liquidation_with_liquidation_buffer(
+    liquidation_buffer: Decimal<i64, DECIMALS>,
+    expected_qty: BaseCurrency<i64, DECIMALS>,
+) {
+    let contract_spec = ContractSpecification::new(
+        leverage!(5),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
+        test_fee_maker(),
+        test_fee_taker(),
+    )
+    .unwrap()
+    .with_liquidation_buffer(liquidation_buffer)
+    .unwrap();
+    let mut config = Config::new(QuoteCurrency::new(1000, 0), 10, contract_spec, 3600).unwrap();
+    config.set_partial_liquidation_buffer(Some(Decimal::try_from_scaled(5, 2).unwrap()));
+    let mut exchange =
+        ExchangeBuilder::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::new(config).build();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(49, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    // The strict liquidation price is 101 * 0.9 = 90.9, with the buffer it's 101 * 0.92 = 92.92.
+    let res = exchange.update_state(&Bba {
+        bid: QuoteCurrency::new(92, 0),
+        ask: QuoteCurrency::new(93, 0),
+        timestamp_exchange_ns: 1.into(),
+    });
+    if liquidation_buffer == Decimal::zero() {
+        assert!(res.is_ok());
+    } else {
+        assert_eq!(res, Err(Error::RiskError(RiskError::Liquidate)));
+    }
+    // The breach closes only the quantity above 556.0306 / 0.15 / 92.5 = 40.07.
+    assert_eq!(exchange.position().quantity(), expected_qty);
+}
+
+#[test_case::test_case(BaseCurrency::new(10, 0), BaseCurrency::new(3374, 2))]
+#[test_case::test_case(BaseCurrency::new(20, 0), BaseCurrency::new(29, 0))]
+#[test_case::test_case(BaseCurrency::new(49, 0), BaseCurrency::zero())]
+#[tracing_test::traced_test]
+fn partial_liquidation_min_quantity(
+    min_quantity: BaseCurrency<i64, DECIMALS>,
+    expected_qty: BaseCurrency<i64, DECIMALS>,
+) {
+    let contract_spec = ContractSpecification::new(
+        leverage!(5),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(Some(min_quantity), None, BaseCurrency::new(1, 2)).unwrap(),
+        test_fee_maker(),
+        test_fee_taker(),
+    )
+    .unwrap();
+    let mut config = Config::new(QuoteCurrency::new(1000, 0), 10, contract_spec, 3600).unwrap();
+    config.set_partial_liquidation_buffer(Some(Decimal::try_from_scaled(5, 2).unwrap()));
+    let mut exchange =
+        ExchangeBuilder::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::new(config).build();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(49, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    // The breach would close 15.26, which is raised to the `min_quantity`.
+    let bba = Bba {
+        bid: QuoteCurrency::new(90, 0),
+        ask: QuoteCurrency::new(91, 0),
+        timestamp_exchange_ns: 1.into(),
+    };
+    assert_eq!(
+        exchange.update_state(&bba),
+        Err(Error::RiskError(RiskError::Liquidate))
+    );
+    assert_eq!(exchange.position().quantity(), expected_qty);
+}
@@ -1,6 +1,7 @@
+use const_decimal::Decimal;
 use test_case::test_case;
 
-use crate::{mock_exchange_linear, prelude::*, DECIMALS};
+use crate::{mock_exchange_linear, prelude::*, test_fee_maker, test_fee_taker, DECIMALS};
 
 #[tracing_test::traced_test]
 #[test_case(QuoteCurrency::new(100, 0), BaseCurrency::new(2, 0), Side::Buy, QuoteCurrency::new(99, 0); "With buy order")]
@@ -42,6 +43,215 @@ fn partial_limit_order_fill(
     assert!(order
         .fill(qty / BaseCurrency::new(2, 0), ts.into())
         .is_none());
-    let expected_order_update = LimitOrderUpdate::PartiallyFilled(order);
+    let expected_order_update =
+        LimitOrderUpdate::PartiallyFilled(order, qty / BaseCurrency::new(2, 0));
     assert_eq!(exec_orders[0], expected_order_update);
 }
+
+#[tracing_test::traced_test]
+#[test]
+fn partial_limit_order_fill_rounded_to_quantity_tick() {
+    let mut exchange = mock_exchange_linear();
+
+    assert!(exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 1.into()
+        })
+        .unwrap()
+        .is_empty());
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(100, 0),
+        BaseCurrency::new(2, 0),
+    )
+    .unwrap();
+    exchange.submit_limit_order(order).unwrap();
+
+    // The quantity tick is 0.01, so a fill of 0.015 gets rounded down to 0.01.
+    let exec_orders = exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(99, 0),
+            quantity: BaseCurrency::new(15, 3),
+            side: Side::Sell,
+            timestamp_exchange_ns: 2.into(),
+        })
+        .unwrap();
+    assert_eq!(exec_orders.len(), 1);
+    let order = exchange.active_limit_orders().values().next().unwrap();
+    assert_eq!(order.filled_quantity(), BaseCurrency::new(1, 2));
+    assert_eq!(order.remaining_quantity(), BaseCurrency::new(199, 2));
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(1, 2));
+
+    // A fill that rounds to zero does not touch the order.
+    let exec_orders = exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(99, 0),
+            quantity: BaseCurrency::new(5, 3),
+            side: Side::Sell,
+            timestamp_exchange_ns: 3.into(),
+        })
+        .unwrap();
+    assert!(exec_orders.is_empty());
+    let order = exchange.active_limit_orders().values().next().unwrap();
+    assert_eq!(order.filled_quantity(), BaseCurrency::new(1, 2));
+    assert_eq!(order.remaining_quantity(), BaseCurrency::new(199, 2));
+}
+
+#[tracing_test::traced_test]
+#[test]
+fn partial_limit_order_fill_rounded_to_notional_tick() {
+    let contract_spec = ContractSpecification::new(
+        leverage!(1),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, None, BaseCurrency::new(1, 0))
+            .unwrap()
+            .with_notional_mode(),
+        test_fee_maker(),
+        test_fee_taker(),
+    )
+    .unwrap();
+    let config = Config::new(QuoteCurrency::new(1000, 0), 10, contract_spec, 3600).unwrap();
+    let mut exchange =
+        ExchangeBuilder::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::new(config).build();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(100, 0),
+        BaseCurrency::new(2, 0),
+    )
+    .unwrap();
+    exchange.submit_limit_order(order).unwrap();
+
+    // The notional tick of 1 is a quantity of 0.01 at the limit price of 100.
+    let exec_orders = exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(99, 0),
+            quantity: BaseCurrency::new(15, 3),
+            side: Side::Sell,
+            timestamp_exchange_ns: 2.into(),
+        })
+        .unwrap();
+    assert_eq!(exec_orders.len(), 1);
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(1, 2));
+}
+
+#[tracing_test::traced_test]
+#[test]
+fn partial_limit_order_fill_incremental_quantity() {
+    let mut exchange = mock_exchange_linear();
+    assert!(exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 1.into()
+        })
+        .unwrap()
+        .is_empty());
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(100, 0),
+        BaseCurrency::new(3, 0),
+    )
+    .unwrap();
+    exchange.submit_limit_order(order).unwrap();
+
+    // Each partial fill reports the quantity filled by it, next to the cumulative quantity.
+    for (ts, trade_qty, cumulative_qty) in [(2, 10, 10), (3, 15, 25)] {
+        let exec_orders = exchange
+            .update_state(&Trade {
+                price: QuoteCurrency::new(99, 0),
+                quantity: BaseCurrency::new(trade_qty, 1),
+                side: Side::Sell,
+                timestamp_exchange_ns: ts.into(),
+            })
+            .unwrap();
+        assert_eq!(exec_orders.len(), 1);
+        let LimitOrderUpdate::PartiallyFilled(order, filled_qty) = &exec_orders[0] else {
+            panic!("Expected a partial fill");
+        };
+        assert_eq!(*filled_qty, BaseCurrency::new(trade_qty, 1));
+        assert_eq!(
+            order.filled_quantity(),
+            BaseCurrency::new(cumulative_qty, 1)
+        );
+    }
+}
+
+#[tracing_test::traced_test]
+#[test]
+fn partial_limit_order_fill_min_fill_qty() {
+    let contract_spec = ContractSpecification::new(
+        leverage!(1),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
+        test_fee_maker(),
+        test_fee_taker(),
+    )
+    .unwrap();
+    let mut config = Config::new(QuoteCurrency::new(1000, 0), 10, contract_spec, 3600).unwrap();
+    config.set_min_fill_qty(Some(BaseCurrency::new(5, 1)));
+    let mut exchange =
+        ExchangeBuilder::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::new(config).build();
+
+    assert!(exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 1.into()
+        })
+        .unwrap()
+        .is_empty());
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(100, 0),
+        BaseCurrency::new(2, 0),
+    )
+    .unwrap();
+    exchange.submit_limit_order(order).unwrap();
+
+    // Below the minimum fill quantity, so the fill is deferred.
+    assert!(exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(99, 0),
+            quantity: BaseCurrency::new(1, 1),
+            side: Side::Sell,
+            timestamp_exchange_ns: 2.into(),
+        })
+        .unwrap()
+        .is_empty());
+    assert_eq!(exchange.position(), &Position::Neutral);
+
+    let exec_orders = exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(99, 0),
+            quantity: BaseCurrency::new(17, 1),
+            side: Side::Sell,
+            timestamp_exchange_ns: 3.into(),
+        })
+        .unwrap();
+    assert_eq!(exec_orders.len(), 1);
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(17, 1));
+
+    // The remaining quantity of 0.3 may be filled even though its below the threshold.
+    let exec_orders = exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(99, 0),
+            quantity: BaseCurrency::new(1, 0),
+            side: Side::Sell,
+            timestamp_exchange_ns: 4.into(),
+        })
+        .unwrap();
+    assert_eq!(exec_orders.len(), 1);
+    assert!(matches!(exec_orders[0], LimitOrderUpdate::FullyFilled(_)));
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(2, 0));
+}
@@ -0,0 +1,25 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn position_fees() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.position_fees(), QuoteCurrency::zero());
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    // 2 * 101 * 0.0006 taker fee.
+    assert_eq!(exchange.position_fees(), QuoteCurrency::new(1212, 4));
+
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert_eq!(exchange.position_fees(), QuoteCurrency::zero());
+}
@@ -0,0 +1,68 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn position_flip_fees() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    // 2 * 101 * 0.0006 taker fee.
+    assert_eq!(exchange.position_fees(), QuoteCurrency::new(1212, 4));
+    exchange.drain_fills();
+
+    // Flipping from long 2 to short 3 is charged on the full traded quantity of 5,
+    // covering both the closing and the opening leg.
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(-3, 0));
+    let fills = exchange.drain_fills();
+    assert_eq!(fills.len(), 1);
+    assert_eq!(fills[0].fee, QuoteCurrency::new(3, 1));
+    assert_eq!(exchange.fees_paid(), QuoteCurrency::new(4212, 4));
+    // The fee of the flip is paid when closing, so the new position carries none.
+    assert_eq!(exchange.position_fees(), QuoteCurrency::zero());
+
+    // Flipping back from short 3 to long 3 at the ask: 6 * 101 * 0.0006.
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(6, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(3, 0));
+    let fills = exchange.drain_fills();
+    assert_eq!(fills[0].fee, QuoteCurrency::new(3636, 4));
+    assert_eq!(exchange.fees_paid(), QuoteCurrency::new(7848, 4));
+    assert_eq!(exchange.position_fees(), QuoteCurrency::zero());
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn position_flip_fee_equals_fee_of_full_size() {
+    let bba = Bba {
+        bid: QuoteCurrency::new(100, 0),
+        ask: QuoteCurrency::new(101, 0),
+        timestamp_exchange_ns: 0.into(),
+    };
+    let mut flipping = mock_exchange_linear();
+    flipping.update_state(&bba).unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    flipping.submit_market_order(order).unwrap();
+    let fees_before_flip = flipping.fees_paid() + flipping.position_fees();
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(5, 0)).unwrap();
+    flipping.submit_market_order(order).unwrap();
+
+    let mut opening = mock_exchange_linear();
+    opening.update_state(&bba).unwrap();
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(5, 0)).unwrap();
+    opening.submit_market_order(order).unwrap();
+
+    assert_eq!(
+        flipping.fees_paid() + flipping.position_fees() - fees_before_flip,
+        opening.fees_paid() + opening.position_fees()
+    );
+}
@@ -0,0 +1,43 @@
+use const_decimal::Decimal;
+
+use crate::{prelude::*, test_fee_maker, test_fee_taker, DECIMALS};
+
+#[test]
+#[tracing_test::traced_test]
+fn position_return_pct() {
+    let contract_spec = ContractSpecification::new(
+        leverage!(5),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
+        test_fee_maker(),
+        test_fee_taker(),
+    )
+    .unwrap();
+    let config = Config::new(QuoteCurrency::new(1000, 0), 10, contract_spec, 3600).unwrap();
+    let mut exchange =
+        ExchangeBuilder::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::new(config).build();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.position_return_pct(), 0.0);
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(110, 0),
+            ask: QuoteCurrency::new(111, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+
+    // An unrealized pnl of 45 on an initial margin of 505 / 5.
+    assert!((exchange.position_return_pct() - 45.0 / 101.0).abs() < 1e-9);
+    let price_return = 9.0 / 101.0;
+    assert!((exchange.position_return_pct() - 5.0 * price_return).abs() < 1e-9);
+}
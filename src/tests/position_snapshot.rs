@@ -0,0 +1,45 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn position_snapshot_long() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let snapshot = exchange.position_snapshot();
+    assert_eq!(snapshot.side, None);
+    assert_eq!(snapshot.quantity, BaseCurrency::zero());
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(110, 0),
+            ask: QuoteCurrency::new(111, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+
+    let snapshot = exchange.position_snapshot();
+    assert_eq!(snapshot.side, Some(Side::Buy));
+    assert_eq!(snapshot.quantity, exchange.position().quantity());
+    assert_eq!(snapshot.entry_price, exchange.position().entry_price());
+    assert_eq!(
+        snapshot.unrealized_pnl,
+        exchange
+            .position()
+            .unrealized_pnl(QuoteCurrency::new(110, 0), QuoteCurrency::new(111, 0))
+    );
+    assert_eq!(snapshot.unrealized_pnl, QuoteCurrency::new(18, 0));
+    assert_eq!(snapshot.margin, exchange.user_balances().position_margin);
+
+    let serialized = ron::to_string(&snapshot).unwrap();
+    let deserialized: PositionSnapshot<i64, 5, BaseCurrency<i64, 5>> =
+        ron::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, snapshot);
+}
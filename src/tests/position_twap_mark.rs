@@ -0,0 +1,69 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn position_twap_mark() {
+    let mut exchange = mock_exchange_linear();
+    assert!(exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into()
+        })
+        .unwrap()
+        .is_empty());
+    assert_eq!(exchange.position_twap_mark(), None);
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.position_twap_mark(), None);
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(110, 0),
+            ask: QuoteCurrency::new(111, 0),
+            timestamp_exchange_ns: 10.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        exchange.position_twap_mark(),
+        Some(QuoteCurrency::new(1005, 1))
+    );
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(104, 0),
+            ask: QuoteCurrency::new(105, 0),
+            timestamp_exchange_ns: 20.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        exchange.position_twap_mark(),
+        Some(QuoteCurrency::new(1055, 1))
+    );
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(104, 0),
+            ask: QuoteCurrency::new(105, 0),
+            timestamp_exchange_ns: 40.into(),
+        })
+        .unwrap();
+    // (100.5 * 10 + 110.5 * 10 + 104.5 * 20) / 40
+    assert_eq!(
+        exchange.position_twap_mark(),
+        Some(QuoteCurrency::new(105, 0))
+    );
+
+    // Once the position is closed, the twap resets.
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(104, 0),
+            ask: QuoteCurrency::new(105, 0),
+            timestamp_exchange_ns: 50.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.position_twap_mark(), None);
+}
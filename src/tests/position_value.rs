@@ -0,0 +1,34 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn position_value() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.position_value(), QuoteCurrency::zero());
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(110, 0),
+            ask: QuoteCurrency::new(111, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+
+    let notional = exchange.position().total_cost();
+    assert_eq!(notional, QuoteCurrency::new(202, 0));
+    let upnl = exchange
+        .position()
+        .unrealized_pnl(QuoteCurrency::new(110, 0), QuoteCurrency::new(111, 0));
+    assert_eq!(upnl, QuoteCurrency::new(18, 0));
+    assert_eq!(exchange.position_value(), notional + upnl);
+    assert_eq!(exchange.position_value(), QuoteCurrency::new(220, 0));
+}
@@ -0,0 +1,38 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn potential_leverage() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.potential_leverage(), 0.0);
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.potential_leverage(), exchange.current_leverage());
+
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(99, 0),
+        BaseCurrency::new(3, 0),
+    )
+    .unwrap();
+    exchange.submit_limit_order(order).unwrap();
+    let order = LimitOrder::new(
+        Side::Sell,
+        QuoteCurrency::new(103, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    exchange.submit_limit_order(order).unwrap();
+
+    // The buy orders would grow the long position from 2 to 5.
+    assert!(exchange.potential_leverage() > exchange.current_leverage());
+    assert!((exchange.potential_leverage() / exchange.current_leverage() - 2.5).abs() < 1e-9);
+}
@@ -0,0 +1,101 @@
+use const_decimal::Decimal;
+
+use crate::{mock_exchange_linear_with_config, prelude::*, DECIMALS};
+
+#[test]
+#[tracing_test::traced_test]
+fn profit_withdrawal() {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_profit_withdrawal(Some(
+            ProfitWithdrawal::new(Decimal::try_from_scaled(5, 1).unwrap(), 100).unwrap(),
+        ));
+    });
+    let update = |exchange: &mut Exchange<_, DECIMALS, _, _, _, _>, bid, ask, ts: i64| {
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(bid, 0),
+                ask: QuoteCurrency::new(ask, 0),
+                timestamp_exchange_ns: ts.into(),
+            })
+            .unwrap();
+    };
+    let trade = |exchange: &mut Exchange<_, DECIMALS, _, _, _, _>, side| {
+        let order = MarketOrder::new(side, BaseCurrency::new(1, 0)).unwrap();
+        exchange.submit_market_order(order).unwrap();
+    };
+
+    // The first interval realizes a profit of 10, half of which is withdrawn once it elapses.
+    update(&mut exchange, 100, 101, 0);
+    trade(&mut exchange, Side::Buy);
+    update(&mut exchange, 111, 112, 50);
+    trade(&mut exchange, Side::Sell);
+    assert_eq!(exchange.withdrawn_profits(), QuoteCurrency::zero());
+    update(&mut exchange, 111, 112, 100);
+    assert_eq!(exchange.withdrawn_profits(), QuoteCurrency::new(5, 0));
+    assert_eq!(
+        exchange.user_balances().available_wallet_balance,
+        QuoteCurrency::new(10048728, 4)
+    );
+
+    // Without the withdrawal the balance would suffice for this order.
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(9, 0)).unwrap();
+    assert_eq!(
+        exchange.submit_market_order(order),
+        Err(Error::RiskError(RiskError::NotEnoughAvailableBalance))
+    );
+
+    // The second interval realizes another profit of 10.
+    trade(&mut exchange, Side::Buy);
+    update(&mut exchange, 122, 123, 150);
+    trade(&mut exchange, Side::Sell);
+    update(&mut exchange, 122, 123, 199);
+    assert_eq!(exchange.withdrawn_profits(), QuoteCurrency::new(5, 0));
+    update(&mut exchange, 122, 123, 200);
+    assert_eq!(exchange.withdrawn_profits(), QuoteCurrency::new(10, 0));
+    assert_eq!(
+        exchange.user_balances().available_wallet_balance,
+        QuoteCurrency::new(10097324, 4)
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn profit_withdrawal_with_pending_pnl() {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_profit_withdrawal(Some(
+            ProfitWithdrawal::new(Decimal::try_from_scaled(5, 1).unwrap(), 100).unwrap(),
+        ));
+        config.set_pnl_realization_policy(PnlRealizationPolicy::OnFullClose);
+    });
+    let update = |exchange: &mut Exchange<_, DECIMALS, _, _, _, _>, bid, ask, ts: i64| {
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(bid, 0),
+                ask: QuoteCurrency::new(ask, 0),
+                timestamp_exchange_ns: ts.into(),
+            })
+            .unwrap();
+    };
+    let trade = |exchange: &mut Exchange<_, DECIMALS, _, _, _, _>, side, qty| {
+        let order = MarketOrder::new(side, BaseCurrency::new(qty, 0)).unwrap();
+        exchange.submit_market_order(order).unwrap();
+    };
+
+    // The partial close realizes a profit of 10, which stays pending while the position is open.
+    update(&mut exchange, 100, 101, 0);
+    trade(&mut exchange, Side::Buy, 2);
+    update(&mut exchange, 111, 112, 50);
+    trade(&mut exchange, Side::Sell, 1);
+    assert_eq!(exchange.pending_pnl(), QuoteCurrency::new(10, 0));
+    update(&mut exchange, 111, 112, 100);
+    assert_eq!(exchange.withdrawn_profits(), QuoteCurrency::zero());
+
+    // Fully closing the position moves the pending profit into the wallet,
+    // so half of the profit of 20 is withdrawn once the next interval elapses.
+    trade(&mut exchange, Side::Sell, 1);
+    assert_eq!(exchange.pending_pnl(), QuoteCurrency::zero());
+    update(&mut exchange, 111, 112, 199);
+    assert_eq!(exchange.withdrawn_profits(), QuoteCurrency::zero());
+    update(&mut exchange, 111, 112, 200);
+    assert_eq!(exchange.withdrawn_profits(), QuoteCurrency::new(10, 0));
+}
@@ -0,0 +1,146 @@
+use crate::{mock_exchange_linear, mock_exchange_linear_with_config, prelude::*, DECIMALS};
+
+fn trade(price: i64, qty: i64, ts: i64) -> Trade<i64, DECIMALS, BaseCurrency<i64, DECIMALS>> {
+    Trade {
+        price: QuoteCurrency::new(price, 0),
+        quantity: BaseCurrency::new(qty, 0),
+        side: Side::Sell,
+        timestamp_exchange_ns: ts.into(),
+    }
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn queue_position_fills_after_volume_ahead() {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_queue_position_model(true);
+    });
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(100, 0),
+        BaseCurrency::new(2, 0),
+    )
+    .unwrap();
+    let order = exchange
+        .submit_limit_order_with_volume_ahead(order, BaseCurrency::new(5, 0))
+        .unwrap();
+    assert_eq!(
+        order.state().queue_position().unwrap().volume_ahead(),
+        BaseCurrency::new(5, 0)
+    );
+
+    // The traded quantity is still consumed by the volume ahead of the order.
+    assert!(exchange.update_state(&trade(100, 3, 1)).unwrap().is_empty());
+    let active_order = exchange
+        .active_limit_orders()
+        .get_by_id(order.id())
+        .unwrap();
+    assert_eq!(
+        active_order
+            .state()
+            .queue_position()
+            .unwrap()
+            .remaining_volume_ahead(),
+        BaseCurrency::new(2, 0)
+    );
+    assert_eq!(exchange.position(), &Position::Neutral);
+
+    // One contract trades through the remaining volume ahead.
+    let updates = exchange.update_state(&trade(100, 3, 2)).unwrap();
+    assert_eq!(updates.len(), 1);
+    assert!(matches!(
+        updates[0],
+        LimitOrderUpdate::PartiallyFilled(_, _)
+    ));
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(1, 0));
+
+    // Now the order is at the front of the queue.
+    let updates = exchange.update_state(&trade(100, 4, 3)).unwrap();
+    assert_eq!(updates.len(), 1);
+    assert!(matches!(updates[0], LimitOrderUpdate::FullyFilled(_)));
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(2, 0));
+    assert!(exchange.active_limit_orders().is_empty());
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn queue_position_disabled_by_default() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(100, 0),
+        BaseCurrency::new(2, 0),
+    )
+    .unwrap();
+    let order = exchange.submit_limit_order(order).unwrap();
+    assert_eq!(order.state().queue_position(), &None);
+
+    // The strict model assumes the worst queue position, so trades at the limit price do not fill.
+    assert!(exchange
+        .update_state(&trade(100, 10, 1))
+        .unwrap()
+        .is_empty());
+    assert_eq!(exchange.position(), &Position::Neutral);
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn queue_position_deferred_fill_keeps_queue_progress() {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_queue_position_model(true);
+        config.set_min_fill_qty(Some(BaseCurrency::new(3, 0)));
+    });
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(100, 0),
+        BaseCurrency::new(4, 0),
+    )
+    .unwrap();
+    let order = exchange
+        .submit_limit_order_with_volume_ahead(order, BaseCurrency::new(5, 0))
+        .unwrap();
+
+    // Only one contract trades through the volume ahead, which is below the `min_fill_qty`.
+    assert!(exchange.update_state(&trade(100, 6, 1)).unwrap().is_empty());
+    assert_eq!(exchange.position(), &Position::Neutral);
+    let queue_position = exchange
+        .active_limit_orders()
+        .get_by_id(order.id())
+        .unwrap()
+        .state()
+        .queue_position()
+        .unwrap();
+    assert_eq!(
+        queue_position.remaining_volume_ahead(),
+        BaseCurrency::zero()
+    );
+    assert_eq!(queue_position.traded_volume(), BaseCurrency::new(5, 0));
+
+    // The order stays at the front of the queue.
+    let updates = exchange.update_state(&trade(100, 4, 2)).unwrap();
+    assert_eq!(updates.len(), 1);
+    assert!(matches!(updates[0], LimitOrderUpdate::FullyFilled(_)));
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(4, 0));
+}
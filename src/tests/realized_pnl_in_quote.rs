@@ -0,0 +1,45 @@
+use crate::{mock_exchange_inverse, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn realized_pnl_in_quote() {
+    let mut exchange = mock_exchange_inverse(BaseCurrency::new(20, 0));
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(99, 0),
+            ask: QuoteCurrency::new(100, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        exchange.realized_pnl_in_quote(QuoteCurrency::new(100, 0)),
+        QuoteCurrency::zero()
+    );
+
+    let order = MarketOrder::new(Side::Buy, QuoteCurrency::new(1000, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(125, 0),
+            ask: QuoteCurrency::new(126, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Sell, QuoteCurrency::new(1000, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.position(), &Position::Neutral);
+
+    // The round trip realized 1000 / 100 - 1000 / 125 = 2 BTC, worth 250 USD at a mark of 125.
+    assert_eq!(
+        exchange.last_closed_position_pnl(),
+        Some(BaseCurrency::new(2, 0))
+    );
+    assert_eq!(
+        exchange.realized_pnl_in_quote(QuoteCurrency::new(125, 0)),
+        QuoteCurrency::new(250, 0)
+    );
+    assert_eq!(
+        exchange.realized_pnl_in_quote(QuoteCurrency::new(100, 0)),
+        QuoteCurrency::new(200, 0)
+    );
+}
@@ -0,0 +1,47 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn realized_pnl_of_order() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    let opening_order = exchange.submit_market_order(order).unwrap();
+    assert_eq!(
+        exchange.realized_pnl_of_order(opening_order.state().meta().id()),
+        None
+    );
+
+    let order = LimitOrder::new(
+        Side::Sell,
+        QuoteCurrency::new(110, 0),
+        BaseCurrency::new(2, 0),
+    )
+    .unwrap();
+    let closing_order = exchange.submit_limit_order(order).unwrap();
+    assert_eq!(exchange.realized_pnl_of_order(closing_order.id()), None);
+
+    exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(111, 0),
+            quantity: BaseCurrency::new(2, 0),
+            side: Side::Buy,
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert_eq!(
+        exchange.realized_pnl_of_order(closing_order.id()),
+        Some(QuoteCurrency::new(18, 0))
+    );
+    assert_eq!(
+        exchange.realized_pnl_of_order(opening_order.state().meta().id()),
+        None
+    );
+}
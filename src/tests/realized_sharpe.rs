@@ -0,0 +1,86 @@
+use const_decimal::Decimal;
+
+use crate::{prelude::*, DECIMALS};
+
+const HOUR_NS: i64 = 3_600_000_000_000;
+
+/// Trades two round trips, then optionally keeps a large position open while the price swings.
+/// Returns the `realized_sharpe` and the sharpe ratio of the hourly sampled equity,
+/// which includes the unrealized pnl of the open position.
+fn run(hold_position: bool) -> (f64, f64) {
+    let contract_spec = ContractSpecification::new(
+        leverage!(1),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
+        Fee::from(Decimal::zero()),
+        Fee::from(Decimal::zero()),
+    )
+    .unwrap();
+    let starting_balance = QuoteCurrency::new(1000, 0);
+    let config = Config::new(starting_balance, 10, contract_spec, 3600).unwrap();
+    let mut exchange = ExchangeBuilder::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::new(config)
+        .with_account_tracker(FullAccountTracker::new(starting_balance))
+        .build();
+    // The round trips realize a profit of 9 and a loss of 6.
+    let mut last_orders = vec![(Side::Sell, 1)];
+    if hold_position {
+        last_orders.push((Side::Buy, 5));
+    }
+    let steps = [
+        (0, 100, vec![(Side::Buy, 1)]),
+        (1, 110, vec![(Side::Sell, 1)]),
+        (2, 100, vec![(Side::Buy, 1)]),
+        (3, 95, last_orders),
+        (4, 80, vec![]),
+        (5, 120, vec![]),
+        (6, 90, vec![]),
+    ];
+    let mut equities = Vec::new();
+    for (hour, bid, orders) in steps {
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(bid, 0),
+                ask: QuoteCurrency::new(bid + 1, 0),
+                timestamp_exchange_ns: (hour * HOUR_NS).into(),
+            })
+            .unwrap();
+        let upnl = exchange
+            .position()
+            .unrealized_pnl(exchange.market_state().bid(), exchange.market_state().ask());
+        equities.push(Into::<f64>::into(exchange.user_balances().sum() + upnl));
+        for (side, qty) in orders {
+            let order = MarketOrder::new(side, BaseCurrency::new(qty, 0)).unwrap();
+            exchange.submit_market_order(order).unwrap();
+        }
+    }
+
+    let ln_returns: Vec<f64> = equities.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+    let n = ln_returns.len() as f64;
+    let mean = ln_returns.iter().sum::<f64>() / n;
+    let std_dev = (ln_returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n).sqrt();
+    let equity_sharpe = mean / std_dev * ReturnsSource::Hourly.periods_per_year().sqrt();
+
+    (
+        exchange
+            .account_tracker()
+            .realized_sharpe(ReturnsSource::Hourly),
+        equity_sharpe,
+    )
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn realized_sharpe_excludes_open_position() {
+    let (flat_realized_sharpe, flat_equity_sharpe) = run(false);
+    let (holding_realized_sharpe, holding_equity_sharpe) = run(true);
+
+    // Without an open position, the equity only moves with the realized pnl.
+    assert!(flat_realized_sharpe > 0.0);
+    assert!((flat_realized_sharpe - flat_equity_sharpe).abs() < 1e-6);
+
+    // The open position bought 5 at 96 swings the equity down to 923, up to 1123 and back to 973,
+    // which drags the equity based sharpe ratio below zero, but leaves the realized one untouched.
+    assert!(holding_equity_sharpe < 0.0);
+    assert_eq!(holding_realized_sharpe, flat_realized_sharpe);
+}
@@ -0,0 +1,95 @@
+use const_decimal::Decimal;
+
+use crate::{prelude::*, test_fee_taker, DECIMALS};
+
+#[test_case::test_case(false, false; "not credited")]
+#[test_case::test_case(true, true; "accrue separately")]
+#[test_case::test_case(true, false; "credit wallet")]
+#[tracing_test::traced_test]
+fn rebates_maker_fills(credit_maker_rebates: bool, separate_rebate_account: bool) {
+    let contract_spec = ContractSpecification::new(
+        leverage!(1),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
+        Fee::from(Decimal::try_from_scaled(-1, 4).unwrap()),
+        test_fee_taker(),
+    )
+    .unwrap();
+    let mut config = Config::new(QuoteCurrency::new(1000, 0), 10, contract_spec, 3600).unwrap();
+    config.set_credit_maker_rebates(credit_maker_rebates);
+    config.set_separate_rebate_account(separate_rebate_account);
+    let mut exchange = Exchange::<
+        i64,
+        DECIMALS,
+        BaseCurrency<i64, DECIMALS>,
+        NoUserOrderId,
+        InMemoryTransactionAccounting<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>,
+        NoAccountTracker,
+    >::new(NoAccountTracker, config);
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    let qty = BaseCurrency::new(1, 0);
+    let order = LimitOrder::new(Side::Buy, QuoteCurrency::new(100, 0), qty).unwrap();
+    exchange.submit_limit_order(order).unwrap();
+    let updates = exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(99, 0),
+            quantity: qty,
+            side: Side::Sell,
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert_eq!(updates.len(), 1);
+    // Rebates are only settled when the position is reduced.
+    assert_eq!(exchange.accrued_rebates(), QuoteCurrency::zero());
+    assert_eq!(
+        exchange.position().outstanding_fees(),
+        QuoteCurrency::new(-1, 2)
+    );
+
+    let order = LimitOrder::new(Side::Sell, QuoteCurrency::new(102, 0), qty).unwrap();
+    exchange.submit_limit_order(order).unwrap();
+    let updates = exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(103, 0),
+            quantity: qty,
+            side: Side::Buy,
+            timestamp_exchange_ns: 2.into(),
+        })
+        .unwrap();
+    assert_eq!(updates.len(), 1);
+    assert_eq!(exchange.position(), &Position::Neutral);
+
+    let rebates = if credit_maker_rebates {
+        QuoteCurrency::new(202, 4)
+    } else {
+        QuoteCurrency::zero()
+    };
+    let pnl = QuoteCurrency::new(2, 0);
+    if separate_rebate_account {
+        assert_eq!(exchange.accrued_rebates(), rebates);
+        assert_eq!(
+            exchange.user_balances().available_wallet_balance,
+            QuoteCurrency::new(1000, 0) + pnl
+        );
+    } else {
+        assert_eq!(exchange.accrued_rebates(), QuoteCurrency::zero());
+        assert_eq!(
+            exchange.user_balances().available_wallet_balance,
+            QuoteCurrency::new(1000, 0) + pnl + rebates
+        );
+    }
+    // Total equity reconciles in both cases.
+    assert_eq!(
+        exchange.user_balances().sum() + exchange.accrued_rebates(),
+        QuoteCurrency::new(1000, 0) + pnl + rebates
+    );
+    assert_eq!(exchange.fees_paid(), QuoteCurrency::zero());
+}
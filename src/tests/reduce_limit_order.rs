@@ -0,0 +1,72 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn reduce_limit_order() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(100, 0),
+        BaseCurrency::new(5, 0),
+    )
+    .unwrap();
+    let order_id = exchange.submit_limit_order(order).unwrap().id();
+    assert_eq!(
+        exchange.user_balances(),
+        UserBalances {
+            available_wallet_balance: QuoteCurrency::new(500, 0),
+            position_margin: QuoteCurrency::zero(),
+            order_margin: QuoteCurrency::new(500, 0),
+            _q: std::marker::PhantomData
+        }
+    );
+
+    let reduced = exchange
+        .reduce_limit_order(order_id, BaseCurrency::new(2, 0))
+        .unwrap();
+    assert_eq!(reduced.id(), order_id);
+    assert_eq!(reduced.remaining_quantity(), BaseCurrency::new(2, 0));
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+    assert_eq!(
+        exchange.active_limit_orders().get_by_id(order_id).unwrap(),
+        &reduced
+    );
+    assert_eq!(
+        exchange.user_balances(),
+        UserBalances {
+            available_wallet_balance: QuoteCurrency::new(800, 0),
+            position_margin: QuoteCurrency::zero(),
+            order_margin: QuoteCurrency::new(200, 0),
+            _q: std::marker::PhantomData
+        }
+    );
+
+    assert_eq!(
+        exchange.reduce_limit_order(order_id, BaseCurrency::new(2, 0)),
+        Err(OrderError::ReducedQuantityNotLess.into())
+    );
+    assert_eq!(
+        exchange.reduce_limit_order(order_id, BaseCurrency::new(3, 0)),
+        Err(OrderError::ReducedQuantityNotLess.into())
+    );
+    assert_eq!(
+        exchange.reduce_limit_order(order_id, BaseCurrency::zero()),
+        Err(OrderError::OrderQuantityLTEZero.into())
+    );
+    assert_eq!(
+        exchange.reduce_limit_order(1.into(), BaseCurrency::new(1, 0)),
+        Err(Error::OrderIdNotFound { order_id: 1.into() })
+    );
+    assert_eq!(
+        exchange.active_limit_orders().get_by_id(order_id).unwrap(),
+        &reduced
+    );
+}
@@ -0,0 +1,77 @@
+use const_decimal::Decimal;
+
+use crate::{prelude::*, test_fee_maker, test_fee_taker, DECIMALS};
+
+#[test]
+#[tracing_test::traced_test]
+fn reduce_only_margin_ratio() {
+    let contract_spec = ContractSpecification::new(
+        leverage!(5),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
+        test_fee_maker(),
+        test_fee_taker(),
+    )
+    .unwrap();
+    let mut config = Config::new(QuoteCurrency::new(1000, 0), 10, contract_spec, 3600).unwrap();
+    config.set_reduce_only_margin_ratio(Some(Decimal::try_from_scaled(2, 1).unwrap()));
+    let mut exchange =
+        ExchangeBuilder::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::new(config).build();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.margin_ratio(), None);
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(40, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    let increasing = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(90, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    let increasing_id = exchange.submit_limit_order(increasing).unwrap().id();
+    let reducing = LimitOrder::new(
+        Side::Sell,
+        QuoteCurrency::new(110, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    let reducing_id = exchange.submit_limit_order(reducing).unwrap().id();
+
+    // A margin ratio of 800 / 3860 is still above the threshold.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(96, 0),
+            ask: QuoteCurrency::new(97, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert!(exchange.margin_ratio().unwrap() > Decimal::try_from_scaled(2, 1).unwrap());
+    assert_eq!(exchange.active_limit_orders().len(), 2);
+
+    // A margin ratio of 760 / 3820 is below the threshold, but not yet liquidated.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(95, 0),
+            ask: QuoteCurrency::new(96, 0),
+            timestamp_exchange_ns: 2.into(),
+        })
+        .unwrap();
+    assert!(exchange.margin_ratio().unwrap() < Decimal::try_from_scaled(2, 1).unwrap());
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(40, 0));
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+    assert!(exchange
+        .active_limit_orders()
+        .get_by_id(increasing_id)
+        .is_none());
+    assert!(exchange
+        .active_limit_orders()
+        .get_by_id(reducing_id)
+        .is_some());
+}
@@ -0,0 +1,71 @@
+use crate::{mock_exchange_linear, prelude::*, test_fee_taker};
+
+#[test]
+#[tracing_test::traced_test]
+fn reduce_position_half_of_long() {
+    let mut exchange = mock_exchange_linear();
+    assert!(exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into()
+        })
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        exchange.reduce_position(BaseCurrency::new(1, 0)),
+        Err(Error::NoPositionToReduce)
+    );
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    let fee0 = QuoteCurrency::new(202, 0) * *test_fee_taker().as_ref();
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(110, 0),
+            ask: QuoteCurrency::new(111, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    let filled = exchange.reduce_position(BaseCurrency::new(1, 0)).unwrap();
+    assert_eq!(filled.side(), Side::Sell);
+    assert_eq!(filled.quantity(), BaseCurrency::new(1, 0));
+    let fee1 = QuoteCurrency::new(110, 0) * *test_fee_taker().as_ref();
+
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(1, 0));
+    assert_eq!(
+        exchange.position().entry_price(),
+        QuoteCurrency::new(101, 0)
+    );
+    // The realized profit of 9 is credited, along with the released position margin.
+    assert_eq!(
+        exchange.user_balances(),
+        UserBalances {
+            available_wallet_balance: QuoteCurrency::new(908, 0) - fee0 - fee1,
+            position_margin: QuoteCurrency::new(101, 0),
+            order_margin: QuoteCurrency::zero(),
+            _q: std::marker::PhantomData
+        }
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn reduce_position_capped_at_position_size() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    let filled = exchange.reduce_position(BaseCurrency::new(5, 0)).unwrap();
+    assert_eq!(filled.side(), Side::Buy);
+    assert_eq!(filled.quantity(), BaseCurrency::new(2, 0));
+    assert_eq!(exchange.position(), &Position::Neutral);
+}
@@ -0,0 +1,56 @@
+use crate::{mock_exchange_linear_with_config, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn reentry_cooldown() {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_reentry_cooldown_ns(1_000);
+    });
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    // The bid drops below the liquidation price of 50.5
+    assert_eq!(
+        exchange.update_state(&Bba {
+            bid: QuoteCurrency::new(50, 0),
+            ask: QuoteCurrency::new(51, 0),
+            timestamp_exchange_ns: 100.into(),
+        }),
+        Err(Error::RiskError(RiskError::Liquidate))
+    );
+    assert_eq!(exchange.position(), &Position::Neutral);
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap();
+    assert_eq!(
+        exchange.submit_market_order(order),
+        Err(Error::RiskError(RiskError::ReentryCooldownActive))
+    );
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(49, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    assert_eq!(
+        exchange.submit_limit_order(order),
+        Err(Error::RiskError(RiskError::ReentryCooldownActive))
+    );
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(50, 0),
+            ask: QuoteCurrency::new(51, 0),
+            timestamp_exchange_ns: 1_100.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(1, 0));
+}
@@ -0,0 +1,51 @@
+use test_case::test_case;
+
+use crate::{mock_exchange_linear_with_config, prelude::*};
+
+#[test_case(true; "With recording")]
+#[test_case(false; "Without recording")]
+#[tracing_test::traced_test]
+fn rejected_orders(record_rejected_orders: bool) {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_record_rejected_orders(record_rejected_orders);
+    });
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(99, 0),
+            ask: QuoteCurrency::new(100, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    // Accepted orders are not recorded.
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    let over_budget = MarketOrder::new(Side::Buy, BaseCurrency::new(10, 0)).unwrap();
+    assert_eq!(
+        exchange.submit_market_order(over_budget.clone()),
+        Err(Error::RiskError(RiskError::NotEnoughAvailableBalance))
+    );
+    let marketable = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(100, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    let marketable_err = exchange.submit_limit_order(marketable.clone()).unwrap_err();
+
+    if record_rejected_orders {
+        assert_eq!(
+            exchange.rejected_orders(),
+            &[
+                (
+                    RejectedOrder::Market(over_budget),
+                    Error::RiskError(RiskError::NotEnoughAvailableBalance)
+                ),
+                (RejectedOrder::Limit(marketable), marketable_err),
+            ]
+        );
+    } else {
+        assert!(exchange.rejected_orders().is_empty());
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn replace_order() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(90, 0),
+        BaseCurrency::new(10, 0),
+    )
+    .unwrap();
+    let old_id = exchange.submit_limit_order(order).unwrap().id();
+    assert_eq!(
+        exchange.user_balances(),
+        UserBalances {
+            available_wallet_balance: QuoteCurrency::new(100, 0),
+            position_margin: QuoteCurrency::zero(),
+            order_margin: QuoteCurrency::new(900, 0),
+            _q: std::marker::PhantomData
+        }
+    );
+
+    // The new order needs more margin than the wallet holds, which the old order margin covers.
+    let new_order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(95, 0),
+        BaseCurrency::new(10, 0),
+    )
+    .unwrap();
+    let replaced = exchange.replace_order(old_id, new_order).unwrap();
+    assert_ne!(replaced.id(), old_id);
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+    assert!(exchange.active_limit_orders().get_by_id(old_id).is_none());
+    assert_eq!(
+        exchange
+            .active_limit_orders()
+            .get_by_id(replaced.id())
+            .unwrap(),
+        &replaced
+    );
+    // Only the net difference of 50 moved from the wallet.
+    assert_eq!(
+        exchange.user_balances(),
+        UserBalances {
+            available_wallet_balance: QuoteCurrency::new(50, 0),
+            position_margin: QuoteCurrency::zero(),
+            order_margin: QuoteCurrency::new(950, 0),
+            _q: std::marker::PhantomData
+        }
+    );
+
+    // A smaller replacement releases the difference.
+    let new_order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(90, 0),
+        BaseCurrency::new(4, 0),
+    )
+    .unwrap();
+    let smaller = exchange.replace_order(replaced.id(), new_order).unwrap();
+    assert_eq!(smaller.remaining_quantity(), BaseCurrency::new(4, 0));
+    assert_eq!(
+        exchange.user_balances(),
+        UserBalances {
+            available_wallet_balance: QuoteCurrency::new(640, 0),
+            position_margin: QuoteCurrency::zero(),
+            order_margin: QuoteCurrency::new(360, 0),
+            _q: std::marker::PhantomData
+        }
+    );
+
+    assert_eq!(
+        exchange.replace_order(old_id, new_order_at(90)),
+        Err(Error::OrderNoLongerActive)
+    );
+    assert_eq!(
+        exchange.replace_order(100.into(), new_order_at(90)),
+        Err(Error::OrderIdNotFound {
+            order_id: 100.into()
+        })
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn replace_order_rejected_keeps_old_order() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    let old = exchange.submit_limit_order(new_order_at(90)).unwrap();
+    let balances = exchange.user_balances();
+
+    let too_large = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(90, 0),
+        BaseCurrency::new(12, 0),
+    )
+    .unwrap();
+    assert_eq!(
+        exchange.replace_order(old.id(), too_large),
+        Err(Error::RiskError(RiskError::NotEnoughAvailableBalance))
+    );
+    assert_eq!(
+        exchange.replace_order(old.id(), new_order_at(101)),
+        Err(Error::OrderError(
+            OrderError::GoodTillCrossingRejectedOrder {
+                limit_price: QuoteCurrency::<i64, 5>::new(101, 0).to_string(),
+                away_market_quotation_price: QuoteCurrency::<i64, 5>::new(101, 0).to_string(),
+            }
+        ))
+    );
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+    assert_eq!(
+        exchange.active_limit_orders().get_by_id(old.id()).unwrap(),
+        &old
+    );
+    assert_eq!(exchange.user_balances(), balances);
+}
+
+fn new_order_at(
+    limit_price: i64,
+) -> LimitOrder<i64, 5, BaseCurrency<i64, 5>, NoUserOrderId, NewOrder> {
+    LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(limit_price, 0),
+        BaseCurrency::new(5, 0),
+    )
+    .unwrap()
+}
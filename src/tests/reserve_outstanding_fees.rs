@@ -0,0 +1,49 @@
+use test_case::test_case;
+
+use crate::{mock_exchange_linear_with_config, prelude::*, test_fee_taker};
+
+#[test_case(true; "With reserved fees")]
+#[test_case(false; "Without reserved fees")]
+#[tracing_test::traced_test]
+fn reserve_outstanding_fees(reserve_outstanding_fees: bool) {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_reserve_outstanding_fees(reserve_outstanding_fees);
+    });
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    let fee = QuoteCurrency::convert_from(BaseCurrency::new(5, 0), QuoteCurrency::new(101, 0))
+        * *test_fee_taker().as_ref();
+    assert_eq!(exchange.position().outstanding_fees(), fee);
+    let expected_available = if reserve_outstanding_fees {
+        QuoteCurrency::new(495, 0) - fee
+    } else {
+        QuoteCurrency::new(495, 0)
+    };
+    assert_eq!(
+        exchange.user_balances(),
+        UserBalances {
+            available_wallet_balance: expected_available,
+            position_margin: QuoteCurrency::new(505, 0),
+            order_margin: QuoteCurrency::zero(),
+            _q: std::marker::PhantomData
+        }
+    );
+
+    // Once the fees are settled, both policies agree.
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    let exit_fee = QuoteCurrency::new(500, 0) * *test_fee_taker().as_ref();
+    assert_eq!(
+        exchange.user_balances().available_wallet_balance,
+        QuoteCurrency::new(995, 0) - fee - exit_fee
+    );
+}
@@ -0,0 +1,46 @@
+use crate::{mock_exchange_linear_with_account_tracker, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn reset_account_tracker() {
+    let mut exchange = mock_exchange_linear_with_account_tracker(QuoteCurrency::new(1000, 0));
+    assert!(exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into()
+        })
+        .unwrap()
+        .is_empty());
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.account_tracker().num_submitted_market_orders(), 1);
+    assert_eq!(exchange.account_tracker().num_filled_market_orders(), 1);
+    assert_eq!(
+        exchange.account_tracker().buy_volume(),
+        QuoteCurrency::new(505, 0)
+    );
+
+    exchange.reset_account_tracker();
+    let tracker = exchange.account_tracker();
+    assert_eq!(tracker.num_submitted_market_orders(), 0);
+    assert_eq!(tracker.num_filled_market_orders(), 0);
+    assert_eq!(tracker.buy_volume(), QuoteCurrency::zero());
+    assert_eq!(tracker.sell_volume(), QuoteCurrency::zero());
+    assert_eq!(
+        tracker.wallet_balance_start(),
+        exchange.user_balances().sum()
+    );
+    // The position is not affected by the reset.
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(5, 0));
+
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    let tracker = exchange.account_tracker();
+    assert_eq!(tracker.num_submitted_market_orders(), 1);
+    assert_eq!(tracker.num_filled_market_orders(), 1);
+    assert_eq!(tracker.buy_volume(), QuoteCurrency::zero());
+    assert_eq!(tracker.sell_volume(), QuoteCurrency::new(500, 0));
+    assert_eq!(exchange.position(), &Position::Neutral);
+}
@@ -0,0 +1,36 @@
+use crate::{mock_exchange_linear_with_config, prelude::*, DECIMALS};
+
+#[test_case::test_case(None, QuoteCurrency::new(246, 2))]
+#[test_case::test_case(Some(QuoteCurrency::new(1, 1)), QuoteCurrency::new(24, 1))]
+#[test_case::test_case(Some(QuoteCurrency::new(1, 0)), QuoteCurrency::new(2, 0))]
+#[tracing_test::traced_test]
+fn rounded_unrealized_pnl(
+    quantum: Option<QuoteCurrency<i64, DECIMALS>>,
+    expected: QuoteCurrency<i64, DECIMALS>,
+) {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_margin_display_quantum(quantum);
+    });
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(123, 2)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(103, 0),
+            ask: QuoteCurrency::new(104, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+
+    let raw = exchange
+        .position()
+        .unrealized_pnl(QuoteCurrency::new(103, 0), QuoteCurrency::new(104, 0));
+    assert_eq!(raw, QuoteCurrency::new(246, 2));
+    assert_eq!(exchange.rounded_unrealized_pnl(), expected);
+}
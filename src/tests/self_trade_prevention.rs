@@ -0,0 +1,178 @@
+use test_case::test_case;
+
+use crate::{mock_exchange_linear_with_config, prelude::*, DECIMALS};
+
+type TestExchange = Exchange<
+    i64,
+    DECIMALS,
+    BaseCurrency<i64, DECIMALS>,
+    NoUserOrderId,
+    InMemoryTransactionAccounting<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>,
+    NoAccountTracker,
+>;
+
+/// Places a resting order of `resting_side` and moves the market away from it without filling it,
+/// so that a new non-marketable order of the opposite side can cross it.
+/// Returns the limit price of the incoming order, which crosses the resting order.
+fn setup(
+    self_trade_prevention: SelfTradePrevention,
+    resting_side: Side,
+) -> (TestExchange, QuoteCurrency<i64, DECIMALS>) {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_self_trade_prevention(self_trade_prevention);
+    });
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    let (resting_price, bba, incoming_price) = match resting_side {
+        Side::Buy => (100, (95, 96), 99),
+        Side::Sell => (101, (106, 107), 102),
+    };
+    let order = LimitOrder::new(
+        resting_side,
+        QuoteCurrency::new(resting_price, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    exchange.submit_limit_order(order).unwrap();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(bba.0, 0),
+            ask: QuoteCurrency::new(bba.1, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+
+    (exchange, QuoteCurrency::new(incoming_price, 0))
+}
+
+#[test_case(Side::Buy)]
+#[test_case(Side::Sell)]
+#[tracing_test::traced_test]
+fn self_trade_prevention_disabled(resting_side: Side) {
+    let (mut exchange, limit_price) = setup(SelfTradePrevention::Disabled, resting_side);
+    let order = LimitOrder::new(
+        resting_side.inverted(),
+        limit_price,
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    exchange.submit_limit_order(order).unwrap();
+    assert_eq!(exchange.active_limit_orders().len(), 2);
+}
+
+#[test_case(Side::Buy)]
+#[test_case(Side::Sell)]
+#[tracing_test::traced_test]
+fn self_trade_prevention_cancel_resting(resting_side: Side) {
+    let (mut exchange, limit_price) = setup(SelfTradePrevention::CancelResting, resting_side);
+    let order = LimitOrder::new(
+        resting_side.inverted(),
+        limit_price,
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    let order = exchange.submit_limit_order(order).unwrap();
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+    assert_eq!(
+        exchange.active_limit_orders().get_by_id(order.id()),
+        Some(&order)
+    );
+    assert_eq!(
+        exchange.user_balances().order_margin,
+        QuoteCurrency::convert_from(BaseCurrency::new(1, 0), limit_price)
+    );
+}
+
+#[test_case(Side::Buy)]
+#[test_case(Side::Sell)]
+#[tracing_test::traced_test]
+fn self_trade_prevention_cancel_resting_keeps_resting_on_rejection(resting_side: Side) {
+    let (mut exchange, limit_price) = setup(SelfTradePrevention::CancelResting, resting_side);
+    let resting_order = exchange
+        .active_limit_orders()
+        .values()
+        .next()
+        .unwrap()
+        .clone();
+    let balances = exchange.user_balances();
+
+    // Even with the margin of the resting order released, the balance does not suffice.
+    let order = LimitOrder::new(
+        resting_side.inverted(),
+        limit_price,
+        BaseCurrency::new(11, 0),
+    )
+    .unwrap();
+    assert_eq!(
+        exchange.submit_limit_order(order),
+        Err(Error::RiskError(RiskError::NotEnoughAvailableBalance))
+    );
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+    assert_eq!(
+        exchange.active_limit_orders().get_by_id(resting_order.id()),
+        Some(&resting_order)
+    );
+    assert_eq!(exchange.user_balances(), balances);
+}
+
+#[test_case(Side::Buy)]
+#[test_case(Side::Sell)]
+#[tracing_test::traced_test]
+fn self_trade_prevention_cancel_incoming(resting_side: Side) {
+    let (mut exchange, limit_price) = setup(SelfTradePrevention::CancelIncoming, resting_side);
+    let resting_order = exchange
+        .active_limit_orders()
+        .values()
+        .next()
+        .unwrap()
+        .clone();
+    let order = LimitOrder::new(
+        resting_side.inverted(),
+        limit_price,
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    assert_eq!(
+        exchange.submit_limit_order(order),
+        Err(Error::OrderError(OrderError::SelfTradeIncomingCancelled))
+    );
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+    assert_eq!(
+        exchange.active_limit_orders().get_by_id(resting_order.id()),
+        Some(&resting_order)
+    );
+
+    // The cancelled incoming order did not consume an `OrderId`.
+    let passive_price = match resting_side {
+        Side::Buy => QuoteCurrency::new(90, 0),
+        Side::Sell => QuoteCurrency::new(110, 0),
+    };
+    let order = LimitOrder::new(resting_side, passive_price, BaseCurrency::new(1, 0)).unwrap();
+    let order = exchange.submit_limit_order(order).unwrap();
+    assert_eq!(order.id(), OrderId::from(1));
+}
+
+#[test_case(Side::Buy)]
+#[test_case(Side::Sell)]
+#[tracing_test::traced_test]
+fn self_trade_prevention_reject(resting_side: Side) {
+    let (mut exchange, limit_price) = setup(SelfTradePrevention::Reject, resting_side);
+    let order = LimitOrder::new(
+        resting_side.inverted(),
+        limit_price,
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    assert_eq!(
+        exchange.submit_limit_order(order),
+        Err(Error::OrderError(OrderError::SelfTradeRejected))
+    );
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+}
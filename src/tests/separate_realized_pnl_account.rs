@@ -0,0 +1,84 @@
+use crate::{mock_exchange_linear_with_config, prelude::*, DECIMALS};
+
+#[test_case::test_case(false, QuoteCurrency::new(10177468, 4), QuoteCurrency::zero())]
+#[test_case::test_case(true, QuoteCurrency::new(9997468, 4), QuoteCurrency::new(18, 0))]
+#[tracing_test::traced_test]
+fn separate_realized_pnl_account(
+    separate: bool,
+    expected_wallet_balance: QuoteCurrency<i64, DECIMALS>,
+    expected_unswept_pnl: QuoteCurrency<i64, DECIMALS>,
+) {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_separate_realized_pnl_account(separate);
+    });
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(110, 0),
+            ask: QuoteCurrency::new(111, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    // Realizes a profit of 2 * (110 - 101) = 18, paying 0.2532 in taker fees.
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(2, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert_eq!(
+        exchange.user_balances().available_wallet_balance,
+        expected_wallet_balance
+    );
+    assert_eq!(exchange.unswept_realized_pnl(), expected_unswept_pnl);
+
+    assert_eq!(exchange.sweep_realized_pnl(), expected_unswept_pnl);
+    assert_eq!(exchange.unswept_realized_pnl(), QuoteCurrency::zero());
+    assert_eq!(
+        exchange.user_balances().available_wallet_balance,
+        QuoteCurrency::new(10177468, 4)
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn unswept_realized_pnl_counts_towards_equity() {
+    let margin_ratio_and_leverage = |separate: bool| {
+        let mut exchange = mock_exchange_linear_with_config(|config| {
+            config.set_separate_realized_pnl_account(separate);
+        });
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(100, 0),
+                ask: QuoteCurrency::new(101, 0),
+                timestamp_exchange_ns: 0.into(),
+            })
+            .unwrap();
+        let order = MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap();
+        exchange.submit_market_order(order).unwrap();
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(110, 0),
+                ask: QuoteCurrency::new(111, 0),
+                timestamp_exchange_ns: 1.into(),
+            })
+            .unwrap();
+        // Realize the profit, but keep part of the position open.
+        let order = MarketOrder::new(Side::Sell, BaseCurrency::new(1, 0)).unwrap();
+        exchange.submit_market_order(order).unwrap();
+        (
+            exchange.margin_ratio().unwrap(),
+            exchange.current_leverage(),
+            exchange.potential_leverage(),
+        )
+    };
+    assert_eq!(
+        margin_ratio_and_leverage(true),
+        margin_ratio_and_leverage(false)
+    );
+}
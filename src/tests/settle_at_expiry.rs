@@ -0,0 +1,98 @@
+use crate::{mock_exchange_linear_with_config, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn settle_at_expiry() {
+    let mut exchange = mock_exchange_linear_with_config(|config| {
+        config.set_expiry_ts(Some(100.into()));
+    });
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(99, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    exchange.submit_limit_order(order).unwrap();
+    let order = StopLimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(105, 0),
+        QuoteCurrency::new(106, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    exchange.submit_stop_limit_order(order).unwrap();
+
+    assert_eq!(
+        exchange.settle_at_expiry(QuoteCurrency::new(110, 0)),
+        Err(Error::ContractNotYetExpired)
+    );
+    assert!(!exchange.is_expired());
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 100.into(),
+        })
+        .unwrap();
+    exchange
+        .settle_at_expiry(QuoteCurrency::new(110, 0))
+        .unwrap();
+    assert!(exchange.is_expired());
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert!(exchange.active_limit_orders().is_empty());
+    assert!(exchange.active_stop_orders().is_empty());
+    // The realized pnl is 5 * (110 - 101) = 45, minus the fees of the entry.
+    let fees = QuoteCurrency::new(303, 3);
+    assert_eq!(
+        exchange.user_balances(),
+        UserBalances {
+            available_wallet_balance: QuoteCurrency::new(1045, 0) - fees,
+            position_margin: QuoteCurrency::zero(),
+            order_margin: QuoteCurrency::zero(),
+            _q: std::marker::PhantomData
+        }
+    );
+
+    // No more orders are accepted.
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap();
+    assert_eq!(
+        exchange.submit_market_order(order),
+        Err(Error::ContractExpired)
+    );
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(99, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    assert_eq!(
+        exchange.submit_limit_order(order),
+        Err(Error::ContractExpired)
+    );
+    assert_eq!(
+        exchange.settle_at_expiry(QuoteCurrency::new(110, 0)),
+        Err(Error::ContractExpired)
+    );
+
+    // The cleared stop-limit order can no longer trigger.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(106, 0),
+            ask: QuoteCurrency::new(107, 0),
+            timestamp_exchange_ns: 101.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert!(exchange.active_limit_orders().is_empty());
+}
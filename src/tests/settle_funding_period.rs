@@ -0,0 +1,57 @@
+use const_decimal::Decimal;
+use test_case::test_case;
+
+use crate::{mock_exchange_inverse, prelude::*};
+
+#[test_case(Side::Buy, BaseCurrency::new(-5, 5))]
+#[test_case(Side::Sell, BaseCurrency::new(5, 5))]
+#[tracing_test::traced_test]
+fn settle_funding_period_inverse(side: Side, expected_payment: BaseCurrency<i64, 5>) {
+    let mut exchange = mock_exchange_inverse(BaseCurrency::new(1, 0));
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(99, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    // Nothing to settle without a position.
+    assert_eq!(
+        exchange.settle_funding_period(Decimal::try_from_scaled(1, 4).unwrap()),
+        BaseCurrency::zero()
+    );
+
+    let order = MarketOrder::new(side, QuoteCurrency::new(50, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    let balances = exchange.user_balances();
+
+    // The notional of 50 contracts at a mark price of 100 is 0.5 BTC.
+    assert_eq!(
+        exchange.settle_funding_period(Decimal::try_from_scaled(1, 4).unwrap()),
+        expected_payment
+    );
+    let mut expected_balances = balances.clone();
+    expected_balances.available_wallet_balance += expected_payment;
+    assert_eq!(exchange.user_balances(), expected_balances);
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn settle_funding_period_negative_rate() {
+    let mut exchange = mock_exchange_inverse(BaseCurrency::new(1, 0));
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(99, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Buy, QuoteCurrency::new(50, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    // With a negative rate, shorts pay longs.
+    assert_eq!(
+        exchange.settle_funding_period(Decimal::try_from_scaled(-1, 4).unwrap()),
+        BaseCurrency::new(5, 5)
+    );
+}
@@ -0,0 +1,69 @@
+use const_decimal::Decimal;
+
+use crate::{prelude::*, DECIMALS};
+
+const HOUR_NS: i64 = 3_600_000_000_000;
+
+/// Trades two round trips, then optionally keeps a large position open while the price swings.
+/// Returns the sharpe ratio and the unrealized pnl at the end.
+fn run(hold_position: bool) -> (Option<f32>, QuoteCurrency<i64, DECIMALS>) {
+    let contract_spec = ContractSpecification::new(
+        leverage!(1),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
+        Fee::from(Decimal::zero()),
+        Fee::from(Decimal::zero()),
+    )
+    .unwrap();
+    let starting_balance = QuoteCurrency::new(1000, 0);
+    let config = Config::new(starting_balance, 10, contract_spec, 3600).unwrap();
+    let mut exchange = ExchangeBuilder::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::new(config)
+        .with_account_tracker(FullAccountTracker::new(starting_balance))
+        .build();
+    // Each step updates the market and then submits its market orders.
+    // The round trips realize a profit of 9 and a loss of 6.
+    let mut last_orders = vec![(Side::Sell, 1)];
+    if hold_position {
+        last_orders.push((Side::Buy, 5));
+    }
+    let steps = [
+        (0, 100, vec![(Side::Buy, 1)]),
+        (1, 110, vec![(Side::Sell, 1)]),
+        (2, 100, vec![(Side::Buy, 1)]),
+        (3, 95, last_orders),
+        (4, 80, vec![]),
+        (5, 120, vec![]),
+        (6, 90, vec![]),
+    ];
+    for (hour, bid, orders) in steps {
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(bid, 0),
+                ask: QuoteCurrency::new(bid + 1, 0),
+                timestamp_exchange_ns: (hour * HOUR_NS).into(),
+            })
+            .unwrap();
+        for (side, qty) in orders {
+            let order = MarketOrder::new(side, BaseCurrency::new(qty, 0)).unwrap();
+            exchange.submit_market_order(order).unwrap();
+        }
+    }
+    let upnl = exchange
+        .position()
+        .unrealized_pnl(exchange.market_state().bid(), exchange.market_state().ask());
+    (exchange.account_tracker().sharpe(), upnl)
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn sharpe_excludes_unrealized_pnl() {
+    let (flat_sharpe, flat_upnl) = run(false);
+    let (holding_sharpe, holding_upnl) = run(true);
+    assert_eq!(flat_upnl, QuoteCurrency::zero());
+    // The open position bought 5 at 96 and is valued at a bid of 90.
+    assert_eq!(holding_upnl, QuoteCurrency::new(-30, 0));
+
+    assert!(flat_sharpe.is_some());
+    assert_eq!(holding_sharpe, flat_sharpe);
+}
@@ -0,0 +1,69 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn stop_limit_order() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    let order = StopLimitOrder::new(
+        Side::Sell,
+        QuoteCurrency::new(95, 0),
+        QuoteCurrency::new(96, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    exchange.submit_stop_limit_order(order.clone()).unwrap();
+    assert_eq!(exchange.active_stop_orders(), &vec![order]);
+    assert!(exchange.active_limit_orders().is_empty());
+
+    // The bid is still above the trigger price.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(96, 0),
+            ask: QuoteCurrency::new(97, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.active_stop_orders().len(), 1);
+    assert!(exchange.active_limit_orders().is_empty());
+
+    // The trigger fires and the limit order rests in the book.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(95, 0),
+            ask: QuoteCurrency::new(97, 0),
+            timestamp_exchange_ns: 2.into(),
+        })
+        .unwrap();
+    assert!(exchange.active_stop_orders().is_empty());
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+    let limit_order = exchange
+        .active_limit_orders()
+        .values()
+        .next()
+        .unwrap()
+        .clone();
+    assert_eq!(limit_order.side(), Side::Sell);
+    assert_eq!(limit_order.limit_price(), QuoteCurrency::new(96, 0));
+    assert_eq!(limit_order.remaining_quantity(), BaseCurrency::new(1, 0));
+    assert_eq!(exchange.position(), &Position::Neutral);
+
+    // A subsequent buy trade fills the resting limit order.
+    exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(97, 0),
+            quantity: BaseCurrency::new(1, 0),
+            side: Side::Buy,
+            timestamp_exchange_ns: 3.into(),
+        })
+        .unwrap();
+    assert!(exchange.active_limit_orders().is_empty());
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(-1, 0));
+}
@@ -278,3 +278,29 @@ fn submit_market_buy_order_turnaround_short() {
         }
     );
 }
+
+#[test]
+#[tracing_test::traced_test]
+fn submit_market_buy_order_last_market_fill_price() {
+    let mut exchange = mock_exchange_linear();
+    assert!(exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into()
+        })
+        .unwrap()
+        .is_empty());
+    assert_eq!(exchange.last_market_fill_price(), None);
+
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap();
+    let filled_order = exchange.submit_market_order(order).unwrap();
+    assert_eq!(
+        exchange.last_market_fill_price(),
+        Some(filled_order.state().avg_fill_price())
+    );
+    assert_eq!(
+        exchange.last_market_fill_price(),
+        Some(QuoteCurrency::new(101, 0))
+    );
+}
@@ -0,0 +1,76 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn trading_halt() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let limit_order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(95, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    let resting = exchange.submit_limit_order(limit_order.clone()).unwrap();
+
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap())
+        .unwrap();
+    exchange.set_halt(10.into());
+    assert_eq!(
+        exchange.submit_market_order(MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap()),
+        Err(Error::TradingHalted)
+    );
+    assert_eq!(
+        exchange.submit_limit_order(limit_order.clone()),
+        Err(Error::TradingHalted)
+    );
+    assert_eq!(
+        exchange.amend_limit_order(resting.id(), limit_order.clone()),
+        Err(Error::TradingHalted)
+    );
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+
+    // Market updates are still processed during the halt, which lasts up to `until_ts`.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(110, 0),
+            ask: QuoteCurrency::new(111, 0),
+            timestamp_exchange_ns: 10.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        exchange.bba(),
+        (QuoteCurrency::new(110, 0), QuoteCurrency::new(111, 0))
+    );
+    assert_eq!(
+        exchange
+            .position()
+            .unrealized_pnl(exchange.bba().0, exchange.bba().1),
+        QuoteCurrency::new(9, 0)
+    );
+    assert_eq!(
+        exchange.submit_limit_order(limit_order.clone()),
+        Err(Error::TradingHalted)
+    );
+
+    // The halt lifts once the market timestamp passes `until_ts`.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(110, 0),
+            ask: QuoteCurrency::new(111, 0),
+            timestamp_exchange_ns: 11.into(),
+        })
+        .unwrap();
+    exchange.submit_limit_order(limit_order).unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, BaseCurrency::new(1, 0)).unwrap())
+        .unwrap();
+    assert_eq!(exchange.position(), &Position::Neutral);
+}
@@ -0,0 +1,169 @@
+use const_decimal::Decimal;
+
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn trailing_stop_percent() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = StopLimitOrder::new(
+        Side::Sell,
+        QuoteCurrency::new(95, 0),
+        QuoteCurrency::new(96, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap()
+    .with_trailing_distance(TrailingDistance::Percent(
+        Decimal::try_from_scaled(2, 2).unwrap(),
+    ))
+    .unwrap();
+    exchange.submit_stop_limit_order(order).unwrap();
+
+    // Rising prices ratchet the trigger to 2% below the highest bid.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(150, 0),
+            ask: QuoteCurrency::new(151, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        exchange.active_stop_orders()[0].trigger_price(),
+        QuoteCurrency::new(147, 0)
+    );
+    assert_eq!(
+        exchange.active_stop_orders()[0].limit_price(),
+        QuoteCurrency::new(148, 0)
+    );
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(200, 0),
+            ask: QuoteCurrency::new(201, 0),
+            timestamp_exchange_ns: 2.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        exchange.active_stop_orders()[0].trigger_price(),
+        QuoteCurrency::new(196, 0)
+    );
+    assert_eq!(
+        exchange.active_stop_orders()[0].limit_price(),
+        QuoteCurrency::new(197, 0)
+    );
+
+    // A pullback of less than 2% keeps the trigger in place.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(197, 0),
+            ask: QuoteCurrency::new(198, 0),
+            timestamp_exchange_ns: 3.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        exchange.active_stop_orders()[0].trigger_price(),
+        QuoteCurrency::new(196, 0)
+    );
+    assert!(exchange.active_limit_orders().is_empty());
+
+    // A pullback of 2% from the high-water mark triggers the stop.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(196, 0),
+            ask: QuoteCurrency::new(198, 0),
+            timestamp_exchange_ns: 4.into(),
+        })
+        .unwrap();
+    assert!(exchange.active_stop_orders().is_empty());
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+    let limit_order = exchange.active_limit_orders().values().next().unwrap();
+    assert_eq!(limit_order.side(), Side::Sell);
+    assert_eq!(limit_order.limit_price(), QuoteCurrency::new(197, 0));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn trailing_stop_absolute() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = StopLimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(110, 0),
+        QuoteCurrency::new(109, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap()
+    .with_trailing_distance(TrailingDistance::Absolute(QuoteCurrency::new(5, 0)))
+    .unwrap();
+    exchange.submit_stop_limit_order(order).unwrap();
+
+    // Falling prices ratchet the trigger to 5 above the lowest ask.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(89, 0),
+            ask: QuoteCurrency::new(90, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        exchange.active_stop_orders()[0].trigger_price(),
+        QuoteCurrency::new(95, 0)
+    );
+    assert_eq!(
+        exchange.active_stop_orders()[0].limit_price(),
+        QuoteCurrency::new(94, 0)
+    );
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(92, 0),
+            ask: QuoteCurrency::new(93, 0),
+            timestamp_exchange_ns: 2.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        exchange.active_stop_orders()[0].trigger_price(),
+        QuoteCurrency::new(95, 0)
+    );
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(94, 0),
+            ask: QuoteCurrency::new(95, 0),
+            timestamp_exchange_ns: 3.into(),
+        })
+        .unwrap();
+    assert!(exchange.active_stop_orders().is_empty());
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+}
+
+#[test]
+fn trailing_stop_invalid_distance() {
+    let order = StopLimitOrder::new(
+        Side::Sell,
+        QuoteCurrency::<i64, 5>::new(95, 0),
+        QuoteCurrency::new(96, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    assert_eq!(
+        order
+            .clone()
+            .with_trailing_distance(TrailingDistance::Percent(Decimal::one())),
+        Err(OrderError::InvalidTrailingDistance)
+    );
+    assert_eq!(
+        order.with_trailing_distance(TrailingDistance::Absolute(QuoteCurrency::zero())),
+        Err(OrderError::InvalidTrailingDistance)
+    );
+}
@@ -0,0 +1,55 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn update_state_with_trades() {
+    let mut manual = mock_exchange_linear();
+    let mut exchange = mock_exchange_linear();
+    let bba = Bba {
+        bid: QuoteCurrency::new(100, 0),
+        ask: QuoteCurrency::new(101, 0),
+        timestamp_exchange_ns: 0.into(),
+    };
+    for exchange in [&mut manual, &mut exchange] {
+        exchange.update_state(&bba).unwrap();
+        let order = LimitOrder::new(
+            Side::Buy,
+            QuoteCurrency::new(100, 0),
+            BaseCurrency::new(2, 0),
+        )
+        .unwrap();
+        exchange.submit_limit_order(order).unwrap();
+    }
+
+    let trades = [
+        Trade {
+            price: QuoteCurrency::new(99, 0),
+            quantity: BaseCurrency::new(1, 0),
+            side: Side::Sell,
+            timestamp_exchange_ns: 1.into(),
+        },
+        Trade {
+            price: QuoteCurrency::new(101, 0),
+            quantity: BaseCurrency::new(3, 0),
+            side: Side::Buy,
+            timestamp_exchange_ns: 2.into(),
+        },
+    ];
+    let bba = Bba {
+        bid: QuoteCurrency::new(99, 0),
+        ask: QuoteCurrency::new(100, 0),
+        timestamp_exchange_ns: 2.into(),
+    };
+    let price_filter = manual.config().contract_spec().price_filter().clone();
+    let smart_candle = SmartCandle::new(&trades, bba, &price_filter);
+    let manual_updates = manual.update_state(&smart_candle).unwrap().clone();
+    let updates = exchange
+        .update_state_with_trades(&trades, bba)
+        .unwrap()
+        .clone();
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates, manual_updates);
+    assert_eq!(exchange.position(), manual.position());
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(1, 0));
+    assert_eq!(exchange.user_balances(), manual.user_balances());
+}
@@ -5,6 +5,9 @@ pub enum ConfigError {
     #[error("The specified leverage must be > 0")]
     InvalidLeverage,
 
+    #[error("The initial margin requirement derived from the leverage rounds to zero")]
+    InvalidInitMarginReq,
+
     #[error("The provided starting balance must be > 0")]
     InvalidStartingBalance,
 
@@ -28,4 +31,13 @@ pub enum ConfigError {
 
     #[error("The maintenance margin fraction is invalid")]
     InvalidMaintenanceMarginFraction,
+
+    #[error("The chosen leverage exceeds the maximum leverage of the contract")]
+    LeverageTooHigh,
+
+    #[error("The liquidation buffer must be >= 0 and smaller than the maintenance margin")]
+    InvalidLiquidationBuffer,
+
+    #[error("The profit withdrawal fraction must be in range (0..1] and its interval positive")]
+    InvalidProfitWithdrawal,
 }
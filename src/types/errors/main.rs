@@ -46,4 +46,37 @@ pub enum Error {
 
     #[error("Unable to create `Decimal`")]
     UnableToCreateDecimal,
+
+    #[error("The contract has expired and no longer accepts orders.")]
+    ContractExpired,
+
+    #[error("The contract has not yet reached its expiry timestamp.")]
+    ContractNotYetExpired,
+
+    #[error("There is no open position to reduce.")]
+    NoPositionToReduce,
+
+    #[error("The quantity to reduce exceeds the quantity of the hedge leg.")]
+    HedgeLegQuantityExceeded,
+
+    #[error("Orders can only be routed to a hedge leg if hedge mode is enabled.")]
+    HedgeModeDisabled,
+
+    #[error("In hedge mode, orders must be routed to a hedge leg.")]
+    HedgeModeEnabled,
+
+    #[error("The equity of the account dropped below the minimum wallet balance.")]
+    AccountBlownUp,
+
+    #[error("The market is not initialized, as no best bid and ask have been received yet.")]
+    MarketNotInitialized,
+
+    #[error("The maximum number of orders per second is exceeded.")]
+    RateLimitExceeded,
+
+    #[error("An active limit order already uses the same user order id.")]
+    DuplicateUserOrderId,
+
+    #[error("Trading is halted and no orders are accepted.")]
+    TradingHalted,
 }
@@ -37,4 +37,16 @@ pub enum OrderError {
 
     #[error("The order quantity does not conform to the step size")]
     InvalidQuantityStepSize,
+
+    #[error("The limit order would cross a resting order of the same user and was rejected.")]
+    SelfTradeRejected,
+
+    #[error("The limit order would cross a resting order of the same user and was cancelled.")]
+    SelfTradeIncomingCancelled,
+
+    #[error("The reduced order quantity must be less than the remaining quantity.")]
+    ReducedQuantityNotLess,
+
+    #[error("The trailing distance must be positive and a percentage less than one.")]
+    InvalidTrailingDistance,
 }
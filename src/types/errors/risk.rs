@@ -7,4 +7,10 @@ pub enum RiskError {
 
     #[error("The position will be liquidated!")]
     Liquidate,
+
+    #[error("New orders are rejected during the re-entry cooldown after a liquidation.")]
+    ReentryCooldownActive,
+
+    #[error("There is no liquidity in the book to fill the market order.")]
+    NoLiquidity,
 }
@@ -0,0 +1,27 @@
+use super::{Currency, Mon, OrderId, QuoteCurrency, Side, TimestampNs};
+
+/// A single fill of a user order, either of a `MarketOrder` or a `LimitOrder`.
+///
+/// Generics:
+/// - `I`: The numeric data type of currencies.
+/// - `D`: The constant decimal precision of the currencies.
+/// - `BaseOrQuote`: Either `BaseCurrency` or `QuoteCurrency` depending on the futures type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutedTrade<I, const D: u8, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    /// The id of the order that was filled.
+    pub order_id: OrderId,
+    /// The side of the filled order.
+    pub side: Side,
+    /// The price at which the fill occurred.
+    pub price: QuoteCurrency<I, D>,
+    /// The filled quantity.
+    pub quantity: BaseOrQuote,
+    /// The fee charged for the fill, negative for maker rebates.
+    pub fee: BaseOrQuote::PairedCurrency,
+    /// The nanosecond timestamp of the exchange at which the fill occurred.
+    pub timestamp_exchange_ns: TimestampNs,
+}
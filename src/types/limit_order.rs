@@ -3,9 +3,11 @@ use num_traits::Zero;
 
 use super::{
     order_meta::ExchangeOrderMeta, order_status::NewOrder, Currency, Filled, FilledQuantity,
-    MarginCurrency, Mon, OrderId, Pending, QuoteCurrency, RePricing, TimestampNs, UserOrderIdT,
+    MarginCurrency, Mon, OrderId, Pending, QueuePosition, QuoteCurrency, RePricing, TimestampNs,
+    UserOrderIdT,
 };
 use crate::{
+    order_filters::QuantityFilter,
     types::{OrderError, Side},
     utils::NoUserOrderId,
 };
@@ -103,6 +105,39 @@ where
             re_pricing: RePricing::default(),
         })
     }
+
+    /// Create a new limit order without a user_order_id for a `notional` value,
+    /// which is converted into a quantity at the `limit_price`.
+    ///
+    /// # Arguments:
+    /// - `side`: either buy or sell
+    /// - `limit_price`: price to execute at or better
+    /// - `notional`: The value of the order denoted in the paired currency of the quantity.
+    /// - `quantity_filter`: The quantity must pass the filter and is rounded towards zero to its `tick_size`,
+    /// unless the filter is in `notional_mode`.
+    ///
+    /// # Returns:
+    /// Either a successfully created order or an [`OrderError`]
+    pub fn from_notional(
+        side: Side,
+        limit_price: QuoteCurrency<I, D>,
+        notional: BaseOrQuote::PairedCurrency,
+        quantity_filter: &QuantityFilter<I, D, BaseOrQuote>,
+    ) -> Result<Self, OrderError> {
+        if limit_price <= QuoteCurrency::zero() {
+            return Err(OrderError::LimitPriceLTEZero);
+        }
+        let mut quantity = BaseOrQuote::convert_from(notional, limit_price);
+        if !quantity_filter.notional_mode() {
+            quantity = BaseOrQuote::from(
+                quantity
+                    .as_ref()
+                    .quantize_round_to_zero(*quantity_filter.tick_size().as_ref()),
+            );
+        }
+        quantity_filter.validate_order_quantity_at(quantity, limit_price)?;
+        Self::new(side, limit_price, quantity)
+    }
 }
 
 impl<I, const D: u8, BaseOrQuote, UserOrderId> LimitOrder<I, D, BaseOrQuote, UserOrderId, NewOrder>
@@ -187,6 +222,18 @@ where
     BaseOrQuote::PairedCurrency: MarginCurrency<I, D>,
     UserOrderId: UserOrderIdT,
 {
+    /// Reduce the `remaining_quantity` of the active order.
+    /// The `new_qty` must be GT than zero.
+    pub(crate) fn set_remaining_quantity(&mut self, new_qty: BaseOrQuote) {
+        assert!(new_qty > BaseOrQuote::zero());
+        self.remaining_quantity = new_qty;
+    }
+
+    /// Update the estimated `QueuePosition` of the order.
+    pub(crate) fn set_queue_position(&mut self, queue_position: QueuePosition<I, D, BaseOrQuote>) {
+        self.state.queue_position = Some(queue_position);
+    }
+
     /// Used when an order gets some `quantity` filled at a `price`.
     ///
     /// # Returns:
@@ -4,6 +4,7 @@ use super::{
     order_status::NewOrder, Currency, ExchangeOrderMeta, Filled, Mon, OrderError, Pending,
     QuoteCurrency, Side, TimestampNs, UserOrderIdT,
 };
+use crate::order_filters::QuantityFilter;
 
 /// Defines an market order aka taker order.
 /// Generics:
@@ -83,6 +84,36 @@ where
         })
     }
 
+    /// Create a new market order without a `user_order_id` for a `notional` value,
+    /// e.g. buying 500 USD worth of a linear contract.
+    ///
+    /// # Arguments.
+    /// - `side`: either buy or sell
+    /// - `notional`: The value of the order denoted in the paired currency of the quantity.
+    /// - `price`: The reference price at which the `notional` is converted into a quantity.
+    /// - `quantity_filter`: The quantity must pass the filter and is rounded towards zero to its `tick_size`,
+    /// unless the filter is in `notional_mode`.
+    ///
+    /// # Returns:
+    /// Either a successfully created instance or an [`OrderError`]
+    pub fn from_notional(
+        side: Side,
+        notional: BaseOrQuote::PairedCurrency,
+        price: QuoteCurrency<I, D>,
+        quantity_filter: &QuantityFilter<I, D, BaseOrQuote>,
+    ) -> Result<Self, OrderError> {
+        let mut quantity = BaseOrQuote::convert_from(notional, price);
+        if !quantity_filter.notional_mode() {
+            quantity = BaseOrQuote::from(
+                quantity
+                    .as_ref()
+                    .quantize_round_to_zero(*quantity_filter.tick_size().as_ref()),
+            );
+        }
+        quantity_filter.validate_order_quantity_at(quantity, price)?;
+        Self::new(side, quantity)
+    }
+
     /// Create a new limit order
     ///
     /// # Arguments:
@@ -1,4 +1,5 @@
 mod errors;
+mod executed_trade;
 mod fee;
 mod leverage;
 mod limit_order;
@@ -7,12 +8,20 @@ mod order_id;
 mod order_meta;
 mod order_status;
 mod order_update;
+mod pnl_realization_policy;
+mod position_snapshot;
+mod profit_withdrawal;
+mod queue_position;
 mod re_pricing;
+mod rejected_order;
+mod self_trade_prevention;
 mod side;
 mod smol_currency;
+mod stop_limit_order;
 mod timestamp_ns;
 
 pub use errors::*;
+pub use executed_trade::ExecutedTrade;
 pub use fee::{Fee, Maker, Taker};
 pub use leverage::Leverage;
 pub use limit_order::LimitOrder;
@@ -21,9 +30,16 @@ pub use order_id::OrderId;
 pub use order_meta::ExchangeOrderMeta;
 pub use order_status::{Filled, FilledQuantity, NewOrder, Pending};
 pub use order_update::LimitOrderUpdate;
+pub use pnl_realization_policy::PnlRealizationPolicy;
+pub use position_snapshot::PositionSnapshot;
+pub use profit_withdrawal::ProfitWithdrawal;
+pub use queue_position::QueuePosition;
 pub use re_pricing::RePricing;
+pub use rejected_order::RejectedOrder;
+pub use self_trade_prevention::SelfTradePrevention;
 pub use side::Side;
 pub use smol_currency::{BaseCurrency, Currency, MarginCurrency, Mon, QuoteCurrency};
+pub use stop_limit_order::{StopLimitOrder, TrailingDistance};
 pub use timestamp_ns::TimestampNs;
 
 /// Natural Logarithmic Returns newtype wrapping a borrowed slice of generic floats.
@@ -1,6 +1,8 @@
 use getset::{CopyGetters, Getters};
 
-use super::{order_meta::ExchangeOrderMeta, Currency, Mon, QuoteCurrency, TimestampNs};
+use super::{
+    order_meta::ExchangeOrderMeta, Currency, Mon, QueuePosition, QuoteCurrency, TimestampNs,
+};
 
 /// A new order has not been received by the exchange and has thus some pieces of information not available.
 /// This also means the various filters (e.g `PriceFilter` and `QuantityFilter`) have not been checked.
@@ -24,6 +26,10 @@ where
     /// Information about the filled quantity.
     #[getset(get = "pub")]
     pub(crate) filled_quantity: FilledQuantity<I, D, BaseOrQuote>,
+
+    /// The estimated queue position, if `Config::queue_position_model` is enabled.
+    #[getset(get = "pub")]
+    pub(crate) queue_position: Option<QueuePosition<I, D, BaseOrQuote>>,
 }
 
 impl<I, const D: u8, BaseOrQuote> Pending<I, D, BaseOrQuote>
@@ -36,6 +42,7 @@ where
         Self {
             meta,
             filled_quantity: FilledQuantity::Unfilled,
+            queue_position: None,
         }
     }
 }
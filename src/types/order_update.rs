@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use super::{Currency, Filled, LimitOrder, Mon, Pending, UserOrderIdT};
+use super::{Currency, Filled, LimitOrder, Mon, OrderId, Pending, UserOrderIdT};
 
 /// Contains the possible updates to limit orders.
 #[derive(Debug, Clone, Eq, PartialEq, derive_more::Display)]
@@ -11,7 +11,27 @@ where
     UserOrderId: UserOrderIdT + Display,
 {
     /// The limit order was partially filled.
-    PartiallyFilled(LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>),
+    /// Contains the order with its cumulative fill information and the quantity filled now.
+    #[display("{_0}, filled quantity: {_1}")]
+    PartiallyFilled(
+        LimitOrder<I, D, BaseOrQuote, UserOrderId, Pending<I, D, BaseOrQuote>>,
+        BaseOrQuote,
+    ),
     /// The limit order was fully filled.
     FullyFilled(LimitOrder<I, D, BaseOrQuote, UserOrderId, Filled<I, D, BaseOrQuote>>),
 }
+
+impl<I, const D: u8, BaseOrQuote, UserOrderId> LimitOrderUpdate<I, D, BaseOrQuote, UserOrderId>
+where
+    I: Mon<D> + Display,
+    BaseOrQuote: Currency<I, D> + Display,
+    UserOrderId: UserOrderIdT + Display,
+{
+    /// The `OrderId` of the updated limit order.
+    pub fn order_id(&self) -> OrderId {
+        match self {
+            Self::PartiallyFilled(order, _) => order.state().meta().id(),
+            Self::FullyFilled(order) => order.state().meta().id(),
+        }
+    }
+}
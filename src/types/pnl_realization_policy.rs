@@ -0,0 +1,9 @@
+/// Decides when the profit and loss of a partially closed position is realized.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PnlRealizationPolicy {
+    /// Realize the proportional profit and loss of the closed quantity immediately.
+    #[default]
+    Proportional,
+    /// Accrue the profit and loss of partial closes and only realize it once the position is fully closed.
+    OnFullClose,
+}
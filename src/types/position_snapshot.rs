@@ -0,0 +1,25 @@
+use super::{Currency, Mon, QuoteCurrency, Side};
+
+/// A plain, serializable snapshot of the position at the current mark price.
+///
+/// Generics:
+/// - `I`: The numeric data type of currencies.
+/// - `D`: The constant decimal precision of the currencies.
+/// - `BaseOrQuote`: Either `BaseCurrency` or `QuoteCurrency` depending on the futures type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PositionSnapshot<I, const D: u8, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    /// The side of the position, `None` if neutral.
+    pub side: Option<Side>,
+    /// The absolute quantity of the position.
+    pub quantity: BaseOrQuote,
+    /// The average entry price of the position.
+    pub entry_price: QuoteCurrency<I, D>,
+    /// The unrealized profit and loss at the current mark price.
+    pub unrealized_pnl: BaseOrQuote::PairedCurrency,
+    /// The margin reserved for the position.
+    pub margin: BaseOrQuote::PairedCurrency,
+}
@@ -0,0 +1,41 @@
+use const_decimal::Decimal;
+use getset::CopyGetters;
+use num_traits::{One, Zero};
+
+use super::{ConfigError, Mon};
+
+/// Withdraw a fraction of the realized profit from the users wallet at a fixed interval,
+/// so it no longer compounds into the margin of new orders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CopyGetters)]
+pub struct ProfitWithdrawal<I, const D: u8>
+where
+    I: Mon<D>,
+{
+    /// The fraction (in range (0..1]) of the profit realized within an interval to withdraw.
+    #[getset(get_copy = "pub")]
+    fraction: Decimal<I, D>,
+
+    /// The interval between withdrawals in nanoseconds.
+    #[getset(get_copy = "pub")]
+    interval_ns: i64,
+}
+
+impl<I, const D: u8> ProfitWithdrawal<I, D>
+where
+    I: Mon<D>,
+{
+    /// Create a new `ProfitWithdrawal`.
+    ///
+    /// # Returns:
+    /// `ConfigError::InvalidProfitWithdrawal` if the `fraction` is not in range (0..1]
+    /// or the `interval_ns` is not positive.
+    pub fn new(fraction: Decimal<I, D>, interval_ns: i64) -> Result<Self, ConfigError> {
+        if fraction <= Decimal::zero() || fraction > Decimal::one() || interval_ns <= 0 {
+            return Err(ConfigError::InvalidProfitWithdrawal);
+        }
+        Ok(Self {
+            fraction,
+            interval_ns,
+        })
+    }
+}
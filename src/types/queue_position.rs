@@ -0,0 +1,90 @@
+use getset::CopyGetters;
+
+use super::{Currency, Mon, QuoteCurrency};
+use crate::utils::max;
+
+/// An estimate of the position of a pending limit order in the queue of its price level.
+/// The quantity traded at the limit price since submission first consumes the `volume_ahead`
+/// and only then fills the order.
+///
+/// Generics:
+/// - `I`: The numeric data type of currencies.
+/// - `D`: The constant decimal precision of the currencies.
+/// - `BaseOrQuote`: Either `BaseCurrency` or `QuoteCurrency` depending on the futures type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CopyGetters)]
+pub struct QueuePosition<I, const D: u8, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    /// The quantity that was resting ahead of the order when it was submitted.
+    #[getset(get_copy = "pub")]
+    volume_ahead: BaseOrQuote,
+
+    /// The cumulative quantity traded at the limit price since submission.
+    #[getset(get_copy = "pub")]
+    traded_volume: BaseOrQuote,
+
+    _q: std::marker::PhantomData<QuoteCurrency<I, D>>,
+}
+
+impl<I, const D: u8, BaseOrQuote> QueuePosition<I, D, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    /// Create a new instance with the quantity resting ahead of the order.
+    pub fn new(volume_ahead: BaseOrQuote) -> Self {
+        assert!(volume_ahead >= BaseOrQuote::zero());
+        Self {
+            volume_ahead,
+            traded_volume: BaseOrQuote::zero(),
+            _q: std::marker::PhantomData,
+        }
+    }
+
+    /// The quantity that is still ahead of the order.
+    pub fn remaining_volume_ahead(&self) -> BaseOrQuote {
+        max(self.volume_ahead - self.traded_volume, BaseOrQuote::zero())
+    }
+
+    /// Record a `quantity` traded at the limit price.
+    ///
+    /// # Returns:
+    /// The part of `quantity` that exceeds the volume ahead and is available to fill the order.
+    pub(crate) fn record_traded_volume(&mut self, quantity: BaseOrQuote) -> BaseOrQuote {
+        let ahead = self.remaining_volume_ahead();
+        self.traded_volume += quantity;
+        max(quantity - ahead, BaseOrQuote::zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn queue_position_record_traded_volume() {
+        let mut qp = QueuePosition::<i64, 5, _>::new(BaseCurrency::new(10, 0));
+        assert_eq!(qp.remaining_volume_ahead(), BaseCurrency::new(10, 0));
+
+        assert_eq!(
+            qp.record_traded_volume(BaseCurrency::new(6, 0)),
+            BaseCurrency::zero()
+        );
+        assert_eq!(qp.remaining_volume_ahead(), BaseCurrency::new(4, 0));
+
+        assert_eq!(
+            qp.record_traded_volume(BaseCurrency::new(6, 0)),
+            BaseCurrency::new(2, 0)
+        );
+        assert_eq!(qp.remaining_volume_ahead(), BaseCurrency::zero());
+        assert_eq!(qp.traded_volume(), BaseCurrency::new(12, 0));
+
+        assert_eq!(
+            qp.record_traded_volume(BaseCurrency::new(3, 0)),
+            BaseCurrency::new(3, 0)
+        );
+    }
+}
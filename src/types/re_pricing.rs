@@ -2,11 +2,11 @@
 /// decide what to do.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum RePricing {
-    // TODO: impl `Marketable`, requiring proper order book and matching engine i suppose.
-    // A limit order is marketable if it can take liquidity from the book.
-    // if the entry price locks or crosses an away market quotation it will immediately be filled
-    // and the remaining quantity will rest in the book afterwards, if any.
-    // Marketable,
+    /// If at the time of entry an order locks or crosses an away market quotation,
+    /// it takes liquidity and is immediately filled in full at the best bid or ask,
+    /// paying the `fee_taker_limit` of the contract.
+    /// Otherwise it rests in the book like any other limit order.
+    Marketable,
     /// If at the time of entry an order locks or crosses an away market quotation, the
     /// order will be immediately canceled back to the member.
     /// Good-Til-Crossing (GTX), sometimes referred to as limit maker or post-only orders,
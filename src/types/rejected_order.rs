@@ -0,0 +1,22 @@
+use super::{Currency, LimitOrder, MarketOrder, Mon, NewOrder, UserOrderIdT};
+
+/// An order submission that the `Exchange` rejected,
+/// recorded if `Config::record_rejected_orders` is enabled.
+///
+/// Generics:
+/// - `I`: The numeric data type of currencies.
+/// - `D`: The constant decimal precision of the currencies.
+/// - `BaseOrQuote`: Either `BaseCurrency` or `QuoteCurrency` depending on the futures type.
+/// - `UserOrderId`: The type of user order id to use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RejectedOrder<I, const D: u8, BaseOrQuote, UserOrderId>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    UserOrderId: UserOrderIdT,
+{
+    /// A rejected `MarketOrder`.
+    Market(MarketOrder<I, D, BaseOrQuote, UserOrderId, NewOrder>),
+    /// A rejected `LimitOrder`.
+    Limit(LimitOrder<I, D, BaseOrQuote, UserOrderId, NewOrder>),
+}
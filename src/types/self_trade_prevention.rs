@@ -0,0 +1,15 @@
+/// Decides what happens when a new limit order would cross a resting limit order of the same user.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SelfTradePrevention {
+    /// Orders of the user may cross each other without any intervention.
+    #[default]
+    Disabled,
+    /// Cancel the resting orders that would be crossed and accept the incoming order.
+    /// If the incoming order is rejected, the resting orders stay active.
+    CancelResting,
+    /// Cancel the incoming order, keeping the resting orders.
+    /// Like with `Reject`, the incoming order is never accepted, so it consumes no `OrderId`.
+    CancelIncoming,
+    /// Reject the incoming order before it is accepted by the exchange.
+    Reject,
+}
@@ -30,6 +30,8 @@ use super::{Currency, MarginCurrency, Mon, QuoteCurrency};
     derive_more::Neg,
     derive_more::From,
     derive_more::AsRef,
+    Serialize,
+    Deserialize,
 )]
 #[mul(forward)]
 #[div(forward)]
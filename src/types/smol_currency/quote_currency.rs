@@ -30,6 +30,8 @@ use super::{BaseCurrency, Currency, MarginCurrency, Mon};
     derive_more::Neg,
     derive_more::From,
     derive_more::AsRef,
+    Serialize,
+    Deserialize,
 )]
 #[mul(forward)]
 #[div(forward)]
@@ -0,0 +1,201 @@
+use const_decimal::Decimal;
+use getset::CopyGetters;
+use num_traits::{One, Zero};
+
+use super::{
+    order_status::NewOrder, Currency, LimitOrder, MarginCurrency, Mon, OrderError, QuoteCurrency,
+    Side, UserOrderIdT,
+};
+use crate::utils::NoUserOrderId;
+
+/// Defines a stop-limit order, which rests with the exchange until the market reaches its
+/// `trigger_price` and then submits a `LimitOrder` at its `limit_price`.
+///
+/// Generics:
+/// - `I`: The numeric data type of currencies.
+/// - `D`: The constant decimal precision of the currencies.
+/// - `BaseOrQuote`: Either `BaseCurrency` or `QuoteCurrency` depending on the futures type.
+/// - `UserOrderId`: The type of user order id to use. Set to `()` if you don't need one.
+#[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
+pub struct StopLimitOrder<I, const D: u8, BaseOrQuote, UserOrderId>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    UserOrderId: UserOrderIdT,
+{
+    /// Order Id provided by the user, passed on to the triggered `LimitOrder`.
+    #[getset(get_copy = "pub")]
+    user_order_id: UserOrderId,
+
+    /// Whether its a buy or sell order.
+    #[getset(get_copy = "pub")]
+    side: Side,
+
+    /// A buy order triggers once the ask reaches or exceeds this price,
+    /// a sell order once the bid reaches or falls below it.
+    #[getset(get_copy = "pub")]
+    trigger_price: QuoteCurrency<I, D>,
+
+    /// The limit price of the `LimitOrder` submitted on trigger.
+    #[getset(get_copy = "pub")]
+    limit_price: QuoteCurrency<I, D>,
+
+    /// The quantity of the `LimitOrder` submitted on trigger.
+    #[getset(get_copy = "pub")]
+    quantity: BaseOrQuote,
+
+    /// If set, the `trigger_price` and `limit_price` follow the market at this distance
+    /// whenever it moves away from the trigger.
+    #[getset(get_copy = "pub")]
+    trailing_distance: Option<TrailingDistance<I, D>>,
+}
+
+/// The distance at which a trailing `StopLimitOrder` follows the best price reached by the market,
+/// being the highest bid for a sell and the lowest ask for a buy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrailingDistance<I, const D: u8>
+where
+    I: Mon<D>,
+{
+    /// A fixed offset from the best price reached.
+    Absolute(QuoteCurrency<I, D>),
+    /// A fraction of the best price reached in range (0..1), e.g. 0.02 for 2%.
+    /// The trigger price of a sell is then `high_water_mark * (1 - pct)`.
+    Percent(Decimal<I, D>),
+}
+
+impl<I, const D: u8, BaseOrQuote> StopLimitOrder<I, D, BaseOrQuote, NoUserOrderId>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    BaseOrQuote::PairedCurrency: MarginCurrency<I, D>,
+{
+    /// Create a new stop-limit order without a user_order_id.
+    ///
+    /// # Arguments:
+    /// - `side`: either buy or sell
+    /// - `trigger_price`: The price at which the limit order is submitted.
+    /// - `limit_price`: The price of the submitted limit order.
+    /// - `quantity`: A positive nonzero quantity of the amount of contracts this order is for.
+    ///
+    /// # Returns:
+    /// Either a successfully created order or an [`OrderError`]
+    pub fn new(
+        side: Side,
+        trigger_price: QuoteCurrency<I, D>,
+        limit_price: QuoteCurrency<I, D>,
+        quantity: BaseOrQuote,
+    ) -> Result<Self, OrderError> {
+        Self::new_with_user_order_id(side, trigger_price, limit_price, quantity, NoUserOrderId)
+    }
+}
+
+impl<I, const D: u8, BaseOrQuote, UserOrderId> StopLimitOrder<I, D, BaseOrQuote, UserOrderId>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    BaseOrQuote::PairedCurrency: MarginCurrency<I, D>,
+    UserOrderId: UserOrderIdT,
+{
+    /// Create a new stop-limit order.
+    ///
+    /// # Arguments:
+    /// - `side`: either buy or sell
+    /// - `trigger_price`: The price at which the limit order is submitted.
+    /// - `limit_price`: The price of the submitted limit order.
+    /// - `quantity`: How many contracts should be traded
+    /// - `user_order_id`: The user provided id. This value is ignored by the exchange.
+    ///
+    /// # Returns:
+    /// Either a successfully created order or an [`OrderError`]
+    pub fn new_with_user_order_id(
+        side: Side,
+        trigger_price: QuoteCurrency<I, D>,
+        limit_price: QuoteCurrency<I, D>,
+        quantity: BaseOrQuote,
+        user_order_id: UserOrderId,
+    ) -> Result<Self, OrderError> {
+        if trigger_price <= QuoteCurrency::zero() || limit_price <= QuoteCurrency::zero() {
+            return Err(OrderError::LimitPriceLTEZero);
+        }
+        if quantity <= BaseOrQuote::zero() {
+            return Err(OrderError::OrderQuantityLTEZero);
+        }
+        Ok(Self {
+            user_order_id,
+            side,
+            trigger_price,
+            limit_price,
+            quantity,
+            trailing_distance: None,
+        })
+    }
+
+    /// Let the order trail the market at the `trailing_distance`.
+    ///
+    /// # Returns:
+    /// `OrderError::InvalidTrailingDistance` if an absolute distance is not positive
+    /// or a percentage is not in range (0..1).
+    pub fn with_trailing_distance(
+        mut self,
+        trailing_distance: TrailingDistance<I, D>,
+    ) -> Result<Self, OrderError> {
+        let is_valid = match trailing_distance {
+            TrailingDistance::Absolute(offset) => offset > QuoteCurrency::zero(),
+            TrailingDistance::Percent(pct) => pct > Decimal::zero() && pct < Decimal::one(),
+        };
+        if !is_valid {
+            return Err(OrderError::InvalidTrailingDistance);
+        }
+        self.trailing_distance = Some(trailing_distance);
+        Ok(self)
+    }
+
+    /// Move the `trigger_price` towards the market if it moved away by more than the
+    /// `trailing_distance`, rounded to the `tick_size` towards zero.
+    /// The `limit_price` keeps its offset to the `trigger_price`.
+    pub(crate) fn trail(
+        &mut self,
+        bid: QuoteCurrency<I, D>,
+        ask: QuoteCurrency<I, D>,
+        tick_size: QuoteCurrency<I, D>,
+    ) {
+        let Some(trailing_distance) = self.trailing_distance else {
+            return;
+        };
+        let trigger_price = match (self.side, trailing_distance) {
+            (Side::Buy, TrailingDistance::Absolute(offset)) => ask + offset,
+            (Side::Buy, TrailingDistance::Percent(pct)) => ask * (Decimal::one() + pct),
+            (Side::Sell, TrailingDistance::Absolute(offset)) => bid - offset,
+            (Side::Sell, TrailingDistance::Percent(pct)) => bid * (Decimal::one() - pct),
+        }
+        .quantize_round_to_zero(tick_size);
+        let ratchets = match self.side {
+            Side::Buy => trigger_price < self.trigger_price,
+            Side::Sell => trigger_price > self.trigger_price,
+        };
+        if ratchets && trigger_price > QuoteCurrency::zero() {
+            self.limit_price += trigger_price - self.trigger_price;
+            self.trigger_price = trigger_price;
+        }
+    }
+
+    /// Whether the market has reached the `trigger_price`.
+    pub(crate) fn is_triggered(&self, bid: QuoteCurrency<I, D>, ask: QuoteCurrency<I, D>) -> bool {
+        match self.side {
+            Side::Buy => ask >= self.trigger_price,
+            Side::Sell => bid <= self.trigger_price,
+        }
+    }
+
+    /// The `LimitOrder` to submit once triggered.
+    pub(crate) fn into_limit_order(self) -> LimitOrder<I, D, BaseOrQuote, UserOrderId, NewOrder> {
+        LimitOrder::new_with_user_order_id(
+            self.side,
+            self.limit_price,
+            self.quantity,
+            self.user_order_id,
+        )
+        .expect("Is validated on construction")
+    }
+}
@@ -1,10 +1,25 @@
 use std::fmt::Display;
 
 use derive_more::{Add, AddAssign, Div, Mul, Sub};
+use serde::{Deserialize, Serialize};
 
 /// The type of a timestamp that is measured in nanoseconds.
 #[derive(
-    Default, Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Add, Sub, Div, AddAssign, Mul,
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Eq,
+    Add,
+    Sub,
+    Div,
+    AddAssign,
+    Mul,
+    Serialize,
+    Deserialize,
 )]
 #[div(forward)]
 #[mul(forward)]
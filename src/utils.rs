@@ -54,6 +54,25 @@ pub(crate) fn assert_user_wallet_balance<I, const D: u8, Acc, BaseOrQuote>(
     assert!(wallet_balance >= BaseOrQuote::zero());
 }
 
+/// Asserts that the users wallet balance plus the unrealized profit `credit` backing orders,
+/// as enabled with `Config::credit_unrealized_pnl`, is greater than zero.
+/// Only then may the wallet balance itself become negative.
+#[inline]
+pub(crate) fn assert_user_wallet_balance_with_credit<I, const D: u8, Acc, BaseOrQuote>(
+    transaction_accounting: &Acc,
+    credit: BaseOrQuote,
+) where
+    I: Mon<D>,
+    Acc: TransactionAccounting<I, D, BaseOrQuote>,
+    BaseOrQuote: MarginCurrency<I, D>,
+{
+    let wallet_balance = transaction_accounting
+        .margin_balance_of(USER_WALLET_ACCOUNT)
+        .expect("is valid");
+    assert!(credit >= BaseOrQuote::zero());
+    assert!(wallet_balance + credit >= BaseOrQuote::zero());
+}
+
 /// Create a `Decimal` from an `f64` value.
 // TODO: maybe upstream this impl to `const_decimal`
 #[inline(always)]